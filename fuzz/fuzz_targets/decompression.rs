@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sas::sas::sas7bdat::compression::{rdc_decompress, rle_decompress};
+
+// The first byte selects the scheme and the next two the row length; the rest is the
+// compressed row.
+fuzz_target!(|data: &[u8]| {
+    let Some((&[scheme, high, low], input)) = data.split_first_chunk() else {
+        return;
+    };
+    let output_len = u16::from_be_bytes([high, low]) as usize;
+    let output = if scheme & 0x01 == 0 {
+        rle_decompress(input, output_len)
+    } else {
+        rdc_decompress(input, output_len)
+    };
+    if let Ok(output) = output {
+        assert_eq!(output.len(), output_len);
+    }
+});
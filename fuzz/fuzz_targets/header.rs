@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sas::sas::sas7bdat::header::parse_header;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_header(data);
+});
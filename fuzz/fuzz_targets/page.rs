@@ -0,0 +1,43 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sas::sas::sas7bdat::page::{page_rows, read_page_header};
+use rust_sas::sas::sas7bdat::Sas7bdatContext;
+use rust_sas::types::{ReadStatCompression, SasEndian, SasHeaderInfo};
+
+// The first byte selects the word size, byte order, compression and mix page row count, the
+// next two the row length; the rest is the page.
+fuzz_target!(|data: &[u8]| {
+    let Some((&[flags, high, low], page)) = data.split_first_chunk() else {
+        return;
+    };
+    let is_64bit = flags & 0x01 != 0;
+    let header = SasHeaderInfo::builder()
+        .is_64bit(is_64bit)
+        .endianness(if flags & 0x02 != 0 {
+            SasEndian::Big
+        } else {
+            SasEndian::Little
+        })
+        .page_size(page.len() as u64)
+        .page_header_size(if is_64bit { 40 } else { 24 })
+        .subheader_pointer_size(if is_64bit { 24 } else { 12 })
+        .build()
+        .unwrap();
+
+    let mut ctx = Sas7bdatContext::from_header(&header);
+    ctx.compression = match (flags >> 2) & 0x03 {
+        0 => ReadStatCompression::None,
+        1 => ReadStatCompression::Rows,
+        _ => ReadStatCompression::Binary,
+    };
+    ctx.row_length = u16::from_be_bytes([high, low]) as usize;
+    ctx.mix_page_row_count = (flags >> 4) as u64;
+
+    let _ = read_page_header(page, &ctx);
+    if let Ok(rows) = page_rows(page, &ctx) {
+        for row in rows {
+            assert_eq!(row.len(), ctx.row_length);
+        }
+    }
+});
@@ -0,0 +1,17 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sas::sas::sas7bdat::Sas7bdatReader;
+use rust_sas::types::SasReadOptions;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(mut reader) = Sas7bdatReader::from_bytes(data.to_vec()) {
+        if let Ok(rows) = reader.rows(&SasReadOptions::default()) {
+            for row in rows {
+                if row.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+});
@@ -0,0 +1,59 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rust_sas::sas::sas7bdat::row::decode_row;
+use rust_sas::sas::sas7bdat::subheader::{Sas7bdatMetadataParser, SasSubheaderKind};
+use rust_sas::sas::sas7bdat::Sas7bdatContext;
+use rust_sas::types::{SasEndian, SasHeaderInfo};
+
+/// Rows longer than this are not decoded, to keep the harness itself from allocating too much.
+const MAX_DECODED_ROW_LENGTH: usize = 1 << 16;
+
+const KINDS: [SasSubheaderKind; 8] = [
+    SasSubheaderKind::RowSize,
+    SasSubheaderKind::ColumnSize,
+    SasSubheaderKind::Counts,
+    SasSubheaderKind::ColumnFormat,
+    SasSubheaderKind::ColumnAttributes,
+    SasSubheaderKind::ColumnText,
+    SasSubheaderKind::ColumnList,
+    SasSubheaderKind::ColumnName,
+];
+
+// The first byte selects the word size and byte order. It is followed by subheaders, each a
+// byte selecting its kind and a 16-bit big-endian length before its body.
+fuzz_target!(|data: &[u8]| {
+    let Some((&flags, mut rest)) = data.split_first() else {
+        return;
+    };
+    let is_64bit = flags & 0x01 != 0;
+    let header = SasHeaderInfo::builder()
+        .is_64bit(is_64bit)
+        .endianness(if flags & 0x02 != 0 {
+            SasEndian::Big
+        } else {
+            SasEndian::Little
+        })
+        .page_header_size(if is_64bit { 40 } else { 24 })
+        .subheader_pointer_size(if is_64bit { 24 } else { 12 })
+        .encoding("UTF-8".to_string())
+        .build()
+        .unwrap();
+    let mut ctx = Sas7bdatContext::from_header(&header);
+
+    let mut parser = Sas7bdatMetadataParser::new();
+    while let Some((&[kind, high, low], body)) = rest.split_first_chunk() {
+        let length = (u16::from_be_bytes([high, low]) as usize).min(body.len());
+        let kind = &KINDS[kind as usize % KINDS.len()];
+        let _ = parser.parse_subheader(kind, &body[..length], &ctx);
+        rest = &body[length..];
+    }
+
+    if let Ok((variables, _)) = parser.finish(&mut ctx) {
+        if ctx.row_length <= MAX_DECODED_ROW_LENGTH {
+            let row = vec![0; ctx.row_length];
+            let selection: Vec<usize> = (0..variables.len()).collect();
+            decode_row(&row, &ctx, &variables, &selection);
+        }
+    }
+});
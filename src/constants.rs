@@ -1,5 +1,6 @@
 // Code: constants.rs
 
+#[allow(clippy::module_inception)]
 pub mod constants {
     /// Constants for SAS7BDAT files
     #[derive(Debug)]
@@ -0,0 +1,285 @@
+use crate::sas::sas7bdat::Sas7bdatReader;
+use crate::sas::temporal::format_iso8601_with;
+use crate::types::{
+    ReadStatError, ReadStatValue, ReadStatValueType, ReadStatVariable, SasCsvOptions, SasError,
+    SasReadOptions, SasTemporalKind, SasTemporalPolicy, SasTemporalText,
+};
+use std::io::Write;
+
+/// Writes observations as comma-separated values, with a header line of variable names.
+/// Missing numeric values are written as empty fields and numerics with date, time and datetime
+/// formats as ISO 8601, applying the options' temporal policy to dates that cannot be rendered.
+/// Fields are quoted only when they need to be.
+#[derive(Debug)]
+pub struct SasCsvWriter<W: Write> {
+    writer: W,
+    variables: Vec<ReadStatVariable>,
+    temporal_kinds: Vec<Option<SasTemporalKind>>,
+    options: SasCsvOptions,
+}
+
+impl<W: Write> SasCsvWriter<W> {
+    /// Create a writer for rows holding the values of `variables`, in that order.
+    pub fn new(writer: W, variables: Vec<ReadStatVariable>, options: SasCsvOptions) -> Self {
+        let temporal_kinds = variables
+            .iter()
+            .map(ReadStatVariable::temporal_kind)
+            .collect();
+        Self {
+            writer,
+            variables,
+            temporal_kinds,
+            options,
+        }
+    }
+
+    /// Write the header and every row of `reader` selected by `read_options`.
+    ///
+    /// # Returns
+    /// The number of observations written.
+    pub fn write_all(
+        reader: &mut Sas7bdatReader,
+        read_options: &SasReadOptions,
+        writer: W,
+        options: SasCsvOptions,
+    ) -> Result<u64, SasError> {
+        let variables = reader
+            .resolve_columns(&read_options.columns)?
+            .into_iter()
+            .map(|index| reader.variables()[index].clone())
+            .collect();
+        let mut csv_writer = Self::new(writer, variables, options);
+        csv_writer.write_header()?;
+
+        let mut count = 0;
+        for row in reader.rows(read_options)? {
+            csv_writer
+                .write_row(&row?)
+                .map_err(|err| err.with_row(read_options.row_offset + count))?;
+            count += 1;
+        }
+        csv_writer.flush()?;
+        Ok(count)
+    }
+
+    /// Write the line of variable names.
+    pub fn write_header(&mut self) -> Result<(), SasError> {
+        let names: Vec<String> = self
+            .variables
+            .iter()
+            .map(|variable| variable.name.clone())
+            .collect();
+        self.write_line(&names)
+    }
+
+    /// Write one observation. `row` holds the values of the writer's variables, in order.
+    pub fn write_row(&mut self, row: &[ReadStatValue]) -> Result<(), SasError> {
+        if row.len() != self.variables.len() {
+            return Err(ReadStatError::ColumnCountMismatch.into());
+        }
+        let policy = self.options.temporal_policy;
+        let fields = row
+            .iter()
+            .zip(&self.temporal_kinds)
+            .zip(&self.variables)
+            .map(|((value, kind), variable)| {
+                text_value(value, *kind, policy)
+                    .map_err(|err| SasError::new(err).with_variable(variable.name.as_str()))
+            })
+            .collect::<Result<Vec<String>, SasError>>()?;
+        self.write_line(&fields)
+    }
+
+    pub fn flush(&mut self) -> Result<(), SasError> {
+        self.writer
+            .flush()
+            .map_err(|err| SasError::io(ReadStatError::Write, err))
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_line(&mut self, fields: &[String]) -> Result<(), SasError> {
+        let line: Vec<String> = fields.iter().map(|field| quote_field(field)).collect();
+        writeln!(self.writer, "{}", line.join(","))
+            .map_err(|err| SasError::io(ReadStatError::Write, err))
+    }
+}
+
+/// Render a value as text: strings as they are, missing numerics as an empty string, temporal
+/// numerics as ISO 8601 and other numbers in their shortest form. `policy` decides what happens
+/// to temporal numerics that cannot be rendered; those it treats as missing are empty.
+///
+/// # Returns
+/// `ReadStatError::BadTimestampValue` for such a value under `SasTemporalPolicy::Error`.
+pub fn text_value(
+    value: &ReadStatValue,
+    kind: Option<SasTemporalKind>,
+    policy: SasTemporalPolicy,
+) -> Result<String, ReadStatError> {
+    let text = match &value.value {
+        ReadStatValueType::String(text) => text.clone(),
+        ReadStatValueType::Double(number) if number.is_nan() => String::new(),
+        ReadStatValueType::Double(number) => match kind {
+            Some(kind) => match format_iso8601_with(*number, kind, policy)? {
+                Some(SasTemporalText::Iso8601(text)) => text,
+                Some(SasTemporalText::Raw(number)) => number.to_string(),
+                None => String::new(),
+            },
+            None => number.to_string(),
+        },
+        ReadStatValueType::Float(number) => number.to_string(),
+        ReadStatValueType::Int8(number) => number.to_string(),
+        ReadStatValueType::Int16(number) => number.to_string(),
+        ReadStatValueType::Int32(number) => number.to_string(),
+        ReadStatValueType::Int64(number) => number.to_string(),
+        ReadStatValueType::UInt8(number) => number.to_string(),
+        ReadStatValueType::UInt16(number) => number.to_string(),
+        ReadStatValueType::UInt32(number) => number.to_string(),
+        ReadStatValueType::UInt64(number) => number.to_string(),
+    };
+    Ok(text)
+}
+
+/// Quote a field if it holds a delimiter, quote or line break, doubling any quotes.
+fn quote_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::ReadStatType;
+
+    #[test]
+    fn test_quote_field() {
+        assert_eq!(quote_field("plain"), "plain");
+        assert_eq!(quote_field("a,b"), "\"a,b\"");
+        assert_eq!(quote_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_text_value() {
+        let value = |number: f64| {
+            ReadStatValue::new(
+                ReadStatType::Double,
+                None,
+                ReadStatValueType::Double(number),
+            )
+        };
+        let text = |number, kind| text_value(&value(number), kind, SasTemporalPolicy::Error);
+        assert_eq!(text(1.0, None), Ok("1".to_string()));
+        assert_eq!(text(2.5, None), Ok("2.5".to_string()));
+        assert_eq!(text(f64::NAN, None), Ok(String::new()));
+        assert_eq!(
+            text(0.0, Some(SasTemporalKind::Date)),
+            Ok("1960-01-01".to_string())
+        );
+    }
+
+    #[test]
+    fn test_write_row_temporal_policy() {
+        let variable = |name: &str, format: &str| {
+            ReadStatVariable::builder()
+                .name(name.to_string())
+                .format(format.to_string())
+                .build()
+                .unwrap()
+        };
+        let variables = vec![variable("VISIT", "DATE9."), variable("START", "TIME8.")];
+        let double = |number| {
+            ReadStatValue::new(
+                ReadStatType::Double,
+                None,
+                ReadStatValueType::Double(number),
+            )
+        };
+        let write = |policy, row: &[ReadStatValue]| {
+            let mut writer =
+                SasCsvWriter::new(Vec::new(), variables.clone(), SasCsvOptions::new(policy));
+            writer
+                .write_row(row)
+                .map(|()| String::from_utf8(writer.into_inner()).unwrap())
+        };
+
+        // Out of range
+        let row = [double(1e13), double(0.0)];
+        let err = write(SasTemporalPolicy::Error, &row).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::BadTimestampValue);
+        assert_eq!(err.variable.as_deref(), Some("VISIT"));
+        assert_eq!(
+            write(SasTemporalPolicy::Missing, &row).unwrap(),
+            ",00:00:00\n"
+        );
+        assert_eq!(
+            write(SasTemporalPolicy::Clamp, &row).unwrap(),
+            "+262142-12-31,00:00:00\n"
+        );
+        assert_eq!(
+            write(SasTemporalPolicy::Raw, &row).unwrap(),
+            "10000000000000,00:00:00\n"
+        );
+
+        // A fractional date and a time of 25 hours
+        let row = [double(23_406.5), double(90_000.0)];
+        let err = write(SasTemporalPolicy::Error, &row).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::BadTimestampValue);
+        assert_eq!(err.variable.as_deref(), Some("VISIT"));
+        assert_eq!(write(SasTemporalPolicy::Missing, &row).unwrap(), ",\n");
+        assert_eq!(
+            write(SasTemporalPolicy::Clamp, &row).unwrap(),
+            "2024-01-31,23:59:59.999999\n"
+        );
+        assert_eq!(
+            write(SasTemporalPolicy::Raw, &row).unwrap(),
+            "23406.5,90000\n"
+        );
+    }
+
+    #[test]
+    fn test_write_all() {
+        let path = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 8),
+                SasFixtureColumn::numeric("VISIT", "DATE", 9),
+            ])
+            .rows(vec![
+                vec![
+                    ReadStatValueType::Double(1.0),
+                    ReadStatValueType::String("Doe, J".to_string()),
+                    ReadStatValueType::Double(366.0),
+                ],
+                vec![
+                    ReadStatValueType::Double(2.0),
+                    ReadStatValueType::String(String::new()),
+                    ReadStatValueType::Double(f64::NAN),
+                ],
+            ])
+            .build()
+            .write_temp("csv_write_all");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+
+        let mut output = Vec::new();
+        let count = SasCsvWriter::write_all(
+            &mut reader,
+            &SasReadOptions::default(),
+            &mut output,
+            SasCsvOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "ID,NAME,VISIT\n1,\"Doe, J\",1961-01-01\n2,,\n"
+        );
+    }
+}
@@ -0,0 +1,426 @@
+use crate::sas::sas7bdat::Sas7bdatReader;
+use crate::sas::temporal::format_iso8601_with;
+use crate::types::{
+    ReadStatEncoding, ReadStatError, ReadStatMetadata, ReadStatType, ReadStatValue,
+    ReadStatValueType, ReadStatVariable, SasError, SasJsonLinesOptions, SasMissing,
+    SasMissingStyle, SasReadOptions, SasTemporalKind, SasTemporalText,
+};
+use serde_json::{json, Map, Number, Value};
+use std::io::Write;
+
+/// Writes observations as JSON Lines: one JSON object per line, keyed by variable name in
+/// variable order.
+///
+/// ```no_run
+/// use rust_sas::export::jsonl::SasJsonLinesWriter;
+/// use rust_sas::sas::sas7bdat::Sas7bdatReader;
+/// use rust_sas::types::{SasJsonLinesOptions, SasReadOptions};
+///
+/// let mut reader = Sas7bdatReader::open("claims.sas7bdat").unwrap();
+/// let stdout = std::io::stdout();
+/// SasJsonLinesWriter::write_all(&mut reader, &SasReadOptions::default(), stdout.lock(),
+///     SasJsonLinesOptions::default()).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SasJsonLinesWriter<W: Write> {
+    writer: W,
+    variables: Vec<ReadStatVariable>,
+    temporal_kinds: Vec<Option<SasTemporalKind>>,
+    options: SasJsonLinesOptions,
+}
+
+impl<W: Write> SasJsonLinesWriter<W> {
+    /// Create a writer for rows holding the values of `variables`, in that order.
+    pub fn new(writer: W, variables: Vec<ReadStatVariable>, options: SasJsonLinesOptions) -> Self {
+        let temporal_kinds = variables
+            .iter()
+            .map(ReadStatVariable::temporal_kind)
+            .collect();
+        Self {
+            writer,
+            variables,
+            temporal_kinds,
+            options,
+        }
+    }
+
+    /// Write every row of `reader` selected by `read_options`, preceded by the metadata record
+    /// if the options ask for it.
+    ///
+    /// # Returns
+    /// The number of observations written.
+    pub fn write_all(
+        reader: &mut Sas7bdatReader,
+        read_options: &SasReadOptions,
+        writer: W,
+        options: SasJsonLinesOptions,
+    ) -> Result<u64, SasError> {
+        let variables = reader
+            .resolve_columns(&read_options.columns)?
+            .into_iter()
+            .map(|index| reader.variables()[index].clone())
+            .collect();
+        let mut json_writer = Self::new(writer, variables, options);
+        if json_writer.options.include_metadata {
+            json_writer.write_metadata(reader.metadata())?;
+        }
+
+        let mut count = 0;
+        for row in reader.rows(read_options)? {
+            json_writer
+                .write_row(&row?)
+                .map_err(|err| err.with_row(read_options.row_offset + count))?;
+            count += 1;
+        }
+        json_writer.flush()?;
+        Ok(count)
+    }
+
+    /// Write a record describing the dataset, under a single `_metadata` key so that it can be
+    /// told apart from observations.
+    pub fn write_metadata(&mut self, metadata: &ReadStatMetadata) -> Result<(), SasError> {
+        let record = json!({ "_metadata": metadata_record(metadata, &self.variables) });
+        self.write_line(&record)
+    }
+
+    /// Write one observation. `row` holds the values of the writer's variables, in order.
+    pub fn write_row(&mut self, row: &[ReadStatValue]) -> Result<(), SasError> {
+        if row.len() != self.variables.len() {
+            return Err(ReadStatError::ColumnCountMismatch.into());
+        }
+
+        let mut record = Map::with_capacity(row.len());
+        for ((variable, kind), value) in self.variables.iter().zip(&self.temporal_kinds).zip(row) {
+            let json = self
+                .json_value(value, *kind)
+                .map_err(|err| SasError::new(err).with_variable(variable.name.as_str()))?;
+            record.insert(variable.name.clone(), json);
+        }
+        self.write_line(&Value::Object(record))
+    }
+
+    pub fn flush(&mut self) -> Result<(), SasError> {
+        self.writer
+            .flush()
+            .map_err(|err| SasError::io(ReadStatError::Write, err))
+    }
+
+    /// Return the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_line(&mut self, value: &Value) -> Result<(), SasError> {
+        serde_json::to_writer(&mut self.writer, value)
+            .map_err(|err| SasError::io(ReadStatError::Write, err.into()))?;
+        self.writer
+            .write_all(b"\n")
+            .map_err(|err| SasError::io(ReadStatError::Write, err))
+    }
+
+    fn json_value(
+        &self,
+        value: &ReadStatValue,
+        kind: Option<SasTemporalKind>,
+    ) -> Result<Value, ReadStatError> {
+        let json = match &value.value {
+            ReadStatValueType::String(text) => Value::String(text.clone()),
+            ReadStatValueType::Double(number) if number.is_nan() => self.missing_value(value),
+            ReadStatValueType::Double(number) => match kind {
+                Some(kind) if self.options.iso_dates => {
+                    match format_iso8601_with(*number, kind, self.options.temporal_policy)? {
+                        Some(SasTemporalText::Iso8601(text)) => Value::String(text),
+                        Some(SasTemporalText::Raw(number)) => number_value(number),
+                        None => self.missing_value(&ReadStatValue::missing(SasMissing::System)),
+                    }
+                }
+                _ => number_value(*number),
+            },
+            ReadStatValueType::Float(number) => number_value(*number as f64),
+            ReadStatValueType::Int8(number) => json!(number),
+            ReadStatValueType::Int16(number) => json!(number),
+            ReadStatValueType::Int32(number) => json!(number),
+            ReadStatValueType::Int64(number) => json!(number),
+            ReadStatValueType::UInt8(number) => json!(number),
+            ReadStatValueType::UInt16(number) => json!(number),
+            ReadStatValueType::UInt32(number) => json!(number),
+            ReadStatValueType::UInt64(number) => json!(number),
+        };
+        Ok(json)
+    }
+
+    fn missing_value(&self, value: &ReadStatValue) -> Value {
+        let kind = value.missing_kind().unwrap_or(SasMissing::System);
+        let style = match kind {
+            SasMissing::System => &self.options.system_missing,
+            SasMissing::Underscore | SasMissing::Letter(_) => &self.options.special_missing,
+        };
+        match style {
+            SasMissingStyle::Null => Value::Null,
+            SasMissingStyle::SasNotation => Value::String(kind.to_string()),
+            SasMissingStyle::Text(text) => Value::String(text.clone()),
+        }
+    }
+}
+
+fn number_value(number: f64) -> Value {
+    Number::from_f64(number).map_or(Value::Null, Value::Number)
+}
+
+fn metadata_record(metadata: &ReadStatMetadata, variables: &[ReadStatVariable]) -> Value {
+    let timestamp = |time: &chrono::DateTime<chrono::Utc>| {
+        time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+    };
+    json!({
+        "table_name": metadata.table_name.name,
+        "row_count": metadata.row_count as u64,
+        "var_count": metadata.var_count as u64,
+        "created_at": timestamp(&metadata.created_at),
+        "modified_at": timestamp(&metadata.modified_at),
+        "file_format_version": metadata.file_format_version,
+        "compression": format!("{:?}", metadata.compression),
+        "endianness": format!("{:?}", metadata.endianness),
+        "encoding": match &metadata.file_encoding {
+            ReadStatEncoding::Other(name) => name.clone(),
+            encoding => format!("{:?}", encoding),
+        },
+        "is_64bit": metadata.is_64bit,
+        "variables": variables
+            .iter()
+            .map(|variable| json!({
+                "name": variable.name,
+                "type": match variable.type_ {
+                    ReadStatType::String | ReadStatType::StringRef => "string",
+                    _ => "numeric",
+                },
+                "format": variable.format,
+                "label": variable.label,
+            }))
+            .collect::<Vec<Value>>(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::SasTemporalPolicy;
+
+    fn variable(name: &str, type_: ReadStatType, format: &str) -> ReadStatVariable {
+        ReadStatVariable::builder()
+            .name(name.to_string())
+            .type_(type_)
+            .format(format.to_string())
+            .build()
+            .unwrap()
+    }
+
+    fn double(value: f64, tag: &str) -> ReadStatValue {
+        let missing = tag.bytes().next().map(SasMissing::from_tag);
+        ReadStatValue::new(
+            ReadStatType::Double,
+            missing,
+            ReadStatValueType::Double(value),
+        )
+    }
+
+    fn write_rows(options: SasJsonLinesOptions, rows: &[Vec<ReadStatValue>]) -> String {
+        let variables = vec![
+            variable("ID", ReadStatType::Double, "BEST12."),
+            variable("VISIT", ReadStatType::Double, "DATE9."),
+            variable("AT", ReadStatType::Double, "DATETIME20."),
+            variable("NAME", ReadStatType::String, "$8."),
+        ];
+        let mut writer = SasJsonLinesWriter::new(Vec::new(), variables, options);
+        for row in rows {
+            writer.write_row(row).unwrap();
+        }
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_write_row_with_iso_dates() {
+        let row = vec![
+            double(1.0, ""),
+            double(23_406.0, ""),
+            double(86_400.5, ""),
+            ReadStatValue::new(
+                ReadStatType::String,
+                None,
+                ReadStatValueType::String("Ann \"A\"".to_string()),
+            ),
+        ];
+        let output = write_rows(SasJsonLinesOptions::default(), &[row]);
+        assert_eq!(
+            output,
+            "{\"ID\":1.0,\"VISIT\":\"2024-01-31\",\"AT\":\"1960-01-02T00:00:00.500\",\"NAME\":\"Ann \\\"A\\\"\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_row_without_iso_dates() {
+        let options = SasJsonLinesOptions::builder()
+            .iso_dates(false)
+            .build()
+            .unwrap();
+        let row = vec![
+            double(1.0, ""),
+            double(23_406.0, ""),
+            double(0.0, ""),
+            ReadStatValue::new(
+                ReadStatType::String,
+                None,
+                ReadStatValueType::String(String::new()),
+            ),
+        ];
+        let output = write_rows(options, &[row]);
+        assert_eq!(
+            output,
+            "{\"ID\":1.0,\"VISIT\":23406.0,\"AT\":0.0,\"NAME\":\"\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_value_styles() {
+        let row = vec![
+            double(f64::NAN, ""),
+            double(f64::NAN, "A"),
+            double(f64::NAN, "_"),
+            ReadStatValue::new(
+                ReadStatType::String,
+                None,
+                ReadStatValueType::String(String::new()),
+            ),
+        ];
+
+        let output = write_rows(SasJsonLinesOptions::default(), std::slice::from_ref(&row));
+        assert_eq!(
+            output,
+            "{\"ID\":null,\"VISIT\":null,\"AT\":null,\"NAME\":\"\"}\n"
+        );
+
+        let options = SasJsonLinesOptions::builder()
+            .special_missing(SasMissingStyle::SasNotation)
+            .build()
+            .unwrap();
+        let output = write_rows(options, std::slice::from_ref(&row));
+        assert_eq!(
+            output,
+            "{\"ID\":null,\"VISIT\":\".A\",\"AT\":\"._\",\"NAME\":\"\"}\n"
+        );
+
+        let options = SasJsonLinesOptions::builder()
+            .system_missing(SasMissingStyle::SasNotation)
+            .special_missing(SasMissingStyle::Text("special".to_string()))
+            .build()
+            .unwrap();
+        let output = write_rows(options, &[row]);
+        assert_eq!(
+            output,
+            "{\"ID\":\".\",\"VISIT\":\"special\",\"AT\":\"special\",\"NAME\":\"\"}\n"
+        );
+    }
+
+    #[test]
+    fn test_write_row_temporal_policy() {
+        let row = vec![
+            double(1.0, ""),
+            double(1e13, ""),
+            double(0.0, ""),
+            ReadStatValue::new(
+                ReadStatType::String,
+                None,
+                ReadStatValueType::String(String::new()),
+            ),
+        ];
+        let mut writer = SasJsonLinesWriter::new(
+            Vec::new(),
+            vec![
+                variable("ID", ReadStatType::Double, "BEST12."),
+                variable("VISIT", ReadStatType::Double, "DATE9."),
+                variable("AT", ReadStatType::Double, "DATETIME20."),
+                variable("NAME", ReadStatType::String, "$8."),
+            ],
+            SasJsonLinesOptions::default(),
+        );
+        let err = writer.write_row(&row).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::BadTimestampValue);
+        assert_eq!(err.variable.as_deref(), Some("VISIT"));
+
+        let visit = |policy| {
+            let options = SasJsonLinesOptions::builder()
+                .system_missing(SasMissingStyle::SasNotation)
+                .temporal_policy(policy)
+                .build()
+                .unwrap();
+            let output = write_rows(options, std::slice::from_ref(&row));
+            serde_json::from_str::<Value>(&output).unwrap()["VISIT"].clone()
+        };
+        assert_eq!(visit(SasTemporalPolicy::Missing), json!("."));
+        assert_eq!(visit(SasTemporalPolicy::Clamp), json!("+262142-12-31"));
+        assert_eq!(visit(SasTemporalPolicy::Raw), json!(1e13));
+    }
+
+    #[test]
+    fn test_write_row_wrong_length() {
+        let mut writer = SasJsonLinesWriter::new(
+            Vec::new(),
+            vec![variable("ID", ReadStatType::Double, "")],
+            SasJsonLinesOptions::default(),
+        );
+        assert_eq!(
+            writer.write_row(&[]).map_err(|err| err.kind),
+            Err(ReadStatError::ColumnCountMismatch)
+        );
+    }
+
+    #[test]
+    fn test_write_all_with_metadata() {
+        let path = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::numeric("VISIT", "DATE", 9),
+            ])
+            .rows(vec![
+                vec![
+                    ReadStatValueType::Double(1.0),
+                    ReadStatValueType::Double(0.0),
+                ],
+                vec![
+                    ReadStatValueType::Double(2.0),
+                    ReadStatValueType::Double(f64::NAN),
+                ],
+            ])
+            .build()
+            .write_temp("jsonl_write_all");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let options = SasJsonLinesOptions::builder()
+            .include_metadata(true)
+            .build()
+            .unwrap();
+
+        let mut output = Vec::new();
+        let count = SasJsonLinesWriter::write_all(
+            &mut reader,
+            &SasReadOptions::default(),
+            &mut output,
+            options,
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+
+        let lines: Vec<Value> = String::from_utf8(output)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        let metadata = &lines[0]["_metadata"];
+        assert_eq!(metadata["table_name"], "FIXTURE");
+        assert_eq!(metadata["row_count"], 2);
+        assert_eq!(metadata["created_at"], "1970-01-01T00:00:00Z");
+        assert_eq!(metadata["variables"][1]["format"], "DATE9.");
+        assert_eq!(lines[1], json!({ "ID": 1.0, "VISIT": "1960-01-01" }));
+        assert_eq!(lines[2], json!({ "ID": 2.0, "VISIT": null }));
+    }
+}
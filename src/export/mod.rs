@@ -0,0 +1,11 @@
+pub mod csv;
+pub mod jsonl;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod schema;
+
+pub use csv::SasCsvWriter;
+pub use jsonl::SasJsonLinesWriter;
+#[cfg(feature = "parquet")]
+pub use parquet::write_sas_parquet;
+pub use schema::{SasSchemaDocument, SAS_SCHEMA_VERSION};
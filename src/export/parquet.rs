@@ -0,0 +1,51 @@
+use crate::sas::sas7bdat::read_sas_polars_with;
+use crate::types::{SasPolarsOptions, SasReadOptions};
+use polars::prelude::*;
+use std::fs::File;
+use std::path::Path;
+
+/// Convert the rows of a sas7bdat file selected by `options` to a Parquet file, with the column
+/// types chosen by `read_sas_polars_with` for `polars_options`.
+///
+/// # Returns
+/// The number of observations written.
+pub fn write_sas_parquet<P: AsRef<Path>, Q: AsRef<Path>>(
+    path: P,
+    options: &SasReadOptions,
+    output: Q,
+    polars_options: SasPolarsOptions,
+) -> PolarsResult<u64> {
+    let mut df = read_sas_polars_with(path, options, polars_options)?;
+    let file = File::create(output)?;
+    ParquetWriter::new(file).finish(&mut df)?;
+    Ok(df.height() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::SasFixture;
+
+    #[test]
+    fn test_write_sas_parquet() {
+        let path = SasFixture::builder().build().write_temp("parquet_input");
+        let output = path.with_file_name("output.parquet");
+        let count = write_sas_parquet(
+            &path,
+            &SasReadOptions::default(),
+            &output,
+            SasPolarsOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(count, 2);
+
+        let df = ParquetReader::new(File::open(&output).unwrap())
+            .finish()
+            .unwrap();
+        assert_eq!(df.shape(), (2, 2));
+        assert_eq!(
+            df.column("NAME").unwrap().str().unwrap().get(1),
+            Some("two")
+        );
+    }
+}
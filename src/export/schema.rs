@@ -0,0 +1,623 @@
+use crate::sas::sas7bdat::reader::encoding_from_charset;
+use crate::sas::sas7bdat::Sas7bdatReader;
+use crate::types::{
+    ReadStatAlignment, ReadStatCompression, ReadStatEncoding, ReadStatEndianness, ReadStatError,
+    ReadStatFileLabel, ReadStatLabelSet, ReadStatMeasure, ReadStatMetadata, ReadStatMissingRanges,
+    ReadStatSchema, ReadStatSchemaEntry, ReadStatType, ReadStatValue, ReadStatValueLabel,
+    ReadStatValueType, ReadStatVariable, SasLabelName, SasMissing, SasTableName,
+};
+use chrono::{DateTime, Utc};
+use serde_json::{json, Map, Number, Value};
+
+/// Version of the JSON layout written by `SasSchemaDocument::to_json`. It is bumped whenever a
+/// field is renamed or removed, so that documents kept under version control stay readable.
+pub const SAS_SCHEMA_VERSION: u64 = 1;
+
+/// The complete schema of a dataset: its metadata, every variable and the label sets attached
+/// to them. It round-trips through a stable, pretty-printed JSON document whose keys keep their
+/// order, so that schemas can be diffed between deliveries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasSchemaDocument {
+    pub metadata: ReadStatMetadata,
+    pub file_label: String,
+    pub variables: Vec<ReadStatVariable>,
+    pub label_sets: Vec<ReadStatLabelSet>,
+}
+
+impl SasSchemaDocument {
+    pub fn new(
+        metadata: ReadStatMetadata,
+        file_label: String,
+        variables: Vec<ReadStatVariable>,
+        label_sets: Vec<ReadStatLabelSet>,
+    ) -> Self {
+        Self {
+            metadata,
+            file_label,
+            variables,
+            label_sets,
+        }
+    }
+
+    /// Capture the schema of an open dataset. Label sets are collected from the variables,
+    /// once per name; the empty placeholder every variable starts with is left out.
+    pub fn from_reader(reader: &Sas7bdatReader) -> Self {
+        let mut label_sets: Vec<ReadStatLabelSet> = Vec::new();
+        for variable in reader.variables() {
+            let label_set = &variable.label_set;
+            if has_value_labels(label_set)
+                && !label_sets.iter().any(|known| known.name == label_set.name)
+            {
+                label_sets.push(label_set.clone());
+            }
+        }
+        Self::new(
+            reader.metadata().clone(),
+            reader.file_label().to_string(),
+            reader.variables().to_vec(),
+            label_sets,
+        )
+    }
+
+    /// Render the document as pretty-printed JSON, ending with a line break.
+    pub fn to_json(&self) -> String {
+        let document = json!({
+            "schema_version": SAS_SCHEMA_VERSION,
+            "metadata": metadata_json(&self.metadata, &self.file_label),
+            "variables": self.variables.iter().map(variable_json).collect::<Vec<Value>>(),
+            "label_sets": self.label_sets.iter().map(label_set_json).collect::<Vec<Value>>(),
+        });
+        let mut text = serde_json::to_string_pretty(&document).unwrap_or_default();
+        text.push('\n');
+        text
+    }
+
+    /// Parse a document written by `to_json`.
+    ///
+    /// # Returns
+    /// `ReadStatError::UnsupportedFileFormatVersion` if the document was written by a newer
+    /// version of this library, and `ReadStatError::Parse` if it is not valid JSON or a field is
+    /// missing or has the wrong type.
+    pub fn from_json(text: &str) -> Result<Self, ReadStatError> {
+        let document: Value = serde_json::from_str(text).map_err(|_| ReadStatError::Parse)?;
+        let version = field(&document, "schema_version")?
+            .as_u64()
+            .ok_or(ReadStatError::Parse)?;
+        if version == 0 || version > SAS_SCHEMA_VERSION {
+            return Err(ReadStatError::UnsupportedFileFormatVersion);
+        }
+
+        let label_sets = array(&document, "label_sets")?
+            .iter()
+            .map(parse_label_set)
+            .collect::<Result<Vec<_>, _>>()?;
+        let variables = array(&document, "variables")?
+            .iter()
+            .map(|variable| parse_variable(variable, &label_sets))
+            .collect::<Result<Vec<_>, _>>()?;
+        let metadata = field(&document, "metadata")?;
+        Ok(Self::new(
+            parse_metadata(metadata)?,
+            string(metadata, "label")?,
+            variables,
+            label_sets,
+        ))
+    }
+
+    /// Build the schema a writer needs to produce a dataset with these variables, one entry
+    /// per variable in order.
+    pub fn to_writer_schema(&self) -> ReadStatSchema {
+        let entries: Vec<ReadStatSchemaEntry> = self
+            .variables
+            .iter()
+            .enumerate()
+            .map(|(col, variable)| {
+                let labelset = if has_value_labels(&variable.label_set) {
+                    variable.label_set.name.name.clone()
+                } else {
+                    String::new()
+                };
+                ReadStatSchemaEntry::new(
+                    0,
+                    col as u32,
+                    variable.storage_width as u32,
+                    variable.skip,
+                    variable.clone(),
+                    labelset,
+                    '.',
+                )
+            })
+            .collect();
+        ReadStatSchema::new(
+            self.metadata.table_name.name.clone(),
+            1,
+            entries.len() as u32,
+            0,
+            entries.len() as u32,
+            ',',
+            entries,
+        )
+    }
+}
+
+fn has_value_labels(label_set: &ReadStatLabelSet) -> bool {
+    label_set.value_labels_count > 0
+}
+
+fn metadata_json(metadata: &ReadStatMetadata, file_label: &str) -> Value {
+    json!({
+        "table_name": metadata.table_name.name,
+        "label": file_label,
+        "file_type": match metadata.file_label {
+            ReadStatFileLabel::Sas => "sas7bdat",
+            ReadStatFileLabel::Xport => "xport",
+        },
+        "file_format_version": metadata.file_format_version,
+        "row_count": metadata.row_count as u64,
+        "var_count": metadata.var_count as u64,
+        "created_at": timestamp_json(&metadata.created_at),
+        "modified_at": timestamp_json(&metadata.modified_at),
+        "compression": match metadata.compression {
+            ReadStatCompression::None => "none",
+            ReadStatCompression::Rows => "rows",
+            ReadStatCompression::Binary => "binary",
+        },
+        "endianness": match metadata.endianness {
+            ReadStatEndianness::Little => "little",
+            ReadStatEndianness::Big => "big",
+        },
+        "encoding": match &metadata.file_encoding {
+            ReadStatEncoding::Utf8 => "UTF-8",
+            ReadStatEncoding::Latin1 => "ISO-8859-1",
+            ReadStatEncoding::Windows1252 => "WINDOWS-1252",
+            ReadStatEncoding::Ascii => "US-ASCII",
+            ReadStatEncoding::Other(name) => name,
+        },
+        "is_64bit": metadata.is_64bit,
+    })
+}
+
+fn variable_json(variable: &ReadStatVariable) -> Value {
+    json!({
+        "index": variable.index,
+        "name": variable.name,
+        "type": type_name(&variable.type_),
+        "format": variable.format,
+        "informat": variable.informat,
+        "label": variable.label,
+        "label_set": has_value_labels(&variable.label_set)
+            .then(|| variable.label_set.name.name.clone()),
+        "offset": variable.offset,
+        "storage_width": variable.storage_width,
+        "user_width": variable.user_width,
+        "display_width": variable.display_width,
+        "display_decimals": variable.display_decimals,
+        "measure": variable.measure.as_ref().map(|measure| match measure {
+            ReadStatMeasure::Nominal => "nominal",
+            ReadStatMeasure::Ordinal => "ordinal",
+            ReadStatMeasure::Scale => "scale",
+        }),
+        "alignment": variable.alignment.as_ref().map(|alignment| match alignment {
+            ReadStatAlignment::Left => "left",
+            ReadStatAlignment::Center => "center",
+            ReadStatAlignment::Right => "right",
+        }),
+        "missing_ranges": variable
+            .missing_ranges
+            .missing_ranges
+            .iter()
+            .map(|value| json!({ "value": value_json(&value.value), "tags": tags_json(value.missing) }))
+            .collect::<Vec<Value>>(),
+        "skip": variable.skip,
+    })
+}
+
+fn label_set_json(label_set: &ReadStatLabelSet) -> Value {
+    let value_label = &label_set.value_labels;
+    json!({
+        "name": label_set.name.name,
+        "type": type_name(&label_set.type_),
+        "value_label": {
+            "label": value_label.label,
+            "double_key": Number::from_f64(value_label.double_key).map(Value::Number),
+            "int32_key": value_label.int32_key,
+            "string_key": value_label.string_key,
+            "tags": tags_json(value_label.missing),
+        },
+        "value_labels_count": label_set.value_labels_count,
+        "variables": label_set.variables.as_ref().map(|variables| {
+            variables.iter().map(|name| name.name.clone()).collect::<Vec<String>>()
+        }),
+    })
+}
+
+fn value_json(value: &ReadStatValueType) -> Value {
+    match value {
+        ReadStatValueType::String(text) => json!(text),
+        ReadStatValueType::Int8(number) => json!(number),
+        ReadStatValueType::Int16(number) => json!(number),
+        ReadStatValueType::Int32(number) => json!(number),
+        ReadStatValueType::Int64(number) => json!(number),
+        ReadStatValueType::UInt8(number) => json!(number),
+        ReadStatValueType::UInt16(number) => json!(number),
+        ReadStatValueType::UInt32(number) => json!(number),
+        ReadStatValueType::UInt64(number) => json!(number),
+        ReadStatValueType::Float(number) => Number::from_f64(*number as f64).into(),
+        ReadStatValueType::Double(number) => Number::from_f64(*number).into(),
+    }
+}
+
+fn timestamp_json(time: &DateTime<Utc>) -> Value {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        .into()
+}
+
+const TYPE_NAMES: [(ReadStatType, &str); 12] = [
+    (ReadStatType::String, "string"),
+    (ReadStatType::Int8, "int8"),
+    (ReadStatType::Int16, "int16"),
+    (ReadStatType::Int32, "int32"),
+    (ReadStatType::Int64, "int64"),
+    (ReadStatType::UInt8, "uint8"),
+    (ReadStatType::UInt16, "uint16"),
+    (ReadStatType::UInt32, "uint32"),
+    (ReadStatType::UInt64, "uint64"),
+    (ReadStatType::Float, "float"),
+    (ReadStatType::Double, "double"),
+    (ReadStatType::StringRef, "string_ref"),
+];
+
+fn type_name(type_: &ReadStatType) -> &'static str {
+    TYPE_NAMES
+        .iter()
+        .find(|(known, _)| known == type_)
+        .map(|(_, name)| *name)
+        .unwrap_or_default()
+}
+
+fn parse_type(value: &Value) -> Result<ReadStatType, ReadStatError> {
+    let name = value.as_str().ok_or(ReadStatError::Parse)?;
+    TYPE_NAMES
+        .iter()
+        .find(|(_, known)| *known == name)
+        .map(|(type_, _)| type_.clone())
+        .ok_or(ReadStatError::Parse)
+}
+
+fn parse_metadata(metadata: &Value) -> Result<ReadStatMetadata, ReadStatError> {
+    let file_label = match string(metadata, "file_type")?.as_str() {
+        "sas7bdat" => ReadStatFileLabel::Sas,
+        "xport" => ReadStatFileLabel::Xport,
+        _ => return Err(ReadStatError::Parse),
+    };
+    let compression = match string(metadata, "compression")?.as_str() {
+        "none" => ReadStatCompression::None,
+        "rows" => ReadStatCompression::Rows,
+        "binary" => ReadStatCompression::Binary,
+        _ => return Err(ReadStatError::Parse),
+    };
+    let endianness = match string(metadata, "endianness")?.as_str() {
+        "little" => ReadStatEndianness::Little,
+        "big" => ReadStatEndianness::Big,
+        _ => return Err(ReadStatError::Parse),
+    };
+    Ok(ReadStatMetadata::new(
+        unsigned(metadata, "row_count")? as u128,
+        unsigned(metadata, "var_count")? as u128,
+        parse_timestamp(field(metadata, "created_at")?)?,
+        parse_timestamp(field(metadata, "modified_at")?)?,
+        u16::try_from(unsigned(metadata, "file_format_version")?)
+            .map_err(|_| ReadStatError::Parse)?,
+        compression,
+        endianness,
+        SasTableName::new(string(metadata, "table_name")?),
+        file_label,
+        encoding_from_charset(&string(metadata, "encoding")?),
+        boolean(metadata, "is_64bit")?,
+    ))
+}
+
+fn parse_timestamp(value: &Value) -> Result<DateTime<Utc>, ReadStatError> {
+    match value {
+        Value::Null => Ok(DateTime::UNIX_EPOCH),
+        Value::String(text) => DateTime::parse_from_rfc3339(text)
+            .map(|time| time.with_timezone(&Utc))
+            .map_err(|_| ReadStatError::BadTimestampString),
+        _ => Err(ReadStatError::Parse),
+    }
+}
+
+fn parse_variable(
+    variable: &Value,
+    label_sets: &[ReadStatLabelSet],
+) -> Result<ReadStatVariable, ReadStatError> {
+    let type_ = parse_type(field(variable, "type")?)?;
+    let mut builder = ReadStatVariable::builder();
+    if let Some(name) = field(variable, "label_set")?.as_str() {
+        let label_set = label_sets
+            .iter()
+            .find(|label_set| label_set.name.name == name)
+            .ok_or(ReadStatError::Parse)?;
+        builder.label_set(label_set.clone());
+    }
+
+    let missing_ranges = array(variable, "missing_ranges")?
+        .iter()
+        .map(|range| {
+            Ok(ReadStatValue::new(
+                type_.clone(),
+                parse_tags(range)?,
+                parse_value(&type_, field(range, "value")?)?,
+            ))
+        })
+        .collect::<Result<Vec<_>, ReadStatError>>()?;
+    let measure = match field(variable, "measure")?.as_str() {
+        Some("nominal") => Some(ReadStatMeasure::Nominal),
+        Some("ordinal") => Some(ReadStatMeasure::Ordinal),
+        Some("scale") => Some(ReadStatMeasure::Scale),
+        Some(_) => return Err(ReadStatError::Parse),
+        None => None,
+    };
+    let alignment = match field(variable, "alignment")?.as_str() {
+        Some("left") => Some(ReadStatAlignment::Left),
+        Some("center") => Some(ReadStatAlignment::Center),
+        Some("right") => Some(ReadStatAlignment::Right),
+        Some(_) => return Err(ReadStatError::Parse),
+        None => None,
+    };
+    let index = u32::try_from(unsigned(variable, "index")?).map_err(|_| ReadStatError::Parse)?;
+
+    builder
+        .type_(type_)
+        .index(index)
+        .name(string(variable, "name")?)
+        .format(string(variable, "format")?)
+        .informat(string(variable, "informat")?)
+        .label(string(variable, "label")?)
+        .offset(unsigned(variable, "offset")?)
+        .storage_width(unsigned(variable, "storage_width")?)
+        .user_width(unsigned(variable, "user_width")?)
+        .display_width(unsigned(variable, "display_width")?)
+        .display_decimals(unsigned(variable, "display_decimals")?)
+        .missing_ranges(ReadStatMissingRanges::new(
+            missing_ranges.clone(),
+            missing_ranges.len() as u32,
+        ))
+        .measure(measure)
+        .alignment(alignment)
+        .skip(boolean(variable, "skip")?)
+        .index_after_skipping(index)
+        .build()
+        .map_err(|_| ReadStatError::Parse)
+}
+
+fn parse_label_set(label_set: &Value) -> Result<ReadStatLabelSet, ReadStatError> {
+    let type_ = parse_type(field(label_set, "type")?)?;
+    let value_label = field(label_set, "value_label")?;
+    let label = string(value_label, "label")?;
+    let string_key = string(value_label, "string_key")?;
+    let int32_key = field(value_label, "int32_key")?
+        .as_i64()
+        .and_then(|key| i32::try_from(key).ok())
+        .ok_or(ReadStatError::Parse)?;
+    let double_key = match field(value_label, "double_key")? {
+        Value::Null => f64::NAN,
+        key => key.as_f64().ok_or(ReadStatError::Parse)?,
+    };
+    let value_labels = ReadStatValueLabel::new(
+        label.clone(),
+        label.len() as u32,
+        double_key,
+        int32_key,
+        string_key.clone(),
+        string_key.len() as u32,
+        parse_tags(value_label)?,
+    );
+    let count = u32::try_from(unsigned(label_set, "value_labels_count")?)
+        .map_err(|_| ReadStatError::Parse)?;
+
+    let mut parsed = ReadStatLabelSet::new(
+        type_,
+        SasLabelName::new(string(label_set, "name")?),
+        value_labels,
+        count,
+        count,
+    );
+    if let Some(variables) = field(label_set, "variables")?.as_array() {
+        let names = variables
+            .iter()
+            .map(|name| name.as_str().map(SasLabelName::from_str))
+            .collect::<Option<Vec<_>>>()
+            .ok_or(ReadStatError::Parse)?;
+        parsed.variable_count = Some(names.len() as u32);
+        parsed.variable_capacity = Some(names.len() as u32);
+        parsed.variables = Some(names);
+    }
+    Ok(parsed)
+}
+
+fn parse_value(type_: &ReadStatType, value: &Value) -> Result<ReadStatValueType, ReadStatError> {
+    let parsed = match type_ {
+        ReadStatType::String | ReadStatType::StringRef => value
+            .as_str()
+            .map(|text| ReadStatValueType::String(text.to_string())),
+        ReadStatType::Int8 => integer(value).map(ReadStatValueType::Int8),
+        ReadStatType::Int16 => integer(value).map(ReadStatValueType::Int16),
+        ReadStatType::Int32 => integer(value).map(ReadStatValueType::Int32),
+        ReadStatType::Int64 => value.as_i64().map(ReadStatValueType::Int64),
+        ReadStatType::UInt8 => integer(value).map(ReadStatValueType::UInt8),
+        ReadStatType::UInt16 => integer(value).map(ReadStatValueType::UInt16),
+        ReadStatType::UInt32 => integer(value).map(ReadStatValueType::UInt32),
+        ReadStatType::UInt64 => value.as_u64().map(ReadStatValueType::UInt64),
+        ReadStatType::Float => value
+            .as_f64()
+            .or(value.is_null().then_some(f64::NAN))
+            .map(|number| ReadStatValueType::Float(number as f32)),
+        ReadStatType::Double => value
+            .as_f64()
+            .or(value.is_null().then_some(f64::NAN))
+            .map(ReadStatValueType::Double),
+    };
+    parsed.ok_or(ReadStatError::Parse)
+}
+
+fn integer<T: TryFrom<i64>>(value: &Value) -> Option<T> {
+    value.as_i64().and_then(|number| T::try_from(number).ok())
+}
+
+fn field<'a>(object: &'a Value, name: &str) -> Result<&'a Value, ReadStatError> {
+    object
+        .as_object()
+        .and_then(|object: &Map<String, Value>| object.get(name))
+        .ok_or(ReadStatError::Parse)
+}
+
+fn array<'a>(object: &'a Value, name: &str) -> Result<&'a Vec<Value>, ReadStatError> {
+    field(object, name)?.as_array().ok_or(ReadStatError::Parse)
+}
+
+fn string(object: &Value, name: &str) -> Result<String, ReadStatError> {
+    field(object, name)?
+        .as_str()
+        .map(str::to_string)
+        .ok_or(ReadStatError::Parse)
+}
+
+/// Special missing values are written as a list holding their letter or underscore, which is
+/// empty for system missing and values that are not missing.
+fn tags_json(missing: Option<SasMissing>) -> Vec<String> {
+    missing
+        .and_then(|missing| missing.tag())
+        .map(|tag| vec![tag.to_string()])
+        .unwrap_or_default()
+}
+
+fn parse_tags(object: &Value) -> Result<Option<SasMissing>, ReadStatError> {
+    match strings(object, "tags")?.as_slice() {
+        [] => Ok(None),
+        [tag] => match tag.as_bytes() {
+            &[tag @ (b'_' | b'A'..=b'Z')] => Ok(Some(SasMissing::from_tag(tag))),
+            _ => Err(ReadStatError::Parse),
+        },
+        _ => Err(ReadStatError::Parse),
+    }
+}
+
+fn strings(object: &Value, name: &str) -> Result<Vec<String>, ReadStatError> {
+    array(object, name)?
+        .iter()
+        .map(|text| text.as_str().map(str::to_string))
+        .collect::<Option<Vec<_>>>()
+        .ok_or(ReadStatError::Parse)
+}
+
+fn unsigned(object: &Value, name: &str) -> Result<u64, ReadStatError> {
+    field(object, name)?.as_u64().ok_or(ReadStatError::Parse)
+}
+
+fn boolean(object: &Value, name: &str) -> Result<bool, ReadStatError> {
+    field(object, name)?.as_bool().ok_or(ReadStatError::Parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+
+    fn fixture_document() -> SasSchemaDocument {
+        let path = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("VISIT", "DATE", 9)
+                    .with_informat("DATE", 9)
+                    .with_label("Visit date"),
+                SasFixtureColumn::character("ARM", 4),
+            ])
+            .rows(Vec::new())
+            .build()
+            .write_temp("schema_document");
+        SasSchemaDocument::from_reader(&Sas7bdatReader::open(&path).unwrap())
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = fixture_document().to_json();
+        let document: Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(document["schema_version"], 1);
+        assert_eq!(document["metadata"]["table_name"], "FIXTURE");
+        assert_eq!(document["metadata"]["label"], "Fixture dataset");
+        assert_eq!(document["metadata"]["created_at"], "1970-01-01T00:00:00Z");
+        assert_eq!(document["variables"][0]["name"], "VISIT");
+        assert_eq!(document["variables"][0]["type"], "double");
+        assert_eq!(document["variables"][0]["informat"], "DATE9.");
+        assert_eq!(document["variables"][0]["label_set"], Value::Null);
+        assert_eq!(document["variables"][1]["type"], "string");
+        assert_eq!(document["label_sets"], json!([]));
+        assert!(json.starts_with("{\n  \"schema_version\": 1,\n  \"metadata\""));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut document = fixture_document();
+        let mut label_set = ReadStatLabelSet::new(
+            ReadStatType::String,
+            SasLabelName::from_str("$ARMFMT"),
+            ReadStatValueLabel::new("Placebo".to_string(), 7, 0.0, 0, "A".to_string(), 1, None),
+            1,
+            1,
+        );
+        label_set.variables = Some(vec![SasLabelName::from_str("ARM")]);
+        label_set.variable_count = Some(1);
+        label_set.variable_capacity = Some(1);
+        document.variables[1].label_set = label_set.clone();
+        document.variables[0].missing_ranges = ReadStatMissingRanges::new(
+            vec![ReadStatValue::new(
+                ReadStatType::Double,
+                Some(SasMissing::Letter('A')),
+                ReadStatValueType::Double(-1.0),
+            )],
+            1,
+        );
+        document.label_sets = vec![label_set];
+
+        let parsed = SasSchemaDocument::from_json(&document.to_json()).unwrap();
+        assert_eq!(parsed.file_label, document.file_label);
+        assert_eq!(parsed.variables, document.variables);
+        assert_eq!(parsed.label_sets, document.label_sets);
+        assert_eq!(parsed.metadata.table_name, document.metadata.table_name);
+        assert_eq!(parsed.metadata.row_count, document.metadata.row_count);
+        assert_eq!(
+            parsed.metadata.file_encoding,
+            document.metadata.file_encoding
+        );
+        assert_eq!(parsed.to_json(), document.to_json());
+    }
+
+    #[test]
+    fn test_from_json_errors() {
+        assert_eq!(
+            SasSchemaDocument::from_json("not json"),
+            Err(ReadStatError::Parse)
+        );
+        assert_eq!(
+            SasSchemaDocument::from_json("{\"schema_version\": 99}"),
+            Err(ReadStatError::UnsupportedFileFormatVersion)
+        );
+        let mut document: Value = serde_json::from_str(&fixture_document().to_json()).unwrap();
+        document["variables"][0]["type"] = json!("decimal");
+        assert_eq!(
+            SasSchemaDocument::from_json(&document.to_string()),
+            Err(ReadStatError::Parse)
+        );
+    }
+
+    #[test]
+    fn test_to_writer_schema() {
+        let schema = fixture_document().to_writer_schema();
+        assert_eq!(schema.filename, "FIXTURE");
+        assert_eq!(schema.entry_count, 2);
+        assert_eq!(schema.entries[1].col, 1);
+        assert_eq!(schema.entries[1].len, 4);
+        assert_eq!(schema.entries[1].variable.name, "ARM");
+        assert_eq!(schema.entries[1].labelset, "");
+    }
+}
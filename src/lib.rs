@@ -1,4 +1,6 @@
 pub mod constants;
+pub mod export;
+pub mod report;
 pub mod sas;
 pub mod types;
 
@@ -1,3 +1,286 @@
-fn main() {
-    println!("Hello, world!");
+use clap::{Parser, Subcommand, ValueEnum};
+use rust_sas::export::{SasCsvWriter, SasJsonLinesWriter, SasSchemaDocument};
+use rust_sas::report::{format_contents, format_info, format_schema, format_validation};
+use rust_sas::sas::sas7bdat::{validate, Sas7bdatReader};
+use rust_sas::types::{
+    ReadStatHandler, SasCsvOptions, SasError, SasJsonLinesOptions, SasReadOptions,
+    SasTemporalPolicy,
+};
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Inspect and convert SAS datasets without SAS.
+#[derive(Debug, Parser)]
+#[command(name = "sas", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Print the header and metadata of a dataset
+    Info { file: PathBuf },
+
+    /// Print a report like SAS PROC CONTENTS
+    Contents {
+        file: PathBuf,
+
+        /// List variables in creation order instead of alphabetically
+        #[arg(long)]
+        varnum: bool,
+    },
+
+    /// Print the first observations of a dataset as CSV
+    Head {
+        file: PathBuf,
+
+        /// Number of observations to print
+        #[arg(short = 'n', long, default_value_t = 10)]
+        rows: u64,
+
+        /// What to do with dates that cannot be printed
+        #[arg(long, value_enum, default_value_t = TemporalPolicy::Error)]
+        temporal_policy: TemporalPolicy,
+    },
+
+    /// Print the variables of a dataset
+    Schema {
+        file: PathBuf,
+
+        /// Print the full schema as a versioned JSON document
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Check the structure of a dataset and report every problem found
+    Validate {
+        file: PathBuf,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Convert a dataset to another format
+    Convert {
+        file: PathBuf,
+
+        /// Output format
+        #[arg(long = "to", value_enum)]
+        to: OutputFormat,
+
+        /// Output file; standard output if omitted (not allowed for parquet)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Comma-separated list of variables to keep
+        #[arg(long, value_delimiter = ',')]
+        columns: Option<Vec<String>>,
+
+        /// Maximum number of observations to convert
+        #[arg(long)]
+        limit: Option<u64>,
+
+        /// Report progress on standard error (csv and jsonl output)
+        #[arg(long)]
+        progress: bool,
+
+        /// What to do with dates that cannot be written
+        #[arg(long, value_enum, default_value_t = TemporalPolicy::Error)]
+        temporal_policy: TemporalPolicy,
+    },
+}
+
+/// Number of pages read between two progress reports.
+const PROGRESS_PAGES: u64 = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Csv,
+    Parquet,
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+enum TemporalPolicy {
+    Error,
+    Missing,
+    Clamp,
+    Raw,
+}
+
+impl From<TemporalPolicy> for SasTemporalPolicy {
+    fn from(policy: TemporalPolicy) -> Self {
+        match policy {
+            TemporalPolicy::Error => SasTemporalPolicy::Error,
+            TemporalPolicy::Missing => SasTemporalPolicy::Missing,
+            TemporalPolicy::Clamp => SasTemporalPolicy::Clamp,
+            TemporalPolicy::Raw => SasTemporalPolicy::Raw,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("sas: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    match cli.command {
+        Command::Info { file } => {
+            let reader = open(&file)?;
+            print!("{}", format_info(&reader));
+        }
+        Command::Contents { file, varnum } => {
+            let reader = open(&file)?;
+            print!("{}", format_contents(&reader, varnum));
+        }
+        Command::Head {
+            file,
+            rows,
+            temporal_policy,
+        } => {
+            let mut reader = open(&file)?;
+            let options = SasReadOptions::new(None, 0, Some(rows));
+            SasCsvWriter::write_all(
+                &mut reader,
+                &options,
+                io::stdout().lock(),
+                SasCsvOptions::new(temporal_policy.into()),
+            )
+            .map_err(|err| describe(&file, err))?;
+        }
+        Command::Schema { file, json } => {
+            let reader = open(&file)?;
+            if json {
+                print!("{}", SasSchemaDocument::from_reader(&reader).to_json());
+            } else {
+                print!("{}", format_schema(reader.variables()));
+            }
+        }
+        Command::Validate { file, json } => {
+            let report = validate(&file).map_err(|err| describe(&file, err))?;
+            if json {
+                print!("{}", report.to_json());
+            } else {
+                print!("{}", format_validation(&report));
+            }
+            if !report.is_valid() {
+                return Err(format!("{}: file is not valid", file.display()));
+            }
+        }
+        Command::Convert {
+            file,
+            to,
+            output,
+            columns,
+            limit,
+            progress,
+            temporal_policy,
+        } => {
+            let options = SasReadOptions::new(columns, 0, limit);
+            convert(
+                &file,
+                to,
+                output.as_deref(),
+                &options,
+                progress,
+                temporal_policy.into(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn convert(
+    file: &Path,
+    to: OutputFormat,
+    output: Option<&Path>,
+    options: &SasReadOptions,
+    progress: bool,
+    temporal_policy: SasTemporalPolicy,
+) -> Result<(), String> {
+    if to == OutputFormat::Parquet {
+        let output = output.ok_or("converting to parquet requires --output")?;
+        return convert_parquet(file, output, options, temporal_policy);
+    }
+
+    let mut reader = open(file)?;
+    if progress {
+        reader.set_progress_handler(PROGRESS_PAGES, |progress| {
+            eprint!(
+                "\r{}/{} observations ({:.0}%)",
+                progress.rows_read,
+                progress.row_count,
+                progress.fraction() * 100.0
+            );
+            ReadStatHandler::Ok
+        });
+    }
+    let writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).map_err(|err| format!("{}: {}", path.display(), err))?,
+        )),
+        None => Box::new(BufWriter::new(io::stdout().lock())),
+    };
+    let result = match to {
+        OutputFormat::Jsonl => SasJsonLinesWriter::write_all(
+            &mut reader,
+            options,
+            writer,
+            SasJsonLinesOptions {
+                temporal_policy,
+                ..SasJsonLinesOptions::default()
+            },
+        ),
+        _ => SasCsvWriter::write_all(
+            &mut reader,
+            options,
+            writer,
+            SasCsvOptions::new(temporal_policy),
+        ),
+    };
+    if progress {
+        eprintln!();
+    }
+    result.map(|_| ()).map_err(|err| describe(file, err))
+}
+
+#[cfg(feature = "parquet")]
+fn convert_parquet(
+    file: &Path,
+    output: &Path,
+    options: &SasReadOptions,
+    temporal_policy: SasTemporalPolicy,
+) -> Result<(), String> {
+    let polars_options = rust_sas::types::SasPolarsOptions::new(temporal_policy);
+    rust_sas::export::write_sas_parquet(file, options, output, polars_options)
+        .map(|_| ())
+        .map_err(|err| format!("{}: {}", file.display(), err))
+}
+
+#[cfg(not(feature = "parquet"))]
+fn convert_parquet(
+    _file: &Path,
+    _output: &Path,
+    _options: &SasReadOptions,
+    _temporal_policy: SasTemporalPolicy,
+) -> Result<(), String> {
+    Err("parquet output requires building with the `parquet` feature".to_string())
+}
+
+fn open(file: &Path) -> Result<Sas7bdatReader, String> {
+    Sas7bdatReader::open(file).map_err(|err| describe(file, err))
+}
+
+fn describe(file: &Path, err: SasError) -> String {
+    format!("{}: {}", file.display(), err)
 }
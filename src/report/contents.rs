@@ -0,0 +1,177 @@
+use crate::report::info::{compression_name, format_table, type_name};
+use crate::sas::sas7bdat::Sas7bdatReader;
+
+/// Render a report laid out like the output of SAS `PROC CONTENTS`: the dataset attributes,
+/// the engine and host dependent information, and a table of the variables.
+///
+/// # Arguments
+/// * `reader` - The dataset to describe.
+/// * `varnum` - List the variables in creation order, like the `VARNUM` option, instead of
+///   alphabetically.
+pub fn format_contents(reader: &Sas7bdatReader, varnum: bool) -> String {
+    let header = reader.header();
+    let context = reader.context();
+    let mut output = String::from("The CONTENTS Procedure\n\n");
+
+    let attributes = [
+        [
+            "Data Set Name".to_string(),
+            header.table_name.clone(),
+            "Observations".to_string(),
+            context.row_count.to_string(),
+        ],
+        [
+            "Engine".to_string(),
+            format!("V{}", header.major_version),
+            "Variables".to_string(),
+            reader.variables().len().to_string(),
+        ],
+        [
+            "Created".to_string(),
+            format_sas_timestamp(header.creation_time),
+            "Observation Length".to_string(),
+            context.row_length.to_string(),
+        ],
+        [
+            "Last Modified".to_string(),
+            format_sas_timestamp(header.modification_time),
+            "Compressed".to_string(),
+            compression_name(&context.compression).to_string(),
+        ],
+        [
+            "Label".to_string(),
+            reader.file_label().to_string(),
+            String::new(),
+            String::new(),
+        ],
+        [
+            "Encoding".to_string(),
+            header.encoding.clone(),
+            String::new(),
+            String::new(),
+        ],
+    ];
+    output.push_str(&format_table(&attributes));
+
+    output.push_str("\nEngine/Host Dependent Information\n\n");
+    let engine = [
+        [
+            "Data Set Page Size".to_string(),
+            header.page_size.to_string(),
+        ],
+        [
+            "Number of Data Set Pages".to_string(),
+            header.page_count.to_string(),
+        ],
+        ["Header Size".to_string(), header.header_size.to_string()],
+        ["Release Created".to_string(), header.release.clone()],
+        ["Host Created".to_string(), header.host.clone()],
+        ["OS Name".to_string(), header.os_name.clone()],
+    ];
+    output.push_str(&format_table(&engine));
+
+    let mut variables: Vec<_> = reader.variables().iter().enumerate().collect();
+    if varnum {
+        output.push_str("\nVariables in Creation Order\n\n");
+    } else {
+        variables.sort_by_key(|(_, variable)| variable.name.to_ascii_uppercase());
+        output.push_str("\nAlphabetic List of Variables and Attributes\n\n");
+    }
+
+    let mut table = vec![[
+        "#".to_string(),
+        "Variable".to_string(),
+        "Type".to_string(),
+        "Len".to_string(),
+        "Format".to_string(),
+        "Informat".to_string(),
+        "Label".to_string(),
+    ]];
+    for (index, variable) in variables {
+        table.push([
+            (index + 1).to_string(),
+            variable.name.clone(),
+            type_name(&variable.type_).to_string(),
+            variable.storage_width.to_string(),
+            variable.format.clone(),
+            variable.informat.clone(),
+            variable.label.clone(),
+        ]);
+    }
+    output.push_str(&format_table(&table));
+    output
+}
+
+/// Render a Unix timestamp the way SAS 9.4 prints dataset timestamps, eg `09/24/2014 14:53:06`.
+fn format_sas_timestamp(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.format("%m/%d/%Y %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::ReadStatValueType;
+
+    fn contents(varnum: bool) -> String {
+        let path = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("VISIT", "DATE", 9)
+                    .with_informat("DATE", 9)
+                    .with_label("Visit date"),
+                SasFixtureColumn::character("ARM", 4),
+            ])
+            .rows(vec![vec![
+                ReadStatValueType::Double(0.0),
+                ReadStatValueType::String("A".to_string()),
+            ]])
+            .build()
+            .write_temp(&format!("report_contents_{}", varnum));
+        let reader = Sas7bdatReader::open(&path).unwrap();
+        format_contents(&reader, varnum)
+    }
+
+    #[test]
+    fn test_format_contents() {
+        assert_eq!(
+            contents(false),
+            "The CONTENTS Procedure\n\
+             \n\
+             Data Set Name  FIXTURE              Observations        1\n\
+             Engine         V9                   Variables           2\n\
+             Created        01/01/1970 00:00:00  Observation Length  12\n\
+             Last Modified  01/01/1970 00:00:00  Compressed          NO\n\
+             Label          Fixture dataset\n\
+             Encoding       UTF-8\n\
+             \n\
+             Engine/Host Dependent Information\n\
+             \n\
+             Data Set Page Size        4096\n\
+             Number of Data Set Pages  2\n\
+             Header Size               1024\n\
+             Release Created           9.0401M2\n\
+             Host Created              X64_7PRO\n\
+             OS Name                   x86_64\n\
+             \n\
+             Alphabetic List of Variables and Attributes\n\
+             \n\
+             #  Variable  Type  Len  Format  Informat  Label\n\
+             2  ARM       Char  4    $4.\n\
+             1  VISIT     Num   8    DATE9.  DATE9.    Visit date\n"
+        );
+    }
+
+    #[test]
+    fn test_format_contents_varnum() {
+        let report = contents(true);
+        assert!(report.contains(
+            "Variables in Creation Order\n\
+             \n\
+             #  Variable  Type  Len  Format  Informat  Label\n\
+             1  VISIT     Num   8    DATE9.  DATE9.    Visit date\n\
+             2  ARM       Char  4    $4.\n"
+        ));
+    }
+}
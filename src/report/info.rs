@@ -0,0 +1,147 @@
+use crate::sas::sas7bdat::Sas7bdatReader;
+use crate::types::{ReadStatCompression, ReadStatType, ReadStatVariable, SasEndian};
+
+/// Render the header and metadata of a sas7bdat file as `name: value` lines.
+pub fn format_info(reader: &Sas7bdatReader) -> String {
+    let header = reader.header();
+    let context = reader.context();
+    let fields = [
+        ("Table name", header.table_name.clone()),
+        ("Label", reader.file_label().to_string()),
+        ("Release", header.release.clone()),
+        ("Host", header.host.clone()),
+        ("OS", header.os_name.clone()),
+        ("Created", format_timestamp(header.creation_time)),
+        ("Modified", format_timestamp(header.modification_time)),
+        ("Encoding", header.encoding.clone()),
+        (
+            "Platform",
+            format!(
+                "{}, {}",
+                if header.is_64bit { "64-bit" } else { "32-bit" },
+                match header.endianness {
+                    SasEndian::Little => "little-endian",
+                    SasEndian::Big => "big-endian",
+                }
+            ),
+        ),
+        ("Header size", header.header_size.to_string()),
+        ("Page size", header.page_size.to_string()),
+        ("Page count", header.page_count.to_string()),
+        (
+            "Compression",
+            compression_name(&context.compression).to_string(),
+        ),
+        ("Observations", context.row_count.to_string()),
+        ("Variables", reader.variables().len().to_string()),
+        ("Observation length", context.row_length.to_string()),
+    ];
+
+    let width = fields.iter().map(|(name, _)| name.len()).max().unwrap_or(0) + 1;
+    fields
+        .iter()
+        .map(|(name, value)| format!("{:<width$} {}\n", format!("{}:", name), value))
+        .collect()
+}
+
+/// Render the variables of a dataset as an aligned table of number, name, type, length,
+/// format and label.
+pub fn format_schema(variables: &[ReadStatVariable]) -> String {
+    let mut rows = vec![[
+        "#".to_string(),
+        "Name".to_string(),
+        "Type".to_string(),
+        "Length".to_string(),
+        "Format".to_string(),
+        "Label".to_string(),
+    ]];
+    for (number, variable) in variables.iter().enumerate() {
+        rows.push([
+            (number + 1).to_string(),
+            variable.name.clone(),
+            type_name(&variable.type_).to_string(),
+            variable.storage_width.to_string(),
+            variable.format.clone(),
+            variable.label.clone(),
+        ]);
+    }
+    format_table(&rows)
+}
+
+/// Left-align the cells of a table in columns separated by two spaces.
+pub(crate) fn format_table<const N: usize>(rows: &[[String; N]]) -> String {
+    let mut widths = [0; N];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut output = String::new();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:<width$}", cell))
+            .collect();
+        output.push_str(cells.join("  ").trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+/// Render a Unix timestamp as ISO 8601 in UTC.
+pub(crate) fn format_timestamp(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .map(|time| time.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+/// The name SAS uses for a compression scheme in the `COMPRESS=` option.
+pub(crate) fn compression_name(compression: &ReadStatCompression) -> &'static str {
+    match compression {
+        ReadStatCompression::None => "NO",
+        ReadStatCompression::Rows => "CHAR",
+        ReadStatCompression::Binary => "BINARY",
+    }
+}
+
+pub(crate) fn type_name(type_: &ReadStatType) -> &'static str {
+    match type_ {
+        ReadStatType::String | ReadStatType::StringRef => "Char",
+        _ => "Num",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::SasFixture;
+
+    #[test]
+    fn test_format_info() {
+        let path = SasFixture::builder().build().write_temp("report_info");
+        let reader = Sas7bdatReader::open(&path).unwrap();
+        let info = format_info(&reader);
+
+        assert!(info.starts_with("Table name:         FIXTURE\n"));
+        assert!(info.contains("Label:              Fixture dataset\n"));
+        assert!(info.contains("Created:            1970-01-01T00:00:00Z\n"));
+        assert!(info.contains("Platform:           32-bit, little-endian\n"));
+        assert!(info.contains("Compression:        NO\n"));
+        assert!(info.contains("Observations:       2\n"));
+        assert!(info.ends_with("Observation length: 16\n"));
+    }
+
+    #[test]
+    fn test_format_schema() {
+        let path = SasFixture::builder().build().write_temp("report_schema");
+        let reader = Sas7bdatReader::open(&path).unwrap();
+        assert_eq!(
+            format_schema(reader.variables()),
+            "#  Name  Type  Length  Format   Label\n\
+             1  X     Num   8       BEST12.\n\
+             2  NAME  Char  8       $8.\n"
+        );
+    }
+}
@@ -0,0 +1,7 @@
+pub mod contents;
+pub mod info;
+pub mod validation;
+
+pub use contents::format_contents;
+pub use info::{format_info, format_schema};
+pub use validation::format_validation;
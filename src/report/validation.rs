@@ -0,0 +1,58 @@
+use crate::sas::sas7bdat::SasValidationReport;
+
+/// Render a validation report as `name: value` lines followed by one line per problem.
+pub fn format_validation(report: &SasValidationReport) -> String {
+    let rows = match (report.row_count, report.rows_found) {
+        (Some(declared), Some(found)) => format!("{} declared, {} found", declared, found),
+        _ => "unknown".to_string(),
+    };
+    let mut output = format!(
+        "File size:     {}\n\
+         Pages checked: {} of {}\n\
+         Observations:  {}\n",
+        report.file_size, report.pages_checked, report.page_count, rows
+    );
+    match report.issues.len() {
+        0 => output.push_str("No problems found\n"),
+        1 => output.push_str("1 problem found:\n"),
+        count => output.push_str(&format!("{} problems found:\n", count)),
+    }
+    for issue in &report.issues {
+        output.push_str(&format!("  {}\n", issue));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::SasFixture;
+    use crate::sas::sas7bdat::{validate, SasValidationIssue, SasValidationIssueKind};
+
+    #[test]
+    fn test_format_validation() {
+        let path = SasFixture::builder()
+            .build()
+            .write_temp("report_validation");
+        let mut report = validate(&path).unwrap();
+        assert_eq!(
+            format_validation(&report),
+            "File size:     9216\n\
+             Pages checked: 2 of 2\n\
+             Observations:  2 declared, 2 found\n\
+             No problems found\n"
+        );
+
+        report.rows_found = Some(1);
+        report.issues.push(SasValidationIssue::new(
+            SasValidationIssueKind::RowCountMismatch {
+                declared: 2,
+                found: 1,
+            },
+        ));
+        assert!(format_validation(&report).ends_with(
+            "Observations:  2 declared, 1 found\n\
+             1 problem found:\n  2 rows declared, but 1 found\n"
+        ));
+    }
+}
@@ -53,7 +53,7 @@ mod tests {
 
     #[test]
     fn test_endianness() {
-        assert_eq!(is_machine_little_endian(), true);
+        assert!(is_machine_little_endian());
     }
 
     #[test]
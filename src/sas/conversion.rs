@@ -1,17 +1,12 @@
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum ConversionType {
+    #[default]
     Native,
     Xport,
     IeeeBigEndian,
     IeeeLittleEndian,
 }
 
-impl Default for ConversionType {
-    fn default() -> Self {
-        ConversionType::Native
-    }
-}
-
 impl ConversionType {
     pub fn from_i32(value: i32) -> Option<Self> {
         match value {
@@ -32,15 +27,7 @@ impl ConversionType {
         }
     }
 
-    pub fn to_string(&self) -> String {
-        match self {
-            ConversionType::Native => "native".to_string(),
-            ConversionType::Xport => "xport".to_string(),
-            ConversionType::IeeeBigEndian => "ieee-big-endian".to_string(),
-            ConversionType::IeeeLittleEndian => "ieee-little-endian".to_string(),
-        }
-    }
-
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(value: &str) -> Option<Self> {
         match value {
             "native" => Some(ConversionType::Native),
@@ -52,6 +39,17 @@ impl ConversionType {
     }
 }
 
+impl std::fmt::Display for ConversionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionType::Native => write!(f, "native"),
+            ConversionType::Xport => write!(f, "xport"),
+            ConversionType::IeeeBigEndian => write!(f, "ieee-big-endian"),
+            ConversionType::IeeeLittleEndian => write!(f, "ieee-little-endian"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
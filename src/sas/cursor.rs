@@ -0,0 +1,223 @@
+use crate::types::{ReadStatError, SasEndian};
+
+/// A bounds-checked reader of the fixed-width binary fields of SAS files. Multi-byte values are
+/// read in the byte order the cursor was created with; reading past the end of the data is an
+/// error rather than a panic, so malformed files cannot crash the parser.
+///
+/// This replaces readstat's `sas_read2`, `sas_read4` and `sas_read8`, which assemble values in
+/// the machine's byte order and swap them afterwards.
+#[derive(Debug, Clone)]
+pub struct SasCursor<'a> {
+    data: &'a [u8],
+    position: usize,
+    endianness: SasEndian,
+}
+
+impl<'a> SasCursor<'a> {
+    /// Create a cursor at the start of `data`.
+    pub fn new(data: &'a [u8], endianness: SasEndian) -> Self {
+        Self {
+            data,
+            position: 0,
+            endianness,
+        }
+    }
+
+    /// Create a cursor at `offset` bytes from the start of `data`.
+    ///
+    /// # Returns
+    /// `ReadStatError::Read` if the offset is past the end of the data.
+    pub fn at(data: &'a [u8], offset: usize, endianness: SasEndian) -> Result<Self, ReadStatError> {
+        let mut cursor = Self::new(data, endianness);
+        cursor.seek(offset)?;
+        Ok(cursor)
+    }
+
+    pub fn endianness(&self) -> &SasEndian {
+        &self.endianness
+    }
+
+    /// The offset of the next byte to read, from the start of the data.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    /// Move to `position` bytes from the start of the data. The end of the data is a valid
+    /// position, from which nothing can be read.
+    pub fn seek(&mut self, position: usize) -> Result<(), ReadStatError> {
+        if position > self.data.len() {
+            return Err(ReadStatError::Read);
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    /// Move forward `count` bytes without reading them.
+    pub fn skip(&mut self, count: usize) -> Result<(), ReadStatError> {
+        self.read_bytes(count).map(|_| ())
+    }
+
+    /// Read the next `count` bytes as they are stored.
+    pub fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], ReadStatError> {
+        let end = self
+            .position
+            .checked_add(count)
+            .ok_or(ReadStatError::Read)?;
+        let bytes = self
+            .data
+            .get(self.position..end)
+            .ok_or(ReadStatError::Read)?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ReadStatError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+        Ok(array)
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, ReadStatError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, ReadStatError> {
+        let bytes = self.read_array()?;
+        Ok(match self.endianness {
+            SasEndian::Big => u16::from_be_bytes(bytes),
+            SasEndian::Little => u16::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, ReadStatError> {
+        let bytes = self.read_array()?;
+        Ok(match self.endianness {
+            SasEndian::Big => u32::from_be_bytes(bytes),
+            SasEndian::Little => u32::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, ReadStatError> {
+        let bytes = self.read_array()?;
+        Ok(match self.endianness {
+            SasEndian::Big => u64::from_be_bytes(bytes),
+            SasEndian::Little => u64::from_le_bytes(bytes),
+        })
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, ReadStatError> {
+        self.read_u64().map(|value| value as i64)
+    }
+
+    pub fn read_f64(&mut self) -> Result<f64, ReadStatError> {
+        self.read_u64().map(f64::from_bits)
+    }
+
+    /// Read a double stored in `width` bytes. SAS drops the least significant bytes of doubles
+    /// stored in fewer than 8 bytes, so the bytes present are the most significant ones and the
+    /// rest are zero. The bits are kept as they are, including the payload of missing values.
+    ///
+    /// # Returns
+    /// `ReadStatError::Parse` for widths over 8 bytes and `ReadStatError::Read` if the data ends
+    /// first. A width of 0 reads 0.
+    pub fn read_truncated_f64(&mut self, width: usize) -> Result<f64, ReadStatError> {
+        if width > 8 {
+            return Err(ReadStatError::Parse);
+        }
+        let bytes = self.read_bytes(width)?;
+        let mut full = [0; 8];
+        let bits = match self.endianness {
+            SasEndian::Big => {
+                full[..width].copy_from_slice(bytes);
+                u64::from_be_bytes(full)
+            }
+            SasEndian::Little => {
+                full[8 - width..].copy_from_slice(bytes);
+                u64::from_le_bytes(full)
+            }
+        };
+        Ok(f64::from_bits(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_integers_in_both_byte_orders() {
+        let data = [0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+
+        let mut big = SasCursor::new(&data, SasEndian::Big);
+        assert_eq!(big.read_u16(), Ok(0x0102));
+        assert_eq!(big.read_u8(), Ok(0x03));
+        assert_eq!(big.position(), 3);
+        let mut big = SasCursor::new(&data, SasEndian::Big);
+        assert_eq!(big.read_u32(), Ok(0x01020304));
+        let mut big = SasCursor::new(&data, SasEndian::Big);
+        assert_eq!(big.read_u64(), Ok(0x0102030405060708));
+
+        let mut little = SasCursor::new(&data, SasEndian::Little);
+        assert_eq!(little.read_u16(), Ok(0x0201));
+        assert_eq!(little.read_u16(), Ok(0x0403));
+        assert_eq!(little.read_u32(), Ok(0x08070605));
+        assert_eq!(little.remaining(), 0);
+    }
+
+    #[test]
+    fn test_read_signed_and_floats() {
+        let mut cursor = SasCursor::new(&[0xFF; 8], SasEndian::Little);
+        assert_eq!(cursor.read_i64(), Ok(-1));
+
+        let data = 1.5f64.to_be_bytes();
+        assert_eq!(SasCursor::new(&data, SasEndian::Big).read_f64(), Ok(1.5));
+        let data = (-2.25f64).to_le_bytes();
+        assert_eq!(
+            SasCursor::new(&data, SasEndian::Little).read_f64(),
+            Ok(-2.25)
+        );
+    }
+
+    #[test]
+    fn test_read_truncated_f64() {
+        // 1.5 is exactly representable in the 3 most significant bytes
+        let bits = 1.5f64.to_bits();
+        let data = bits.to_le_bytes();
+        assert_eq!(
+            SasCursor::new(&data[5..], SasEndian::Little).read_truncated_f64(3),
+            Ok(1.5)
+        );
+        let data = bits.to_be_bytes();
+        assert_eq!(
+            SasCursor::new(&data[..3], SasEndian::Big).read_truncated_f64(3),
+            Ok(1.5)
+        );
+
+        let mut cursor = SasCursor::new(&data, SasEndian::Big);
+        assert_eq!(cursor.read_truncated_f64(0), Ok(0.0));
+        assert_eq!(cursor.read_truncated_f64(9), Err(ReadStatError::Parse));
+    }
+
+    #[test]
+    fn test_short_input_is_an_error() {
+        let data = [0x01];
+        let mut cursor = SasCursor::new(&data, SasEndian::Little);
+        assert_eq!(cursor.read_u16(), Err(ReadStatError::Read));
+        assert_eq!(cursor.position(), 0);
+        assert_eq!(cursor.read_truncated_f64(2), Err(ReadStatError::Read));
+        assert_eq!(cursor.skip(usize::MAX), Err(ReadStatError::Read));
+        assert_eq!(cursor.read_u8(), Ok(0x01));
+        assert_eq!(cursor.read_u8(), Err(ReadStatError::Read));
+
+        assert!(SasCursor::at(&data, 1, SasEndian::Big).is_ok());
+        assert_eq!(
+            SasCursor::at(&data, 2, SasEndian::Big).err(),
+            Some(ReadStatError::Read)
+        );
+    }
+}
@@ -0,0 +1,574 @@
+use crate::sas::temporal::{naive_to_sas_date, naive_to_sas_datetime};
+use crate::types::{
+    ReadStatError, ReadStatType, ReadStatValue, ReadStatValueType, ReadStatVariable, SasFormat,
+    SasFormatCategory, SasMissing,
+};
+use chrono::{NaiveDate, NaiveTime};
+
+/// Two-digit years are read as the 100 years starting with this one, as SAS does with its
+/// default `YEARCUTOFF=1926` option.
+const YEAR_CUTOFF: i32 = 1926;
+
+const MONTHS: [&str; 12] = [
+    "JANUARY",
+    "FEBRUARY",
+    "MARCH",
+    "APRIL",
+    "MAY",
+    "JUNE",
+    "JULY",
+    "AUGUST",
+    "SEPTEMBER",
+    "OCTOBER",
+    "NOVEMBER",
+    "DECEMBER",
+];
+
+/// A run of digits or of letters in a date, once separators are dropped.
+#[derive(Debug, Clone, PartialEq)]
+enum DateToken<'a> {
+    Number(&'a str),
+    Word(&'a str),
+}
+
+/// Parse text into a value with `informat`, as a SAS `INPUT` statement does: only the first
+/// `w` characters are read when the informat has a width, numbers without a decimal point get
+/// the informat's `d` implied decimals, dates become days since 1960-01-01, datetimes seconds
+/// since 1960-01-01T00:00:00 and times seconds since midnight.
+///
+/// Blank text and `.` read as system missing numbers, and `.A` to `.Z` and `._` as special
+/// missing values. Two-digit years are read as years from 1926 to 2025. Character informats
+/// drop trailing blanks, and all but `$CHAR` also drop leading ones. Numeric informats that are
+/// not parsed here, such as `ROMAN`, read standard numbers.
+///
+/// # Arguments
+/// * `text` - The text to parse.
+/// * `informat` - The informat to parse it with, eg `DATE9.`, `ANYDTDTE.` or `COMMA12.`.
+///
+/// # Returns
+/// `ReadStatError::ConvertBadString` if `text` is not a number, `ReadStatError::BadTimestampString`
+/// if it is not a date, time or datetime, and `ReadStatError::BadFormatString` for
+/// user-defined informats.
+pub fn parse_value(text: &str, informat: &SasFormat) -> Result<ReadStatValue, ReadStatError> {
+    let text = match informat.width {
+        Some(width) => match text.char_indices().nth(width as usize) {
+            Some((end, _)) => &text[..end],
+            None => text,
+        },
+        None => text,
+    };
+    if informat.is_character() {
+        let text = match informat.name.as_str() {
+            "$CHAR" => text.trim_end().to_string(),
+            "$UPCASE" => text.trim().to_uppercase(),
+            _ => text.trim().to_string(),
+        };
+        return Ok(ReadStatValue::new(
+            ReadStatType::String,
+            None,
+            ReadStatValueType::String(text),
+        ));
+    }
+    if informat.category == SasFormatCategory::UserDefined {
+        return Err(ReadStatError::BadFormatString);
+    }
+
+    let text = text.trim();
+    if let Some(kind) = parse_missing(text) {
+        return Ok(ReadStatValue::missing(kind));
+    }
+    let number = parse_number_text(text, informat)?;
+    Ok(ReadStatValue::new(
+        ReadStatType::Double,
+        None,
+        ReadStatValueType::Double(number),
+    ))
+}
+
+/// Parse text into a value of `variable` with the variable's informat, as `parse_value` does.
+/// Variables without an informat read standard numbers, or characters as `$w.` does, where `w`
+/// is the storage width.
+pub fn parse_variable_value(
+    text: &str,
+    variable: &ReadStatVariable,
+) -> Result<ReadStatValue, ReadStatError> {
+    let informat = variable
+        .sas_informat()
+        .unwrap_or_else(|| match variable.type_ {
+            ReadStatType::String => SasFormat::new(
+                "$".to_string(),
+                u16::try_from(variable.storage_width)
+                    .ok()
+                    .filter(|w| *w > 0),
+                None,
+                SasFormatCategory::Character,
+            ),
+            _ => SasFormat::new("BEST".to_string(), None, None, SasFormatCategory::Numeric),
+        });
+    parse_value(text, &informat)
+}
+
+/// Return the kind of missing value `text` is, or None if it is not one.
+fn parse_missing(text: &str) -> Option<SasMissing> {
+    match text.as_bytes() {
+        [] | [b'.'] => Some(SasMissing::System),
+        [b'.', tag] if tag.is_ascii_alphabetic() || *tag == b'_' => {
+            Some(SasMissing::from_tag(tag.to_ascii_uppercase()))
+        }
+        _ => None,
+    }
+}
+
+fn parse_number_text(text: &str, informat: &SasFormat) -> Result<f64, ReadStatError> {
+    let decimals = informat.decimals;
+    let bad_timestamp = |_| ReadStatError::BadTimestampString;
+    match informat.name.as_str() {
+        "DATE" => parse_date_tokens(&date_tokens(text))
+            .ok_or(ReadStatError::BadTimestampString)
+            .map(naive_to_sas_date),
+        "MONYY" => match date_tokens(text)[..] {
+            [DateToken::Word(month), DateToken::Number(year)] => {
+                date(parse_year(year), parse_month(month), Some(1))
+            }
+            _ => None,
+        }
+        .ok_or(ReadStatError::BadTimestampString)
+        .map(naive_to_sas_date),
+        "E8601DA" => NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .map(naive_to_sas_date)
+            .map_err(bad_timestamp),
+        "ANYDTDTE" => parse_any_date(text)
+            .or_else(|| parse_datetime(text).map(|(date, _)| date))
+            .ok_or(ReadStatError::BadTimestampString)
+            .map(naive_to_sas_date),
+        "DATETIME" | "ANYDTDTM" => parse_datetime(text)
+            .or_else(|| match informat.name.as_str() {
+                "ANYDTDTM" => parse_any_date(text).map(|date| (date, NaiveTime::MIN)),
+                _ => None,
+            })
+            .ok_or(ReadStatError::BadTimestampString)
+            .map(|(date, time)| naive_to_sas_datetime(date.and_time(time))),
+        "E8601DT" => text
+            .split_once(['T', 't'])
+            .and_then(|(date, time)| {
+                let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+                Some(naive_to_sas_datetime(date.and_time(parse_iso_time(time)?)))
+            })
+            .ok_or(ReadStatError::BadTimestampString),
+        "TIME" => parse_time(text).ok_or(ReadStatError::BadTimestampString),
+        "E8601TM" => parse_iso_time(text)
+            .map(seconds_since_midnight)
+            .ok_or(ReadStatError::BadTimestampString),
+        "ANYDTTME" => parse_time(text)
+            .or_else(|| parse_datetime(text).map(|(_, time)| seconds_since_midnight(time)))
+            .ok_or(ReadStatError::BadTimestampString),
+        "COMMA" | "DOLLAR" => parse_comma(text, decimals),
+        "PERCENT" => match text.strip_suffix('%') {
+            Some(text) => parse_comma(text, None).map(|number| number / 100.0),
+            None => parse_comma(text, decimals),
+        },
+        name => match date_order(name) {
+            Some(order) => parse_ordered_date(&date_tokens(text), order)
+                .ok_or(ReadStatError::BadTimestampString)
+                .map(naive_to_sas_date),
+            None if informat.temporal_kind().is_some() => Err(ReadStatError::BadFormatString),
+            None => parse_number(text, decimals),
+        },
+    }
+}
+
+/// Parse a standard number, such as `-1234.5` or `1.2E6`. Without a decimal point or
+/// exponent, `decimals` decimals are implied, so that `12345` reads as 123.45 with `8.2`.
+fn parse_number(text: &str, decimals: Option<u16>) -> Result<f64, ReadStatError> {
+    // Rust also parses `inf` and `NaN`, and overflows to infinity, which SAS does not
+    if text.is_empty()
+        || !text
+            .bytes()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'))
+    {
+        return Err(ReadStatError::ConvertBadString);
+    }
+    let number: f64 = text.parse().map_err(|_| ReadStatError::ConvertBadString)?;
+    if !number.is_finite() {
+        return Err(ReadStatError::ConvertBadString);
+    }
+    match decimals {
+        Some(decimals) if !text.contains(['.', 'e', 'E']) => {
+            Ok(number / 10f64.powi(decimals as i32))
+        }
+        _ => Ok(number),
+    }
+}
+
+/// `COMMA` and `DOLLAR`: a number with commas, blanks, dollar and percent signs dropped, and
+/// negative when it is in parentheses.
+fn parse_comma(text: &str, decimals: Option<u16>) -> Result<f64, ReadStatError> {
+    let (negative, text) = match text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let digits: String = text
+        .chars()
+        .filter(|c| !matches!(c, ',' | ' ' | '$' | '%'))
+        .collect();
+    let number = parse_number(&digits, decimals)?;
+    Ok(if negative { -number } else { number })
+}
+
+/// Split a date into runs of digits and of letters, dropping anything else as separators.
+fn date_tokens(text: &str) -> Vec<DateToken<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_alphanumeric()) {
+        rest = &rest[start..];
+        let is_number = rest.starts_with(|c: char| c.is_ascii_digit());
+        let end = rest
+            .find(|c: char| {
+                if is_number {
+                    !c.is_ascii_digit()
+                } else {
+                    !c.is_ascii_alphabetic()
+                }
+            })
+            .unwrap_or(rest.len());
+        let (token, tail) = rest.split_at(end);
+        tokens.push(if is_number {
+            DateToken::Number(token)
+        } else {
+            DateToken::Word(token)
+        });
+        rest = tail;
+    }
+    tokens
+}
+
+fn date(year: Option<i32>, month: Option<u32>, day: Option<u32>) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year?, month?, day?)
+}
+
+/// Read a year of two or four digits.
+fn parse_year(year: &str) -> Option<i32> {
+    let number: i32 = year.parse().ok()?;
+    match year.len() {
+        2 if 1900 + number >= YEAR_CUTOFF => Some(1900 + number),
+        2 => Some(2000 + number),
+        4 => Some(number),
+        _ => None,
+    }
+}
+
+/// Read a month from its name or its first three letters, in any case.
+fn parse_month(month: &str) -> Option<u32> {
+    let month = month.to_ascii_uppercase();
+    if month.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|name| name.starts_with(&month))
+        .map(|index| index as u32 + 1)
+}
+
+fn parse_day(day: &str) -> Option<u32> {
+    (day.len() <= 2).then(|| day.parse().ok()).flatten()
+}
+
+/// `DATE`: a day, a month name and a year, as in `31JAN2024` or `31-JAN-24`.
+fn parse_date_tokens(tokens: &[DateToken<'_>]) -> Option<NaiveDate> {
+    match tokens {
+        [DateToken::Number(day), DateToken::Word(month), DateToken::Number(year)] => {
+            date(parse_year(year), parse_month(month), parse_day(day))
+        }
+        _ => None,
+    }
+}
+
+/// The informats reading a day, a month and a year as numbers, in the order of their name.
+fn date_order(name: &str) -> Option<[usize; 3]> {
+    match name {
+        "DDMMYY" => Some([0, 1, 2]),
+        "MMDDYY" => Some([1, 0, 2]),
+        "YYMMDD" => Some([2, 1, 0]),
+        _ => None,
+    }
+}
+
+/// Read a date of three numbers, or of one number of 6 or 8 digits. `order` gives the
+/// position of the day, the month and the year.
+fn parse_ordered_date(tokens: &[DateToken<'_>], order: [usize; 3]) -> Option<NaiveDate> {
+    let [day, month, year] = order;
+    let parts: Vec<&str> = match tokens {
+        [DateToken::Number(a), DateToken::Number(b), DateToken::Number(c)] => vec![a, b, c],
+        [DateToken::Number(digits)] if matches!(digits.len(), 6 | 8) => {
+            let year_length = digits.len() - 4;
+            let mut lengths = [2, 2, 2];
+            lengths[year] = year_length;
+            let mut start = 0;
+            lengths
+                .iter()
+                .map(|length| {
+                    let part = &digits[start..start + length];
+                    start += length;
+                    part
+                })
+                .collect()
+        }
+        _ => return None,
+    };
+    date(
+        parse_year(parts[year]),
+        parse_day(parts[month]),
+        parse_day(parts[day]),
+    )
+}
+
+/// `ANYDTDTE`: any of the dates SAS recognizes, ie `2024-01-31`, `20240131`, `31JAN2024`,
+/// `JAN2024`, `January 31, 2024` and `01/31/2024`. Dates of three numbers are read month
+/// first, unless the first is too large to be a month.
+fn parse_any_date(text: &str) -> Option<NaiveDate> {
+    let tokens = date_tokens(text);
+    match tokens[..] {
+        [DateToken::Number(year), DateToken::Number(_), DateToken::Number(_)]
+            if year.len() == 4 =>
+        {
+            parse_ordered_date(&tokens, [2, 1, 0])
+        }
+        [DateToken::Number(digits)] if digits.len() == 8 => parse_ordered_date(&tokens, [2, 1, 0]),
+        [DateToken::Number(_)] => parse_ordered_date(&tokens, [1, 0, 2]),
+        [DateToken::Number(_), DateToken::Word(_), DateToken::Number(_)] => {
+            parse_date_tokens(&tokens)
+        }
+        [DateToken::Word(month), DateToken::Number(year)] => {
+            date(parse_year(year), parse_month(month), Some(1))
+        }
+        [DateToken::Word(month), DateToken::Number(day), DateToken::Number(year)] => {
+            date(parse_year(year), parse_month(month), parse_day(day))
+        }
+        [DateToken::Number(first), DateToken::Number(_), DateToken::Number(_)] => {
+            match first.parse::<u32>().ok()? {
+                1..=12 => parse_ordered_date(&tokens, [1, 0, 2]),
+                _ => parse_ordered_date(&tokens, [0, 1, 2]),
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `DATETIME`: a date and a time, separated by `T`, a blank or a colon, as in
+/// `31JAN2024:13:45:07` or `2024-01-31 13:45`.
+fn parse_datetime(text: &str) -> Option<(NaiveDate, NaiveTime)> {
+    let split = text
+        .char_indices()
+        .find(|(index, c)| {
+            matches!(c, 'T' | 't')
+                && text[..*index].ends_with(|c: char| c.is_ascii_digit())
+                && text[index + 1..].starts_with(|c: char| c.is_ascii_digit())
+        })
+        .map(|(index, _)| (&text[..index], &text[index + 1..]))
+        .or_else(|| {
+            text.rsplit_once(char::is_whitespace)
+                .filter(|(_, time)| time.contains(':'))
+        })
+        .or_else(|| text.split_once(':'))?;
+    let (date, time) = split;
+    let date = parse_any_date(date.trim())?;
+    let seconds = parse_time(time.trim())?;
+    if !(0.0..86_400.0).contains(&seconds) {
+        return None;
+    }
+    let whole = seconds.trunc() as u32;
+    let nanos = ((seconds - seconds.trunc()) * 1e9).round() as u32;
+    Some((
+        date,
+        NaiveTime::from_num_seconds_from_midnight_opt(whole, nanos)?,
+    ))
+}
+
+/// `TIME`: hours, minutes and optionally seconds with a fraction, separated by colons and
+/// optionally followed by `AM` or `PM`, as in `13:45`, `1:45:07.5 PM` or `-27:00`. Hours may
+/// exceed 24, for durations.
+fn parse_time(text: &str) -> Option<f64> {
+    let upper = text.to_ascii_uppercase();
+    let (text, meridiem) = match upper.strip_suffix("AM").or(upper.strip_suffix("PM")) {
+        Some(time) => (time.trim_end(), Some(upper.ends_with("PM"))),
+        None => (upper.as_str(), None),
+    };
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let mut parts = text.split(':');
+    let hours: u64 = parts.next().filter(|h| !h.is_empty())?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds: f64 = match parts.next() {
+        Some(seconds) if !seconds.starts_with(['+', '-']) => seconds.parse().ok()?,
+        Some(_) => return None,
+        None => 0.0,
+    };
+    if parts.next().is_some() || minutes >= 60 || !(0.0..60.0).contains(&seconds) {
+        return None;
+    }
+    let hours = match meridiem {
+        Some(_) if !(1..=12).contains(&hours) => return None,
+        Some(pm) => hours % 12 + if pm { 12 } else { 0 },
+        None => hours,
+    };
+    let total = hours.checked_mul(3600)?.checked_add(minutes * 60)? as f64 + seconds;
+    Some(if negative { -total } else { total })
+}
+
+/// `E8601TM`: `hh:mm:ss` with an optional fraction.
+fn parse_iso_time(text: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(text, "%H:%M:%S%.f").ok()
+}
+
+fn seconds_since_midnight(time: NaiveTime) -> f64 {
+    let midnight = time - NaiveTime::MIN;
+    midnight.num_seconds() as f64 + midnight.subsec_nanos() as f64 / 1e9
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str, informat: &str) -> Result<f64, ReadStatError> {
+        let value = parse_value(text, &SasFormat::from_str(informat).unwrap())?;
+        match value.value {
+            ReadStatValueType::Double(number) => Ok(number),
+            other => panic!("{:?} is not a number", other),
+        }
+    }
+
+    // 2024-01-31 in days since 1960-01-01
+    const DATE: f64 = 23406.0;
+
+    #[test]
+    fn test_parse_dates() {
+        let cases = [
+            ("31JAN2024", "DATE9."),
+            ("31-jan-24", "DATE."),
+            ("31/01/2024", "DDMMYY10."),
+            ("310124", "DDMMYY6."),
+            ("01/31/2024", "MMDDYY10."),
+            ("01-31-24", "MMDDYY."),
+            ("20240131", "YYMMDD8."),
+            ("2024-01-31", "YYMMDD10."),
+            ("2024-01-31", "E8601DA."),
+            ("2024-01-31", "ANYDTDTE."),
+            ("20240131", "ANYDTDTE."),
+            ("31JAN2024", "ANYDTDTE."),
+            ("01/31/2024", "ANYDTDTE."),
+            ("31/01/2024", "ANYDTDTE."),
+            ("January 31, 2024", "ANYDTDTE."),
+            ("2024-01-31T13:45:00", "ANYDTDTE."),
+            ("31JAN2024xxxx", "DATE9."),
+        ];
+        for (text, informat) in cases {
+            assert_eq!(parse(text, informat), Ok(DATE), "{} {}", text, informat);
+        }
+        assert_eq!(parse("JAN2024", "MONYY7."), Ok(DATE - 30.0));
+        assert_eq!(parse("01JAN60", "DATE7."), Ok(0.0));
+        assert_eq!(
+            parse("01JAN25", "DATE7."),
+            Ok(parse("01JAN2025", "DATE9.").unwrap())
+        );
+        assert_eq!(
+            parse("01JAN26", "DATE7."),
+            Ok(parse("01JAN1926", "DATE9.").unwrap())
+        );
+        for (text, informat) in [
+            ("31FEB2024", "DATE9."),
+            ("13/13/2024", "MMDDYY10."),
+            ("x", "ANYDTDTE."),
+        ] {
+            assert_eq!(
+                parse(text, informat),
+                Err(ReadStatError::BadTimestampString)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_datetimes_and_times() {
+        let datetime = DATE * 86_400.0 + 49_507.25;
+        assert_eq!(parse("31JAN2024:13:45:07.25", "DATETIME22."), Ok(datetime));
+        assert_eq!(parse("31jan2024 13:45:07.25", "DATETIME."), Ok(datetime));
+        assert_eq!(parse("2024-01-31T13:45:07.25", "E8601DT."), Ok(datetime));
+        assert_eq!(parse("2024-01-31 13:45:07.25", "ANYDTDTM."), Ok(datetime));
+        assert_eq!(parse("2024-01-31", "ANYDTDTM."), Ok(DATE * 86_400.0));
+
+        assert_eq!(parse("13:45", "TIME."), Ok(49_500.0));
+        assert_eq!(parse("1:45:07.5 PM", "TIME."), Ok(49_507.5));
+        assert_eq!(parse("12:00 AM", "TIME."), Ok(0.0));
+        assert_eq!(parse("-27:00", "TIME."), Ok(-97_200.0));
+        assert_eq!(parse("13:45:07", "E8601TM."), Ok(49_507.0));
+        assert_eq!(parse("31JAN2024:13:45:07", "ANYDTTME."), Ok(49_507.0));
+        assert_eq!(
+            parse("13:75", "TIME."),
+            Err(ReadStatError::BadTimestampString)
+        );
+        for informat in ["TIME.", "ANYDTTME.", "DATETIME.", "ANYDTDTM."] {
+            let text = match informat {
+                "DATETIME." | "ANYDTDTM." => "31JAN2024:10000000000000000:00",
+                _ => "10000000000000000:00",
+            };
+            assert_eq!(
+                parse(text, informat),
+                Err(ReadStatError::BadTimestampString)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_numbers() {
+        assert_eq!(parse("  12.5 ", "BEST."), Ok(12.5));
+        assert_eq!(parse("-1.2E3", "BEST12."), Ok(-1200.0));
+        assert_eq!(parse("12345", "8.2"), Ok(123.45));
+        assert_eq!(parse("123.4", "8.2"), Ok(123.4));
+        assert_eq!(parse("12345678", "4."), Ok(1234.0));
+        assert_eq!(parse("1,234,567.89", "COMMA12."), Ok(1_234_567.89));
+        assert_eq!(parse("($1,234.50)", "DOLLAR12."), Ok(-1234.5));
+        assert_eq!(parse("12.5%", "PERCENT."), Ok(0.125));
+        assert_eq!(parse("inf", "BEST."), Err(ReadStatError::ConvertBadString));
+        assert_eq!(
+            parse("1e400", "BEST."),
+            Err(ReadStatError::ConvertBadString)
+        );
+        assert_eq!(
+            parse("-1e400", "COMMA."),
+            Err(ReadStatError::ConvertBadString)
+        );
+        assert_eq!(parse("12a", "COMMA."), Err(ReadStatError::ConvertBadString));
+        assert_eq!(
+            parse_value("1", &SasFormat::from_str("YESNO.").unwrap()),
+            Err(ReadStatError::BadFormatString)
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_and_characters() {
+        assert!(parse("", "BEST.").unwrap().is_nan());
+        assert!(parse(" . ", "DATE9.").unwrap().is_nan());
+        let value = parse_value(".a", &SasFormat::from_str("BEST.").unwrap()).unwrap();
+        assert_eq!(value.missing, Some(SasMissing::Letter('A')));
+        assert!(value.is_missing());
+        let value = parse_value("._", &SasFormat::from_str("BEST.").unwrap()).unwrap();
+        assert_eq!(value.missing_kind(), Some(SasMissing::Underscore));
+
+        let text = |text: &str, informat: &str| {
+            parse_value(text, &SasFormat::from_str(informat).unwrap()).map(|value| value.value)
+        };
+        let string = |text: &str| Ok(ReadStatValueType::String(text.to_string()));
+        assert_eq!(text("  abc  ", "$CHAR."), string("  abc"));
+        assert_eq!(text("  abc  ", "$10."), string("abc"));
+        assert_eq!(text("abcdef", "$CHAR3."), string("abc"));
+        assert_eq!(text("abc", "$UPCASE."), string("ABC"));
+
+        let variable = ReadStatVariable::builder()
+            .informat("MMDDYY10.".to_string())
+            .build()
+            .unwrap();
+        let value = parse_variable_value("01/31/2024", &variable).unwrap();
+        assert_eq!(value.value, ReadStatValueType::Double(DATE));
+    }
+}
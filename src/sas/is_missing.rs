@@ -1,37 +1,15 @@
-use crate::types::ReadStatValue;
+use crate::types::{ReadStatValue, SasMissing};
 
-/// Check if the value is missing, by checking if the value is tagged missing.
+/// Check if the value is tagged missing, ie one of the special missing values `._` and `.A` to
+/// `.Z`, as resolved by `SasMissing::from_tag`.
 ///
 /// # Arguments
 /// * `value` - A `ReadStatValue` struct.
 ///
 /// # Returns
-/// A boolean value. If the value is missing, return `true`, otherwise return `false`.
-///
-/// # Original C function
-/// ```c
-/// void sas_assign_tag(readstat_value_t *value, uint8_t tag) {
-///    /* We accommodate two tag schemes. In the first, the tag is an ASCII code
-///     * given by uint8_t tag above. System missing is represented by an ASCII
-///     * period. In the second scheme, (tag-2) is an offset from 'A', except when
-///     * tag == 0, in which case it represents an underscore, or tag == 1, in
-///     * which case it represents system-missing.
-///     */
-///    if (tag == 0) {
-///        tag = '_';
-///    } else if (tag >= 2 && tag < 28) {
-///        tag = 'A' + (tag - 2);
-///    }
-///    if (sas_validate_tag(tag) == READSTAT_OK) {
-///        value->tag = tag;
-///        value->is_tagged_missing = 1;
-///    } else {
-///        value->tag = 0;
-///        value->is_system_missing = 1;
-///    }
-///}
-/// ```
+/// A boolean value. If the value is tagged missing, return `true`, otherwise return `false`.
 ///
+/// ```text
 /// /* Values can be missing in one of three ways:
 /// * 1. "System missing", delivered to value handlers as NaN. Occurs in all file
 /// *    types. The most common kind of missing value.
@@ -40,61 +18,101 @@ use crate::types::ReadStatValue;
 /// *    corresponding to Stata's .a, .b, values etc. Occurs only in Stata and
 /// *    SAS files.
 /// */
+/// ```
 pub fn is_tagged_missing(value: &ReadStatValue) -> bool {
-    let tags = &value.tags;
-    if tags.is_empty() {
-        return false;
-    } else if tags[0].is_empty() {
-        return false;
-    } else {
-        let tag = tags[0].as_bytes()[0];
-        if tag == 0 {
-            return true;
-        } else if tag >= 2 && tag < 28 {
-            return true;
-        } else if tag == '.' as u8 {
-            return true;
-        } else {
-            return false;
-        }
-    }
+    matches!(
+        value.missing_kind(),
+        Some(SasMissing::Underscore | SasMissing::Letter(_))
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::ReadStatValue;
+    use crate::types::ReadStatValueType;
+
+    fn missing(missing: Option<SasMissing>) -> ReadStatValue {
+        ReadStatValue::builder()
+            .missing(missing)
+            .value(ReadStatValueType::Double(f64::NAN))
+            .build()
+            .unwrap()
+    }
 
     #[test]
     fn test_is_tagged_missing() {
         let value = ReadStatValue::builder().build().unwrap();
-        assert_eq!(is_tagged_missing(&value), false);
+        assert!(!is_tagged_missing(&value));
     }
 
     #[test]
-    fn test_is_tagged_missing_with_tag_0() {
-        let value = ReadStatValue::builder()
-            .tags(vec![String::from(".")])
-            .build()
-            .unwrap();
-        assert_eq!(is_tagged_missing(&value), true);
+    fn test_is_tagged_missing_system_missing() {
+        assert!(!is_tagged_missing(&missing(None)));
+        assert!(!is_tagged_missing(&missing(Some(SasMissing::System))));
     }
 
     #[test]
-    fn test_is_tagged_missing_with_tag_2() {
-        let value = ReadStatValue::builder()
-            .tags(vec![String::from("A")])
-            .build()
-            .unwrap();
-        assert_eq!(is_tagged_missing(&value), false);
+    fn test_is_tagged_missing_with_letter() {
+        assert!(is_tagged_missing(&missing(Some(SasMissing::Letter('A')))));
+        assert!(is_tagged_missing(&missing(Some(SasMissing::Letter('Z')))));
+        assert!(is_tagged_missing(&missing(Some(SasMissing::Underscore))));
+    }
+
+    #[test]
+    fn test_sas_missing_from_tag() {
+        // ASCII scheme
+        assert_eq!(SasMissing::from_tag(b'_'), SasMissing::Underscore);
+        assert_eq!(SasMissing::from_tag(b'R'), SasMissing::Letter('R'));
+        assert_eq!(SasMissing::from_tag(b'.'), SasMissing::System);
+        // offset scheme
+        assert_eq!(SasMissing::from_tag(0), SasMissing::Underscore);
+        assert_eq!(SasMissing::from_tag(1), SasMissing::System);
+        assert_eq!(SasMissing::from_tag(2), SasMissing::Letter('A'));
+        assert_eq!(SasMissing::from_tag(27), SasMissing::Letter('Z'));
+        assert_eq!(SasMissing::from_tag(28), SasMissing::System);
+    }
+
+    #[test]
+    fn test_sas_missing_from_nan_bits() {
+        let missing = |tag: u8| 0xFFFF_0000_0000_0000 | ((!tag as u64) << 40);
+        assert_eq!(
+            SasMissing::from_nan_bits(missing(b'R')),
+            Some(SasMissing::Letter('R'))
+        );
+        assert_eq!(
+            SasMissing::from_nan_bits(missing(b'D')),
+            Some(SasMissing::Letter('D'))
+        );
+        assert_eq!(
+            SasMissing::from_nan_bits(missing(b'_')),
+            Some(SasMissing::Underscore)
+        );
+        assert_eq!(
+            SasMissing::from_nan_bits(missing(b'.')),
+            Some(SasMissing::System)
+        );
+        assert_eq!(
+            SasMissing::from_nan_bits(missing(4)),
+            Some(SasMissing::Letter('C'))
+        );
+        assert_eq!(SasMissing::from_nan_bits(1.5f64.to_bits()), None);
+    }
+
+    #[test]
+    fn test_sas_missing_display() {
+        assert_eq!(SasMissing::System.to_string(), ".");
+        assert_eq!(SasMissing::Underscore.to_string(), "._");
+        assert_eq!(SasMissing::Letter('R').to_string(), ".R");
+        assert_eq!(SasMissing::Letter('D').tag(), Some('D'));
+        assert_eq!(SasMissing::System.tag(), None);
     }
 
     #[test]
-    fn test_is_tagged_missing_with_tag_28() {
+    fn test_is_tagged_missing_not_nan() {
         let value = ReadStatValue::builder()
-            .tags(vec![String::from("Z")])
+            .missing(Some(SasMissing::Letter('A')))
             .build()
             .unwrap();
-        assert_eq!(is_tagged_missing(&value), false);
+        assert!(!is_tagged_missing(&value));
     }
 }
@@ -1,4 +1,9 @@
 pub mod byte_swap;
 pub mod conversion;
+pub mod cursor;
+pub mod informat;
 pub mod is_missing;
-pub mod read_int;
+pub mod render;
+pub mod sas7bcat;
+pub mod sas7bdat;
+pub mod temporal;
@@ -0,0 +1,739 @@
+use crate::sas::sas7bcat::SasCatalog;
+use crate::sas::temporal::{format_iso8601, sas_date_to_naive, sas_datetime_to_naive};
+use crate::types::{
+    ReadStatType, ReadStatValue, ReadStatValueType, ReadStatVariable, SasFormat, SasFormatCategory,
+};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
+
+const MONTHS: [&str; 12] = [
+    "JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC",
+];
+
+/// The order of the parts of a `DDMMYY`, `MMDDYY` or `YYMMDD` date.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DateOrder {
+    DayMonthYear,
+    MonthDayYear,
+    YearMonthDay,
+}
+
+/// Render a value as SAS displays it with `format`, as the `PUT` function does: numbers are
+/// right-aligned and characters left-aligned in the width of the format, and numbers that do
+/// not fit are shown as asterisks. Missing numbers are shown as `.`, or as their tag for
+/// special missing values such as `.A`.
+///
+/// User-defined formats are looked up in `catalog`. Values without a label, and values of
+/// formats missing from the catalog, are displayed with `BEST` for numbers and as they are for
+/// characters. Numeric formats that are not rendered here, such as `ROMAN`, fall back to
+/// `BEST`, and date, time and datetime formats fall back to ISO 8601.
+///
+/// # Arguments
+/// * `value` - The value to display.
+/// * `format` - The format to display it with, eg `DATE9.` or `COMMA12.2`.
+/// * `catalog` - The catalog holding user-defined formats, if any.
+pub fn render_value(
+    value: &ReadStatValue,
+    format: &SasFormat,
+    catalog: Option<&SasCatalog>,
+) -> String {
+    let width = format.width.map(usize::from);
+    if format.is_user_defined() {
+        let label = catalog
+            .and_then(|catalog| catalog.format(&format.name))
+            .and_then(|user_format| user_format.label(value));
+        match (label, width) {
+            (Some(label), Some(width)) => return align_left(label, width),
+            (Some(label), None) => return label.to_string(),
+            _ => {}
+        }
+    }
+    match (&value.value, value.as_f64()) {
+        (ReadStatValueType::String(text), _) => render_text(text, format, width),
+        (_, Some(number)) if format.is_user_defined() || format.is_character() => {
+            let width = width.unwrap_or(12);
+            fit(
+                render_missing(value, number).unwrap_or_else(|| best(number, width)),
+                width,
+            )
+        }
+        (_, Some(number)) => render_number(value, number, format),
+        (_, None) => String::new(),
+    }
+}
+
+/// Render a value of `variable` with the variable's format, as `render_value` does. Variables
+/// without a format are displayed with `BEST12.` for numbers and `$w.` for characters, where
+/// `w` is the storage width.
+pub fn render_variable_value(
+    value: &ReadStatValue,
+    variable: &ReadStatVariable,
+    catalog: Option<&SasCatalog>,
+) -> String {
+    let format = variable
+        .sas_format()
+        .unwrap_or_else(|| match variable.type_ {
+            ReadStatType::String => SasFormat::new(
+                "$".to_string(),
+                u16::try_from(variable.storage_width)
+                    .ok()
+                    .filter(|w| *w > 0),
+                None,
+                SasFormatCategory::Character,
+            ),
+            _ => SasFormat::new(
+                "BEST".to_string(),
+                Some(12),
+                None,
+                SasFormatCategory::Numeric,
+            ),
+        });
+    render_value(value, &format, catalog)
+}
+
+/// Return how a missing number is displayed: its tag for special missing values, or `.`.
+fn render_missing(value: &ReadStatValue, number: f64) -> Option<String> {
+    if !number.is_nan() {
+        return None;
+    }
+    match value.missing_kind().and_then(|kind| kind.tag()) {
+        Some(tag) => Some(tag.to_string()),
+        None => Some(".".to_string()),
+    }
+}
+
+/// Character formats keep leading blanks; `$UPCASE` also converts to upper case.
+fn render_text(text: &str, format: &SasFormat, width: Option<usize>) -> String {
+    let text = if format.name == "$UPCASE" {
+        text.to_uppercase()
+    } else {
+        text.to_string()
+    };
+    match width {
+        Some(width) => align_left(&text, width),
+        None => text,
+    }
+}
+
+/// The width of a format whose specification leaves it out, as SAS defaults it.
+fn default_width(name: &str) -> Option<usize> {
+    let width = match name {
+        "DATE" => 7,
+        "MONYY" => 5,
+        "WEEKDATE" => 29,
+        "E8601DA" => 10,
+        "DATETIME" => 16,
+        "E8601DT" => 19,
+        "TIME" | "TOD" | "E8601TM" => 8,
+        "HHMM" => 5,
+        "COMMA" | "DOLLAR" | "PERCENT" => 6,
+        "Z" => 1,
+        "BEST" | "F" | "" => 12,
+        _ if date_order(name).is_some() => 8,
+        _ => return None,
+    };
+    Some(width)
+}
+
+fn render_number(value: &ReadStatValue, number: f64, format: &SasFormat) -> String {
+    let name = format.name.as_str();
+    let Some(width) = format
+        .width
+        .map(usize::from)
+        .or_else(|| default_width(name))
+    else {
+        // Date, time and datetime formats not rendered here have no default width to fill
+        return render_missing(value, number)
+            .or_else(|| {
+                format
+                    .temporal_kind()
+                    .and_then(|kind| format_iso8601(number, kind))
+            })
+            .unwrap_or_else(|| best(number, 12));
+    };
+    if let Some(missing) = render_missing(value, number) {
+        return fit(missing, width);
+    }
+    let decimals = format.decimals.map(usize::from).unwrap_or(0);
+    let text = match name {
+        "DATE" => sas_date(number).and_then(|date| render_date(date, width)),
+        "MONYY" => sas_date(number).and_then(|date| render_monyy(date, width)),
+        "WEEKDATE" => sas_date(number).and_then(|date| render_weekdate(date, width)),
+        "E8601DA" => sas_date(number).map(|date| date.format("%Y-%m-%d").to_string()),
+        "DATETIME" => sas_datetime_to_naive(number)
+            .filter(|datetime| (0..=9999).contains(&datetime.year()))
+            .and_then(|datetime| render_datetime(datetime, width, decimals)),
+        "E8601DT" => sas_datetime_to_naive(number)
+            .filter(|datetime| (0..=9999).contains(&datetime.year()))
+            .and_then(|datetime| render_e8601dt(datetime, width, decimals)),
+        "TIME" | "TOD" | "E8601TM" | "HHMM" => render_time(name, number, width, decimals),
+        "COMMA" => Some(render_comma(number, width, decimals, "")),
+        "DOLLAR" => Some(render_comma(number, width, decimals, "$")),
+        "PERCENT" => Some(render_percent(number, decimals)),
+        "Z" => Some(render_z(number, width, decimals)),
+        "F" | "" => Some(fixed(number, width, decimals)),
+        _ => match date_order(name) {
+            Some((order, separator)) => sas_date(number)
+                .and_then(|date| render_day_month_year(date, order, separator, width)),
+            None => match format.temporal_kind() {
+                Some(kind) => format_iso8601(number, kind),
+                None => Some(best(number, width)),
+            },
+        },
+    };
+    text.map(|text| fit(text, width))
+        .unwrap_or_else(|| "*".repeat(width))
+}
+
+/// Convert a SAS date, refusing years SAS cannot display in four digits.
+fn sas_date(days: f64) -> Option<NaiveDate> {
+    sas_date_to_naive(days).filter(|date| (0..=9999).contains(&date.year()))
+}
+
+/// Split the name of a `DDMMYY`, `MMDDYY` or `YYMMDD` format into the order of its parts and
+/// its separator, given by an optional suffix: `B` blank, `C` colon, `D` dash, `N` none,
+/// `P` period or `S` slash.
+fn date_order(name: &str) -> Option<(DateOrder, Option<char>)> {
+    let (order, suffix) = if let Some(suffix) = name.strip_prefix("DDMMYY") {
+        (DateOrder::DayMonthYear, suffix)
+    } else if let Some(suffix) = name.strip_prefix("MMDDYY") {
+        (DateOrder::MonthDayYear, suffix)
+    } else {
+        (DateOrder::YearMonthDay, name.strip_prefix("YYMMDD")?)
+    };
+    let separator = match suffix {
+        "" if order == DateOrder::YearMonthDay => Some('-'),
+        "" | "S" => Some('/'),
+        "B" => Some(' '),
+        "C" => Some(':'),
+        "D" => Some('-'),
+        "N" => None,
+        "P" => Some('.'),
+        _ => return None,
+    };
+    Some((order, separator))
+}
+
+/// `DATE`: `31JAN` up to width 6, `31JAN24` up to 8, `31JAN2024` up to 10 and then
+/// `31-JAN-2024`.
+fn render_date(date: NaiveDate, width: usize) -> Option<String> {
+    let month = MONTHS[date.month0() as usize];
+    match width {
+        0..=4 => None,
+        5..=6 => Some(format!("{:02}{}", date.day(), month)),
+        7..=8 => Some(format!(
+            "{:02}{}{:02}",
+            date.day(),
+            month,
+            date.year() % 100
+        )),
+        9..=10 => Some(format!("{:02}{}{}", date.day(), month, date.year())),
+        _ => Some(format!("{:02}-{}-{}", date.day(), month, date.year())),
+    }
+}
+
+/// `DDMMYY`, `MMDDYY` and `YYMMDD`: a four-digit year from width 10 (8 without separator), a
+/// two-digit year from width 6 with separators from 8, and only the first two or one of the
+/// parts below that.
+fn render_day_month_year(
+    date: NaiveDate,
+    order: DateOrder,
+    separator: Option<char>,
+    width: usize,
+) -> Option<String> {
+    let (long_year, two_digit_year, separated_year) = match separator {
+        Some(_) => (10, 6, 8),
+        None => (8, 6, 6),
+    };
+    let year = if width >= long_year {
+        format!("{:04}", date.year())
+    } else {
+        format!("{:02}", date.year() % 100)
+    };
+    let day = format!("{:02}", date.day());
+    let month = format!("{:02}", date.month());
+    let parts = match order {
+        DateOrder::DayMonthYear => [day, month, year],
+        DateOrder::MonthDayYear => [month, day, year],
+        DateOrder::YearMonthDay => [year, month, day],
+    };
+    let (count, separator) = match width {
+        0..=1 => return None,
+        2..=3 => (1, None),
+        4 => (2, None),
+        5 => (2, separator),
+        _ if width >= two_digit_year && width < separated_year => (3, None),
+        _ => (3, separator),
+    };
+    let separator = separator.map(String::from).unwrap_or_default();
+    Some(parts[..count].join(&separator))
+}
+
+/// `MONYY`: `JAN24` up to width 6 and `JAN2024` from 7.
+fn render_monyy(date: NaiveDate, width: usize) -> Option<String> {
+    let month = MONTHS[date.month0() as usize];
+    match width {
+        0..=4 => None,
+        5..=6 => Some(format!("{}{:02}", month, date.year() % 100)),
+        _ => Some(format!("{}{}", month, date.year())),
+    }
+}
+
+/// `WEEKDATE`: `Wed` up to width 8, `Wednesday` up to 14, `Wed, Jan 31, 24` up to 16,
+/// `Wed, Jan 31, 2024` up to 22 and then `Wednesday, January 31, 2024` when it fits.
+fn render_weekdate(date: NaiveDate, width: usize) -> Option<String> {
+    let pattern = match width {
+        0..=2 => return None,
+        3..=8 => "%a",
+        9..=14 => "%A",
+        15..=16 => "%a, %b %-d, %y",
+        17..=22 => "%a, %b %-d, %Y",
+        _ => "%A, %B %-d, %Y",
+    };
+    let text = date.format(pattern).to_string();
+    if text.len() > width && width >= 17 {
+        return Some(date.format("%a, %b %-d, %Y").to_string());
+    }
+    if text.len() > width && width >= 9 {
+        return Some(date.format("%a").to_string());
+    }
+    Some(text)
+}
+
+/// The first `decimals` digits of the fraction of a second, after a period, or nothing if
+/// `decimals` is 0 or does not fit in `spare` characters.
+fn second_fraction(datetime: &NaiveDateTime, decimals: usize, spare: usize) -> String {
+    if decimals == 0 || spare < decimals + 1 {
+        return String::new();
+    }
+    let digits = format!("{:09}", datetime.nanosecond() % 1_000_000_000);
+    let digits = format!("{:0<width$}", digits, width = decimals);
+    format!(".{}", &digits[..decimals])
+}
+
+/// `DATETIME`: the date as `DATE7.` from width 7, followed by the hours from 10, the minutes
+/// from 13 and the seconds from 16, with a four-digit year from 18. Decimals are shown when
+/// they fit.
+fn render_datetime(datetime: NaiveDateTime, width: usize, decimals: usize) -> Option<String> {
+    let date = datetime.date();
+    let day = match width {
+        0..=6 => return None,
+        7..=17 => render_date(date, 7)?,
+        _ => render_date(date, 9)?,
+    };
+    let time = match width {
+        7..=9 => String::new(),
+        10..=12 => format!(":{:02}", datetime.hour()),
+        13..=15 => format!(":{:02}:{:02}", datetime.hour(), datetime.minute()),
+        _ => format!(
+            ":{:02}:{:02}:{:02}",
+            datetime.hour(),
+            datetime.minute(),
+            datetime.second()
+        ),
+    };
+    let spare = width.saturating_sub(day.len() + time.len());
+    let fraction = if width >= 18 {
+        second_fraction(&datetime, decimals, spare)
+    } else {
+        String::new()
+    };
+    Some(format!("{}{}{}", day, time, fraction))
+}
+
+/// `E8601DT`: `2024-01-31T13:45` up to width 18 and `2024-01-31T13:45:00` from 19, with
+/// decimals when they fit.
+fn render_e8601dt(datetime: NaiveDateTime, width: usize, decimals: usize) -> Option<String> {
+    match width {
+        0..=15 => None,
+        16..=18 => Some(datetime.format("%Y-%m-%dT%H:%M").to_string()),
+        _ => Some(format!(
+            "{}{}",
+            datetime.format("%Y-%m-%dT%H:%M:%S"),
+            second_fraction(&datetime, decimals, width - 19)
+        )),
+    }
+}
+
+/// `TIME`, `TOD`, `E8601TM` and `HHMM`, for a number of seconds. `TIME` and `HHMM` show hours
+/// past 24 and do not pad them, while `TOD` shows the time of day and `E8601TM` pads hours to
+/// two digits. Units too small for the width are truncated, except that `HHMM` rounds to the
+/// nearest minute.
+fn render_time(name: &str, seconds: f64, width: usize, decimals: usize) -> Option<String> {
+    if !seconds.is_finite() || seconds.abs() > 1e14 {
+        return None;
+    }
+    let (sign, seconds) = match name {
+        "TOD" => ("", seconds.rem_euclid(86_400.0)),
+        _ if seconds < 0.0 => ("-", -seconds),
+        _ => ("", seconds),
+    };
+    if name == "HHMM" {
+        let minutes = (seconds / 60.0).round() as u64;
+        return match width {
+            0..=1 => None,
+            2..=4 => Some(format!("{}{}", sign, (minutes + 30) / 60)),
+            _ => Some(format!("{}{}:{:02}", sign, minutes / 60, minutes % 60)),
+        };
+    }
+
+    let whole = seconds.trunc() as u64;
+    let (hours, minutes, secs) = (whole / 3600, whole / 60 % 60, whole % 60);
+    let hours = match name {
+        "TIME" => hours.to_string(),
+        _ => format!("{:02}", hours),
+    };
+    let text = match width {
+        0..=1 => return None,
+        2..=4 => hours,
+        5..=7 => format!("{}:{:02}", hours, minutes),
+        _ => format!("{}:{:02}:{:02}", hours, minutes, secs),
+    };
+    let spare = width.saturating_sub(sign.len() + text.len());
+    let fraction = if width >= 8 && decimals > 0 && spare > decimals {
+        let digits = (seconds.fract() * 10f64.powi(decimals as i32)).trunc();
+        format!(".{:0width$}", digits as u64, width = decimals)
+    } else {
+        String::new()
+    };
+    Some(format!("{}{}{}", sign, text, fraction))
+}
+
+/// `COMMA` and `DOLLAR`: thousands separated by commas after `prefix`, eg `-$1,234.50`. When
+/// that does not fit, the separators and prefix are dropped.
+fn render_comma(number: f64, width: usize, decimals: usize, prefix: &str) -> String {
+    let text = format_fixed(number.abs(), decimals);
+    let (integer, fraction) = match text.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (text.as_str(), None),
+    };
+    let mut grouped = String::new();
+    for (index, digit) in integer.chars().enumerate() {
+        if index > 0 && (integer.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    let sign = if number < 0.0 && text.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+        "-"
+    } else {
+        ""
+    };
+    let text = match fraction {
+        Some(fraction) => format!("{}{}{}.{}", sign, prefix, grouped, fraction),
+        None => format!("{}{}{}", sign, prefix, grouped),
+    };
+    if text.len() <= width {
+        text
+    } else {
+        fixed(number, width, decimals)
+    }
+}
+
+/// `PERCENT`: the number multiplied by 100 with a percent sign. Negative numbers are shown in
+/// parentheses, and positive ones are followed by a blank to line up with them.
+fn render_percent(number: f64, decimals: usize) -> String {
+    let text = format_fixed((number * 100.0).abs(), decimals);
+    if number < 0.0 && text.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+        format!("({}%)", text)
+    } else {
+        format!("{}% ", text)
+    }
+}
+
+/// `Z`: the number padded with leading zeros to the width.
+fn render_z(number: f64, width: usize, decimals: usize) -> String {
+    let text = format_fixed(number, decimals);
+    match text.strip_prefix('-') {
+        Some(digits) => format!("-{:0>width$}", digits, width = width.saturating_sub(1)),
+        None => format!("{:0>width$}", text, width = width),
+    }
+}
+
+/// Round half away from zero, as SAS does, unlike the round half to even of `format!`.
+fn round_half_away(number: f64, decimals: usize) -> f64 {
+    let scale = 10f64.powi(decimals as i32);
+    let scaled = number * scale;
+    if scaled.abs() < 9_007_199_254_740_992.0 {
+        scaled.round() / scale
+    } else {
+        number
+    }
+}
+
+/// Format with exactly `decimals` decimals, without the sign of a negative number that
+/// rounds to zero.
+fn format_fixed(number: f64, decimals: usize) -> String {
+    let text = format!("{:.*}", decimals, round_half_away(number, decimals));
+    match text.strip_prefix('-') {
+        Some(digits) if !digits.bytes().any(|b| (b'1'..=b'9').contains(&b)) => digits.to_string(),
+        _ => text,
+    }
+}
+
+/// `w.d` and `F`: the number with `decimals` decimals, with fewer when that does not fit and
+/// as `BEST` when no decimals do.
+fn fixed(number: f64, width: usize, decimals: usize) -> String {
+    (0..=decimals)
+        .rev()
+        .map(|decimals| format_fixed(number, decimals))
+        .find(|text| text.len() <= width)
+        .unwrap_or_else(|| best(number, width))
+}
+
+/// `BEST`: integers as they are, and other numbers with as many significant digits as fit,
+/// in scientific notation such as `1.2345679E15` when that shows more of them.
+fn best(number: f64, width: usize) -> String {
+    if number == 0.0 {
+        return "0".to_string();
+    }
+    if number.trunc() == number && number.abs() < 1e15 {
+        let text = format!("{}", number as i64);
+        if text.len() <= width {
+            return text;
+        }
+    }
+    let fixed = best_fixed(number, width);
+    let scientific = best_scientific(number, width);
+    match (fixed, scientific) {
+        (Some(fixed), Some(scientific)) => {
+            if significant_digits(&fixed) >= significant_digits(&scientific) {
+                fixed
+            } else {
+                scientific
+            }
+        }
+        (Some(text), None) | (None, Some(text)) => text,
+        (None, None) => "*".repeat(width),
+    }
+}
+
+fn best_fixed(number: f64, width: usize) -> Option<String> {
+    (0..=width.min(16))
+        .rev()
+        .map(|decimals| trim_zeros(format_fixed(number, decimals)))
+        .find(|text| text.len() <= width)
+        .filter(|text| significant_digits(text) > 0)
+}
+
+fn best_scientific(number: f64, width: usize) -> Option<String> {
+    (0..=14)
+        .rev()
+        .map(|decimals| {
+            let text = format!("{:.*E}", decimals, number);
+            match text.split_once('E') {
+                Some((mantissa, exponent)) => {
+                    format!("{}E{}", trim_zeros(mantissa.to_string()), exponent)
+                }
+                None => text,
+            }
+        })
+        .find(|text| text.len() <= width)
+}
+
+/// Drop the trailing zeros of a fraction, and its period when nothing is left of it.
+fn trim_zeros(text: String) -> String {
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
+
+/// Count the digits of a number other than leading zeros, ignoring any exponent.
+fn significant_digits(text: &str) -> usize {
+    let mantissa = text.split('E').next().unwrap_or_default();
+    mantissa
+        .chars()
+        .filter(char::is_ascii_digit)
+        .skip_while(|digit| *digit == '0')
+        .count()
+}
+
+/// Right-align `text` in `width` characters, or fill them with asterisks if it does not fit.
+fn fit(text: String, width: usize) -> String {
+    let length = text.chars().count();
+    if length <= width {
+        format!("{}{}", " ".repeat(width - length), text)
+    } else {
+        "*".repeat(width)
+    }
+}
+
+/// Left-align `text` in `width` characters, truncating it if it does not fit.
+fn align_left(text: &str, width: usize) -> String {
+    let text: String = text.chars().take(width).collect();
+    let length = text.chars().count();
+    format!("{}{}", text, " ".repeat(width - length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bcat::SasUserFormat;
+    use crate::types::{ReadStatValueLabel, SasMissing};
+
+    fn number(value: f64) -> ReadStatValue {
+        ReadStatValue::new(ReadStatType::Double, None, ReadStatValueType::Double(value))
+    }
+
+    fn text(value: &str) -> ReadStatValue {
+        ReadStatValue::new(
+            ReadStatType::String,
+            None,
+            ReadStatValueType::String(value.to_string()),
+        )
+    }
+
+    fn render(value: &ReadStatValue, format: &str) -> String {
+        render_value(value, &SasFormat::from_str(format).unwrap(), None)
+    }
+
+    fn width(format: &str) -> usize {
+        let format = SasFormat::from_str(format).unwrap();
+        format
+            .width
+            .map(usize::from)
+            .or_else(|| default_width(&format.name))
+            .unwrap()
+    }
+
+    // 2024-01-31 in days since 1960-01-01, and 2024-01-31T13:45:07.25 in seconds
+    const DATE: f64 = 23406.0;
+    const DATETIME: f64 = 2022327907.25;
+
+    #[test]
+    fn test_render_dates() {
+        let date = number(DATE);
+        let cases = [
+            ("DATE5.", "31JAN"),
+            ("DATE.", "31JAN24"),
+            ("DATE8.", " 31JAN24"),
+            ("DATE9.", "31JAN2024"),
+            ("DATE11.", "31-JAN-2024"),
+            ("DDMMYY.", "31/01/24"),
+            ("DDMMYY10.", "31/01/2024"),
+            ("DDMMYY6.", "310124"),
+            ("DDMMYYD10.", "31-01-2024"),
+            ("DDMMYYN8.", "31012024"),
+            ("MMDDYY10.", "01/31/2024"),
+            ("MMDDYYP10.", "01.31.2024"),
+            ("MMDDYY5.", "01/31"),
+            ("YYMMDD10.", "2024-01-31"),
+            ("YYMMDD8.", "24-01-31"),
+            ("YYMMDDN8.", "20240131"),
+            ("MONYY.", "JAN24"),
+            ("MONYY7.", "JAN2024"),
+            ("WEEKDATE3.", "Wed"),
+            ("WEEKDATE9.", "Wednesday"),
+            ("WEEKDATE15.", "Wed, Jan 31, 24"),
+            ("WEEKDATE17.", "Wed, Jan 31, 2024"),
+            ("WEEKDATE.", "Wednesday, January 31, 2024"),
+            ("E8601DA.", "2024-01-31"),
+        ];
+        for (format, expected) in cases {
+            let expected = fit(expected.to_string(), width(format));
+            assert_eq!(render(&date, format), expected, "{}", format);
+        }
+        assert_eq!(render(&number(-1e9), "DATE9."), "*********");
+        assert_eq!(render(&number(f64::NAN), "DATE9."), "        .");
+    }
+
+    #[test]
+    fn test_render_datetimes_and_times() {
+        let datetime = number(DATETIME);
+        assert_eq!(render(&datetime, "DATETIME."), "31JAN24:13:45:07");
+        assert_eq!(render(&datetime, "DATETIME13."), "31JAN24:13:45");
+        assert_eq!(render(&datetime, "DATETIME20."), "  31JAN2024:13:45:07");
+        assert_eq!(render(&datetime, "DATETIME22.2"), " 31JAN2024:13:45:07.25");
+        assert_eq!(render(&datetime, "E8601DT."), "2024-01-31T13:45:07");
+        assert_eq!(render(&datetime, "E8601DT22.2"), "2024-01-31T13:45:07.25");
+
+        let time = number(3723.5);
+        assert_eq!(render(&time, "TIME."), " 1:02:03");
+        assert_eq!(render(&time, "TIME11.2"), " 1:02:03.50");
+        assert_eq!(render(&time, "TIME5."), " 1:02");
+        assert_eq!(render(&number(-3723.0), "TIME9."), " -1:02:03");
+        assert_eq!(render(&number(100_000.0), "TIME."), "27:46:40");
+        assert_eq!(render(&number(100_000.0), "TOD."), "03:46:40");
+        assert_eq!(render(&time, "E8601TM."), "01:02:03");
+        assert_eq!(render(&number(3690.0), "HHMM."), " 1:02");
+    }
+
+    #[test]
+    fn test_render_numbers() {
+        let cases = [
+            (1.0, "BEST12.", "1"),
+            (12.34567, "BEST.", "12.34567"),
+            (1.0 / 3.0, "BEST8.", "0.333333"),
+            (-1234.5, "BEST6.", "-1235"),
+            (1234567890123.0, "BEST12.", "1.2345679E12"),
+            (1.234e-10, "BEST12.", "1.234E-10"),
+            (0.0, "BEST5.", "0"),
+            (2.5, "8.", "3"),
+            (-2.5, "F8.", "-3"),
+            (1234.567, "8.2", "1234.57"),
+            (1234.567, "F6.2", "1234.6"),
+            (1234567.891, "COMMA12.2", "1,234,567.89"),
+            (1234.5, "COMMA.", "1,235"),
+            (1234567.0, "COMMA5.", "1.2E6"),
+            (-1234.5, "DOLLAR10.2", "-$1,234.50"),
+            (1234.5, "DOLLAR.", "$1,235"),
+            (0.1234, "PERCENT8.1", "12.3% "),
+            (-0.1, "PERCENT.", "(10%)"),
+            (42.0, "Z5.", "00042"),
+            (-4.25, "Z6.1", "-004.3"),
+            (123456.0, "Z3.", "***"),
+        ];
+        for (value, format, expected) in cases {
+            let expected = fit(expected.to_string(), width(format));
+            assert_eq!(render(&number(value), format), expected, "{}", format);
+        }
+    }
+
+    #[test]
+    fn test_render_missing_and_characters() {
+        let tagged = ReadStatValue::missing(SasMissing::Letter('A'));
+        assert_eq!(render(&tagged, "BEST."), "           A");
+        assert_eq!(render(&number(f64::NAN), "COMMA8.2"), "       .");
+        assert_eq!(render(&number(f64::NAN), "WORDDATE."), ".");
+
+        assert_eq!(render(&text("  abc"), "$CHAR6."), "  abc ");
+        assert_eq!(render(&text("abcdef"), "$4."), "abcd");
+        assert_eq!(render(&text("abc"), "$UPCASE."), "ABC");
+        assert_eq!(render(&text("caf\u{e9}"), "$CHAR5."), "caf\u{e9} ");
+    }
+
+    #[test]
+    fn test_render_user_formats() {
+        let label = |key: f64, label: &str| {
+            let mut value_label = ReadStatValueLabel::builder().build().unwrap();
+            value_label.double_key = key;
+            value_label.label = label.to_string();
+            value_label
+        };
+        let catalog = SasCatalog {
+            formats: vec![SasUserFormat::new(
+                "YESNO".to_string(),
+                vec![label(1.0, "Yes"), label(0.0, "No")],
+            )],
+        };
+        let format = SasFormat::from_str("yesno.").unwrap();
+        assert_eq!(render_value(&number(1.0), &format, Some(&catalog)), "Yes");
+        assert_eq!(
+            render_value(&number(2.0), &format, Some(&catalog)),
+            "           2"
+        );
+        assert_eq!(render_value(&number(1.0), &format, None), "           1");
+        let format = SasFormat::from_str("YESNO5.").unwrap();
+        assert_eq!(render_value(&number(0.0), &format, Some(&catalog)), "No   ");
+
+        let variable = ReadStatVariable::builder()
+            .format("DATE9.".to_string())
+            .build()
+            .unwrap();
+        assert_eq!(
+            render_variable_value(&number(DATE), &variable, None),
+            "31JAN2024"
+        );
+    }
+}
@@ -0,0 +1,493 @@
+use crate::sas::cursor::SasCursor;
+use crate::sas::sas7bdat::header::parse_header;
+use crate::sas::sas7bdat::text::{decode_sas_string, trim_padding};
+use crate::types::{
+    ReadStatError, ReadStatValue, ReadStatValueLabel, ReadStatValueType, ReaderLimits, SasEndian,
+    SasError, SasFileMagicNumber, SasHeaderInfo, SasMissing,
+};
+use std::path::Path;
+
+/// Layout of the XLSR records that index the format blocks of a catalog: their size, where
+/// the first one starts on the first page, and the offset of the byte marking a format block
+/// with an `O`.
+const XLSR_SIZE_32: usize = 212;
+const XLSR_OFFSET_32: usize = 856;
+const XLSR_O_OFFSET_32: usize = 50;
+const XLSR_SIZE_64: usize = 232;
+const XLSR_OFFSET_64: usize = 886;
+const XLSR_O_OFFSET_64: usize = 24;
+
+/// Offset of the values of a format block, before any padding or long name.
+const BLOCK_PAYLOAD_OFFSET_32: usize = 106;
+const BLOCK_PAYLOAD_OFFSET_64: usize = 138;
+
+/// A user-defined format, as created by `PROC FORMAT`: a name and the labels it displays for
+/// single values. Names keep the `$` of character formats, eg `$REGION`.
+///
+/// Only the start of each range is read, like readstat does, so a range such as `1-5` labels
+/// the value 1 alone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasUserFormat {
+    pub name: String,
+    pub labels: Vec<ReadStatValueLabel>,
+}
+
+impl SasUserFormat {
+    pub fn new(name: String, labels: Vec<ReadStatValueLabel>) -> Self {
+        Self { name, labels }
+    }
+
+    /// Return true for formats applied to character values.
+    pub fn is_character(&self) -> bool {
+        self.name.starts_with('$')
+    }
+
+    /// Return the label the format displays for `value`, if it has one. Character values are
+    /// compared without their trailing blanks, and missing numerics by their tag.
+    pub fn label(&self, value: &ReadStatValue) -> Option<&str> {
+        let matches = |label: &&ReadStatValueLabel| match &value.value {
+            ReadStatValueType::String(text) => {
+                label.string_key.trim_end() == text.trim_end() && self.is_character()
+            }
+            ReadStatValueType::Double(number) if number.is_nan() => {
+                label.double_key.is_nan()
+                    && Some(label.missing.unwrap_or(SasMissing::System)) == value.missing_kind()
+            }
+            ReadStatValueType::Double(number) => label.double_key == *number,
+            _ => false,
+        };
+        self.labels
+            .iter()
+            .find(matches)
+            .map(|label| label.label.as_str())
+    }
+}
+
+/// The user-defined formats of a sas7bcat format catalog.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SasCatalog {
+    pub formats: Vec<SasUserFormat>,
+}
+
+impl SasCatalog {
+    /// Read the sas7bcat file at the given path.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, SasError> {
+        Self::open_with_limits(path, ReaderLimits::default())
+    }
+
+    /// Read the sas7bcat file at the given path, refusing catalogs that exceed `limits`.
+    pub fn open_with_limits<P: AsRef<Path>>(
+        path: P,
+        limits: ReaderLimits,
+    ) -> Result<Self, SasError> {
+        let bytes = std::fs::read(path).map_err(|err| SasError::io(ReadStatError::Open, err))?;
+        Self::from_bytes_with_limits(&bytes, limits)
+    }
+
+    /// Parse a sas7bcat file held in memory.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SasError> {
+        Self::from_bytes_with_limits(bytes, ReaderLimits::default())
+    }
+
+    /// Parse a sas7bcat file held in memory, refusing catalogs that exceed `limits`. Only
+    /// `max_pages` and `max_catalog_entries` apply to catalogs.
+    ///
+    /// # Original C function
+    /// `readstat_parse_sas7bcat` in readstat's `sas7bcat_read.c`.
+    pub fn from_bytes_with_limits(bytes: &[u8], limits: ReaderLimits) -> Result<Self, SasError> {
+        if bytes.get(0..32) != Some(&SasFileMagicNumber::Sas7bcat.get_array()[..]) {
+            return Err(SasError::new(ReadStatError::Parse).with_offset(0));
+        }
+        let header = parse_header(bytes).map_err(|err| SasError::from(err).with_offset(0))?;
+        limits
+            .check_pages(header.page_count)
+            .map_err(|err| SasError::from(err).with_offset(0))?;
+        let layout = CatalogLayout::new(&header);
+
+        let first_page = layout.page(bytes, 0)?;
+        let mut pointers = Vec::new();
+        if let Some(index) = first_page.get(layout.xlsr_offset..) {
+            layout.read_index(index, &mut pointers);
+        }
+        for page in 1..header.page_count {
+            let data = layout.page(bytes, page)?;
+            if data.get(16..20) == Some(b"XLSR") {
+                layout.read_index(&data[16..], &mut pointers);
+            }
+        }
+        pointers.sort_unstable();
+        pointers.dedup();
+        limits
+            .check_catalog_entries(pointers.len() as u64)
+            .map_err(SasError::from)?;
+
+        let mut formats = Vec::new();
+        for (page, position) in pointers {
+            let block = layout
+                .read_block(bytes, page, position)
+                .map_err(|err| SasError::from(err).with_page(page))?;
+            if let Some(format) = layout
+                .parse_block(&block)
+                .map_err(|err| SasError::from(err).with_page(page))?
+            {
+                formats.push(format);
+            }
+        }
+        Ok(Self { formats })
+    }
+
+    /// Return the format with the given name, compared case-insensitively as SAS does.
+    pub fn format(&self, name: &str) -> Option<&SasUserFormat> {
+        self.formats
+            .iter()
+            .find(|format| format.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// What the layout of a catalog depends on, taken from its header.
+#[derive(Debug)]
+struct CatalogLayout {
+    is_64bit: bool,
+    endianness: SasEndian,
+    pad1: usize,
+    header_size: u64,
+    page_size: u64,
+    page_count: u64,
+    encoding: String,
+    xlsr_size: usize,
+    xlsr_offset: usize,
+    xlsr_o_offset: usize,
+}
+
+impl CatalogLayout {
+    fn new(header: &SasHeaderInfo) -> Self {
+        let (xlsr_size, xlsr_offset, xlsr_o_offset) = if header.is_64bit {
+            (XLSR_SIZE_64, XLSR_OFFSET_64, XLSR_O_OFFSET_64)
+        } else {
+            (XLSR_SIZE_32, XLSR_OFFSET_32, XLSR_O_OFFSET_32)
+        };
+        Self {
+            is_64bit: header.is_64bit,
+            endianness: header.endianness.clone(),
+            pad1: header.pad1 as usize,
+            header_size: header.header_size,
+            page_size: header.page_size,
+            page_count: header.page_count,
+            encoding: header.encoding.clone(),
+            xlsr_size,
+            xlsr_offset,
+            xlsr_o_offset,
+        }
+    }
+
+    /// Return the page at `index`, counted from 0.
+    fn page<'a>(&self, bytes: &'a [u8], index: u64) -> Result<&'a [u8], SasError> {
+        let offset = index
+            .checked_mul(self.page_size)
+            .and_then(|offset| offset.checked_add(self.header_size));
+        offset
+            .and_then(|start| Some((start as usize, start.checked_add(self.page_size)? as usize)))
+            .and_then(|(start, end)| bytes.get(start..end))
+            .ok_or_else(|| {
+                SasError::new(ReadStatError::Read)
+                    .with_page(index)
+                    .with_offset(offset.unwrap_or(u64::MAX))
+            })
+    }
+
+    /// Collect the pages and positions of the format blocks listed by a run of XLSR records,
+    /// which ends at the first record that is neither an XLSR nor blank alignment.
+    ///
+    /// # Original C function
+    /// `sas7bcat_augment_index` in readstat's `sas7bcat_read.c`.
+    fn read_index(&self, index: &[u8], pointers: &mut Vec<(u64, u64)>) {
+        let mut offset = 0;
+        while let Some(xlsr) = index.get(offset..offset + self.xlsr_size) {
+            if &xlsr[..8] == b"        " {
+                offset += 8;
+                continue;
+            }
+            if &xlsr[..4] != b"XLSR" {
+                break;
+            }
+            if xlsr[self.xlsr_o_offset] == b'O' {
+                let mut cursor = SasCursor::new(xlsr, self.endianness.clone());
+                let pointer = if self.is_64bit {
+                    cursor
+                        .seek(8)
+                        .and_then(|_| Ok((cursor.read_u64()?, cursor.read_u16()? as u64)))
+                } else {
+                    cursor
+                        .seek(4)
+                        .and_then(|_| Ok((cursor.read_u32()? as u64, cursor.read_u16()? as u64)))
+                };
+                pointers.extend(pointer.ok());
+            }
+            offset += self.xlsr_size;
+        }
+    }
+
+    /// Gather the bytes of a block, which is stored as a chain of links that may span pages.
+    /// Each link starts with the page and position of the next one and its own length.
+    ///
+    /// # Original C function
+    /// `sas7bcat_read_block` in readstat's `sas7bcat_read.c`.
+    fn read_block(&self, bytes: &[u8], page: u64, position: u64) -> Result<Vec<u8>, ReadStatError> {
+        let link_header_size = if self.is_64bit { 32 } else { 16 };
+        let mut block = Vec::new();
+        let (mut page, mut position) = (page, position);
+        // A chain that loops would otherwise never end, so a block may neither have more links
+        // than fit in the file nor outgrow it
+        let mut links = 0;
+        while page > 0 && position > 0 && page <= self.page_count {
+            links += 1;
+            if links > bytes.len() / link_header_size {
+                return Err(ReadStatError::Parse);
+            }
+            let start = (page - 1)
+                .checked_mul(self.page_size)
+                .and_then(|offset| offset.checked_add(self.header_size + position))
+                .and_then(|start| usize::try_from(start).ok())
+                .ok_or(ReadStatError::Parse)?;
+            let mut cursor = SasCursor::at(bytes, start, self.endianness.clone())
+                .map_err(|_| ReadStatError::Parse)?;
+            let link = cursor.read_bytes(link_header_size)?;
+            let mut link = SasCursor::new(link, self.endianness.clone());
+            page = link.read_u32()? as u64;
+            if self.is_64bit {
+                link.skip(4)?;
+            }
+            position = link.read_u16()? as u64;
+            let length = link.read_u16()? as usize;
+            block.extend_from_slice(cursor.read_bytes(length)?);
+            if block.len() + link_header_size > bytes.len() {
+                return Err(ReadStatError::Parse);
+            }
+        }
+        Ok(block)
+    }
+
+    /// Parse a format block into the format it defines, or `None` for blocks too short to
+    /// hold one or without any labels.
+    ///
+    /// # Original C function
+    /// `sas7bcat_parse_block` in readstat's `sas7bcat_read.c`.
+    fn parse_block(&self, block: &[u8]) -> Result<Option<SasUserFormat>, ReadStatError> {
+        let mut payload_offset = BLOCK_PAYLOAD_OFFSET_32;
+        if block.len() < payload_offset {
+            return Ok(None);
+        }
+
+        let mut cursor = SasCursor::at(block, 2, self.endianness.clone())?;
+        let flags = cursor.read_u16()?;
+        let mut pad = if flags & 0x08 != 0 { 4 } else { 0 };
+        let (capacity, used) = if self.is_64bit {
+            if block.len() < BLOCK_PAYLOAD_OFFSET_64 {
+                return Ok(None);
+            }
+            payload_offset = BLOCK_PAYLOAD_OFFSET_64;
+            cursor.seek(42 + pad)?;
+            (cursor.read_u64()?, cursor.read_u64()?)
+        } else {
+            cursor.seek(38 + pad)?;
+            (cursor.read_u32()? as u64, cursor.read_u32()? as u64)
+        };
+
+        let mut name = decode_sas_string(trim_padding(&block[8..16]), &self.encoding);
+        if pad > 0 {
+            pad += 16;
+        }
+        let long_name = if self.is_64bit { 0x20 } else { 0x80 };
+        if flags & long_name != 0 {
+            let start = payload_offset + pad;
+            match block.get(start..start + 32) {
+                Some(long_name) => {
+                    name = decode_sas_string(trim_padding(long_name), &self.encoding)
+                }
+                None => return Ok(None),
+            }
+            pad += 32;
+        }
+        match block.get(payload_offset + pad..) {
+            Some(values) if used > 0 => self
+                .parse_value_labels(values, used, capacity, &name)
+                .map(|labels| Some(SasUserFormat::new(name, labels))),
+            _ => Ok(None),
+        }
+    }
+
+    /// Parse the values of a format, which are followed by their labels. Values are stored in
+    /// an order of their own, each naming the position of its label.
+    ///
+    /// # Original C function
+    /// `sas7bcat_parse_value_labels` in readstat's `sas7bcat_read.c`.
+    fn parse_value_labels(
+        &self,
+        data: &[u8],
+        used: u64,
+        capacity: u64,
+        name: &str,
+    ) -> Result<Vec<ReadStatValueLabel>, ReadStatError> {
+        // Every value takes at least 6 bytes, which bounds what a hostile count can allocate
+        if used > capacity || capacity.saturating_mul(6) > data.len() as u64 {
+            return Err(ReadStatError::Parse);
+        }
+        let is_string = name.starts_with('$');
+        let mut cursor = SasCursor::new(data, self.endianness.clone());
+
+        let mut value_offsets = vec![None; used as usize];
+        let mut offset = 0;
+        for index in 0..capacity {
+            cursor.seek(offset + 2)?;
+            let entry_length = 6 + cursor.read_u16()? as usize;
+            if index < used {
+                cursor.seek(offset + 10 + self.pad1)?;
+                let label_index = cursor.read_u32()? as usize;
+                *value_offsets
+                    .get_mut(label_index)
+                    .ok_or(ReadStatError::Parse)? = Some(offset);
+            }
+            offset += entry_length;
+        }
+
+        let mut labels = Vec::with_capacity(value_offsets.len());
+        let mut label_offset = offset;
+        for value_offset in value_offsets {
+            let entry = value_offset.ok_or(ReadStatError::Parse)?;
+            let mut label = ReadStatValueLabel::builder()
+                .build()
+                .map_err(|_| ReadStatError::Parse)?;
+            cursor.seek(entry + 2)?;
+            let entry_length = 6 + cursor.read_u16()? as usize;
+            if entry_length < 30 {
+                return Err(ReadStatError::Parse);
+            }
+            if is_string {
+                cursor.seek(entry + entry_length - 16)?;
+                let key = decode_sas_string(trim_padding(cursor.read_bytes(16)?), &self.encoding);
+                label.string_key_length = key.len() as u32;
+                label.string_key = key;
+            } else {
+                // Numeric values are stored negated and big-endian, whatever the file's byte
+                // order; missing values are 0x0000TTFFFFFFFFFF with the tag in TT
+                cursor.seek(entry + 22)?;
+                let bits = u64::from_be_bytes(
+                    cursor
+                        .read_bytes(8)?
+                        .try_into()
+                        .map_err(|_| ReadStatError::Parse)?,
+                );
+                if bits | 0xFF00_0000_0000 == 0xFFFF_FFFF_FFFF {
+                    label.double_key = f64::NAN;
+                    label.missing = Some(SasMissing::from_tag((bits >> 40) as u8));
+                } else {
+                    label.double_key = -f64::from_bits(bits);
+                }
+            }
+
+            cursor.seek(label_offset + 8)?;
+            let length = cursor.read_u16()? as usize;
+            label.label = decode_sas_string(cursor.read_bytes(length)?, &self.encoding);
+            label.label_length = length as u32;
+            label_offset += 8 + 2 + length + 1;
+            labels.push(label);
+        }
+        Ok(labels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bcat::test_fixtures::{SasCatalogFixture, SasCatalogFixtureFormat};
+    use crate::types::ReadStatType;
+
+    fn value(value: ReadStatValueType, missing: Option<SasMissing>) -> ReadStatValue {
+        let type_ = match value {
+            ReadStatValueType::String(_) => ReadStatType::String,
+            _ => ReadStatType::Double,
+        };
+        ReadStatValue::new(type_, missing, value)
+    }
+
+    fn fixture() -> SasCatalogFixture {
+        SasCatalogFixture::new(vec![
+            SasCatalogFixtureFormat::numeric(
+                "YESNO",
+                vec![
+                    (Some(1.0), "Yes"),
+                    (Some(0.0), "No"),
+                    (Some(-2.5), "Negative"),
+                ],
+            ),
+            SasCatalogFixtureFormat::character(
+                "$REGION",
+                vec![("N", "North"), ("S", "South \u{e9}")],
+            ),
+            SasCatalogFixtureFormat::numeric("REASON", vec![(None, "Not asked")]),
+            SasCatalogFixtureFormat::numeric("EMPTY", vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_parse_catalog() {
+        for is_64bit in [false, true] {
+            for endianness in [SasEndian::Little, SasEndian::Big] {
+                let bytes = fixture()
+                    .is_64bit(is_64bit)
+                    .endianness(endianness.clone())
+                    .to_bytes();
+                let catalog = SasCatalog::from_bytes(&bytes).unwrap();
+
+                let names: Vec<&str> = catalog.formats.iter().map(|f| f.name.as_str()).collect();
+                assert_eq!(names, ["YESNO", "$REGION", "REASON"]);
+
+                let yesno = catalog.format("yesno").unwrap();
+                let number = |n: f64| value(ReadStatValueType::Double(n), None);
+                assert_eq!(yesno.label(&number(1.0)), Some("Yes"));
+                assert_eq!(yesno.label(&number(0.0)), Some("No"));
+                assert_eq!(yesno.label(&number(-2.5)), Some("Negative"));
+                assert_eq!(yesno.label(&number(2.0)), None);
+
+                let region = catalog.format("$REGION").unwrap();
+                assert!(region.is_character());
+                let text = |s: &str| value(ReadStatValueType::String(s.to_string()), None);
+                assert_eq!(region.label(&text("S")), Some("South \u{e9}"));
+                assert_eq!(region.label(&text("E")), None);
+
+                let reason = catalog.format("REASON").unwrap();
+                let tagged = value(
+                    ReadStatValueType::Double(f64::NAN),
+                    Some(SasMissing::Letter('A')),
+                );
+                assert_eq!(reason.label(&tagged), Some("Not asked"));
+                assert_eq!(reason.label(&number(f64::NAN)), None);
+            }
+        }
+    }
+
+    #[test]
+    fn test_catalog_limits() {
+        let bytes = fixture().to_bytes();
+        let limits = ReaderLimits::builder()
+            .max_catalog_entries(Some(3))
+            .build()
+            .unwrap();
+        let err = SasCatalog::from_bytes_with_limits(&bytes, limits).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::TooManyCatalogEntries);
+    }
+
+    #[test]
+    fn test_reject_bad_catalogs() {
+        let bytes = fixture().to_bytes();
+        let err = SasCatalog::from_bytes(&bytes[..2000]).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::Read);
+
+        let mut dataset = bytes.clone();
+        dataset[0..32].copy_from_slice(&SasFileMagicNumber::Sas7bdat.get_array());
+        assert_eq!(
+            SasCatalog::from_bytes(&dataset).unwrap_err().kind,
+            ReadStatError::Parse
+        );
+    }
+}
@@ -0,0 +1,6 @@
+pub mod catalog;
+
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+
+pub use catalog::{SasCatalog, SasUserFormat};
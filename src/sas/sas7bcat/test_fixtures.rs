@@ -0,0 +1,189 @@
+//! Synthetic sas7bcat files for tests. The first page holds the index of format blocks and
+//! each following page holds one block, split into two links to exercise block chains.
+
+use crate::sas::sas7bdat::test_fixtures::SasFixture;
+use crate::types::{SasEndian, SasFileMagicNumber};
+
+const PAGE_SIZE: usize = 4096;
+
+/// A format of a fixture catalog. Numeric keys of `None` stand for the missing value `.A`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SasCatalogFixtureFormat {
+    Numeric(String, Vec<(Option<f64>, String)>),
+    Character(String, Vec<(String, String)>),
+}
+
+impl SasCatalogFixtureFormat {
+    pub fn numeric(name: &str, labels: Vec<(Option<f64>, &str)>) -> Self {
+        Self::Numeric(
+            name.to_string(),
+            labels
+                .into_iter()
+                .map(|(key, label)| (key, label.to_string()))
+                .collect(),
+        )
+    }
+
+    pub fn character(name: &str, labels: Vec<(&str, &str)>) -> Self {
+        Self::Character(
+            name.to_string(),
+            labels
+                .into_iter()
+                .map(|(key, label)| (key.to_string(), label.to_string()))
+                .collect(),
+        )
+    }
+}
+
+/// A complete synthetic sas7bcat file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasCatalogFixture {
+    pub is_64bit: bool,
+    pub endianness: SasEndian,
+    pub formats: Vec<SasCatalogFixtureFormat>,
+}
+
+impl SasCatalogFixture {
+    pub fn new(formats: Vec<SasCatalogFixtureFormat>) -> Self {
+        Self {
+            is_64bit: false,
+            endianness: SasEndian::Little,
+            formats,
+        }
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    pub fn is_64bit(mut self, is_64bit: bool) -> Self {
+        self.is_64bit = is_64bit;
+        self
+    }
+
+    pub fn endianness(mut self, endianness: SasEndian) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    fn put(&self, data: &mut [u8], offset: usize, value: u64, size: usize) {
+        let bytes = match self.endianness {
+            SasEndian::Little => value.to_le_bytes()[..size].to_vec(),
+            SasEndian::Big => value.to_be_bytes()[8 - size..].to_vec(),
+        };
+        data[offset..offset + size].copy_from_slice(&bytes);
+    }
+
+    /// Serialize the fixture into the bytes of a sas7bcat file.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let page_count = self.formats.len() + 1;
+        let mut file = SasFixture::builder()
+            .is_64bit(self.is_64bit)
+            .endianness(self.endianness.clone())
+            .build()
+            .header(page_count);
+        file[0..32].copy_from_slice(&SasFileMagicNumber::Sas7bcat.get_array());
+        file[156..164].copy_from_slice(b"CATALOG ");
+
+        file.extend(self.index_page());
+        for (index, format) in self.formats.iter().enumerate() {
+            file.extend(self.block_page(index as u64 + 2, &self.block(format)));
+        }
+        file
+    }
+
+    /// The first page, listing one XLSR record per format. Format `i` is on page `i + 2`.
+    fn index_page(&self) -> Vec<u8> {
+        let mut page = vec![0u8; PAGE_SIZE];
+        let (size, mut offset) = if self.is_64bit {
+            (232, 886)
+        } else {
+            (212, 856)
+        };
+        for index in 0..self.formats.len() {
+            let record = &mut page[offset..offset + size];
+            record[0..4].copy_from_slice(b"XLSR");
+            if self.is_64bit {
+                self.put(record, 8, index as u64 + 2, 8);
+                self.put(record, 16, 16, 2);
+                record[24] = b'O';
+            } else {
+                self.put(record, 4, index as u64 + 2, 4);
+                self.put(record, 8, 16, 2);
+                record[50] = b'O';
+            }
+            offset += size;
+        }
+        page
+    }
+
+    /// Page `number` holding `block` in two links, at positions 16 and 2048.
+    fn block_page(&self, number: u64, block: &[u8]) -> Vec<u8> {
+        let mut page = vec![0u8; PAGE_SIZE];
+        let (first, second) = block.split_at(block.len() / 2);
+        let link_header_size = if self.is_64bit { 32 } else { 16 };
+        let (page_offset, length_offset) = if self.is_64bit { (8, 10) } else { (4, 6) };
+        for (position, next, link) in [(16, 2048, first), (2048, 0, second)] {
+            // The page of the next link is left out after the last one
+            if next > 0 {
+                self.put(&mut page, position, number, 4);
+            }
+            self.put(&mut page, position + page_offset, next as u64, 2);
+            self.put(&mut page, position + length_offset, link.len() as u64, 2);
+            let start = position + link_header_size;
+            page[start..start + link.len()].copy_from_slice(link);
+        }
+        page
+    }
+
+    /// The bytes of the block defining `format`: a header, the values and then the labels.
+    fn block(&self, format: &SasCatalogFixtureFormat) -> Vec<u8> {
+        let (name, count) = match format {
+            SasCatalogFixtureFormat::Numeric(name, labels) => (name, labels.len()),
+            SasCatalogFixtureFormat::Character(name, labels) => (name, labels.len()),
+        };
+        let payload_offset = if self.is_64bit { 138 } else { 106 };
+        let mut block = vec![0u8; payload_offset];
+        block[8..16].fill(b' ');
+        block[8..8 + name.len()].copy_from_slice(name.as_bytes());
+        if self.is_64bit {
+            self.put(&mut block, 42, count as u64, 8);
+            self.put(&mut block, 50, count as u64, 8);
+        } else {
+            self.put(&mut block, 38, count as u64, 4);
+            self.put(&mut block, 42, count as u64, 4);
+        }
+
+        // Values are stored in reverse, so that their label positions are not in order
+        let pad1 = if self.is_64bit { 4 } else { 0 };
+        let mut labels = Vec::new();
+        for index in (0..count).rev() {
+            let mut entry = vec![0u8; 34];
+            self.put(&mut entry, 2, 28, 2);
+            self.put(&mut entry, 10 + pad1, index as u64, 4);
+            let label = match format {
+                SasCatalogFixtureFormat::Numeric(_, labels) => {
+                    let bits = match labels[index].0 {
+                        Some(key) => (-key).to_bits(),
+                        None => 0x0000_02FF_FFFF_FFFF,
+                    };
+                    entry[22..30].copy_from_slice(&bits.to_be_bytes());
+                    &labels[index].1
+                }
+                SasCatalogFixtureFormat::Character(_, labels) => {
+                    entry[18..34].fill(b' ');
+                    let key = labels[index].0.as_bytes();
+                    entry[18..18 + key.len()].copy_from_slice(key);
+                    &labels[index].1
+                }
+            };
+            block.extend(entry);
+            labels.push(label);
+        }
+        for label in labels.into_iter().rev() {
+            let mut record = vec![0u8; 10];
+            self.put(&mut record, 8, label.len() as u64, 2);
+            record.extend(label.as_bytes());
+            record.push(0);
+            block.extend(record);
+        }
+        block
+    }
+}
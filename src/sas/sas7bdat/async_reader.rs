@@ -0,0 +1,413 @@
+use crate::sas::sas7bdat::context::Sas7bdatContext;
+use crate::sas::sas7bdat::header::parse_header;
+use crate::sas::sas7bdat::page::page_rows;
+use crate::sas::sas7bdat::reader::{
+    at_page, build_metadata, parse_metadata_page, progress_at, resolve_columns, variable_index,
+    MIN_HEADER_SIZE,
+};
+use crate::sas::sas7bdat::row::decode_row;
+use crate::sas::sas7bdat::subheader::Sas7bdatMetadataParser;
+use crate::types::{
+    ReadStatError, ReadStatHandler, ReadStatMetadata, ReadStatProgress, ReadStatProgressHandler,
+    ReadStatValue, ReadStatVariable, ReaderLimits, SasError, SasHeaderInfo, SasReadOptions,
+};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::io::SeekFrom;
+use std::path::Path;
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt};
+
+/// Reader for sas7bdat files over a tokio `AsyncRead + AsyncSeek` source, for services that
+/// must not block their executor on file reads. It parses the file exactly like
+/// `Sas7bdatReader`; only the reads of pages are asynchronous.
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use rust_sas::sas::sas7bdat::AsyncSas7bdatReader;
+/// use rust_sas::types::SasReadOptions;
+///
+/// # async fn ingest() {
+/// let mut reader = AsyncSas7bdatReader::open("claims.sas7bdat").await.unwrap();
+/// let mut rows = reader.rows(&SasReadOptions::default()).unwrap();
+/// while let Some(row) = rows.next().await {
+///     println!("{:?}", row.unwrap());
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncSas7bdatReader<R> {
+    io: R,
+    header: SasHeaderInfo,
+    context: Sas7bdatContext,
+    metadata: ReadStatMetadata,
+    variables: Vec<ReadStatVariable>,
+    file_label: String,
+    progress: Option<ReadStatProgressHandler>,
+}
+
+impl AsyncSas7bdatReader<File> {
+    /// Open the sas7bdat file at the given path and parse its header and metadata.
+    pub async fn open<P: AsRef<Path>>(path: P) -> Result<Self, SasError> {
+        Self::open_with_limits(path, ReaderLimits::default()).await
+    }
+
+    /// Open the sas7bdat file at the given path, refusing files that exceed `limits`.
+    pub async fn open_with_limits<P: AsRef<Path>>(
+        path: P,
+        limits: ReaderLimits,
+    ) -> Result<Self, SasError> {
+        let file = File::open(path)
+            .await
+            .map_err(|err| SasError::io(ReadStatError::Open, err))?;
+        Self::from_reader_with_limits(file, limits).await
+    }
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> AsyncSas7bdatReader<R> {
+    /// Parse the header and metadata of a sas7bdat file read from the given source.
+    pub async fn from_reader(io: R) -> Result<Self, SasError> {
+        Self::from_reader_with_limits(io, ReaderLimits::default()).await
+    }
+
+    /// Parse the header and metadata of a sas7bdat file read from the given source, refusing
+    /// files that exceed `limits`.
+    pub async fn from_reader_with_limits(
+        mut io: R,
+        limits: ReaderLimits,
+    ) -> Result<Self, SasError> {
+        let header_bytes = read_at(&mut io, 0, MIN_HEADER_SIZE).await?;
+        let header =
+            parse_header(&header_bytes).map_err(|err| SasError::from(err).with_offset(0))?;
+        limits
+            .check_pages(header.page_count)
+            .map_err(|err| SasError::from(err).with_offset(0))?;
+
+        let mut context = Sas7bdatContext::from_header(&header);
+        context.limits = limits;
+        let mut parser = Sas7bdatMetadataParser::new();
+        for index in 0..header.page_count {
+            let page = read_at(&mut io, context.page_offset(index), context.page_size)
+                .await
+                .map_err(|err| err.with_page(index))?;
+            if parse_metadata_page(&page, index, &mut parser, &mut context)? {
+                break;
+            }
+        }
+
+        let (variables, file_label) = parser.finish(&mut context)?;
+        let metadata = build_metadata(&header, &context, variables.len());
+
+        Ok(Self {
+            io,
+            header,
+            context,
+            metadata,
+            variables,
+            file_label,
+            progress: None,
+        })
+    }
+
+    pub fn header(&self) -> &SasHeaderInfo {
+        &self.header
+    }
+
+    pub fn context(&self) -> &Sas7bdatContext {
+        &self.context
+    }
+
+    pub fn metadata(&self) -> &ReadStatMetadata {
+        &self.metadata
+    }
+
+    pub fn variables(&self) -> &[ReadStatVariable] {
+        &self.variables
+    }
+
+    /// The label of the dataset, as given by the `LABEL=` data set option in SAS.
+    pub fn file_label(&self) -> &str {
+        &self.file_label
+    }
+
+    /// Return the index of the variable with the given name, compared case-insensitively as
+    /// SAS does.
+    pub fn variable_index(&self, name: &str) -> Option<usize> {
+        variable_index(&self.variables, name)
+    }
+
+    /// Resolve a selection of variable names to indexes into `variables`. `None` selects every
+    /// variable in file order.
+    pub fn resolve_columns(&self, columns: &Option<Vec<String>>) -> Result<Vec<usize>, SasError> {
+        resolve_columns(&self.variables, columns)
+    }
+
+    /// Register a callback reporting the progress of row streams every `granularity` pages and
+    /// once the last row has been read. The callback can return `ReadStatHandler::Abort` to end
+    /// the stream with `ReadStatError::UserAbort`.
+    pub fn set_progress_handler<F>(&mut self, granularity: u64, callback: F)
+    where
+        F: FnMut(&ReadStatProgress) -> ReadStatHandler + Send + 'static,
+    {
+        self.progress = Some(ReadStatProgressHandler::new(
+            granularity,
+            Box::new(callback),
+        ));
+    }
+
+    /// Read the raw bytes of the page with the given index.
+    pub async fn read_page(&mut self, index: u64) -> Result<Vec<u8>, SasError> {
+        read_at(
+            &mut self.io,
+            self.context.page_offset(index),
+            self.context.page_size,
+        )
+        .await
+        .map_err(|err| err.with_page(index))
+    }
+
+    /// Stream the rows selected by `options` in batches, one batch per page that holds any of
+    /// them. Each row holds the values of the selected variables, in the order they were
+    /// selected. The stream ends after the first error.
+    pub fn batches(
+        &mut self,
+        options: &SasReadOptions,
+    ) -> Result<impl Stream<Item = Result<Vec<Vec<ReadStatValue>>, SasError>> + Unpin + '_, SasError>
+    {
+        let state = BatchState {
+            selection: self.resolve_columns(&options.columns)?,
+            reader: self,
+            next_page: 0,
+            rows_seen: 0,
+            rows_returned: 0,
+            row_offset: options.row_offset,
+            row_limit: options.row_limit,
+            failed: false,
+        };
+        Ok(Box::pin(stream::unfold(state, |mut state| async move {
+            let batch = state.next_batch().await?;
+            state.failed = batch.is_err();
+            Some((batch, state))
+        })))
+    }
+
+    /// Stream the rows selected by `options`, one at a time. Each row holds the values of the
+    /// selected variables, in the order they were selected.
+    pub fn rows(
+        &mut self,
+        options: &SasReadOptions,
+    ) -> Result<impl Stream<Item = Result<Vec<ReadStatValue>, SasError>> + Unpin + '_, SasError>
+    {
+        Ok(self.batches(options)?.flat_map(|batch| {
+            let rows: Vec<_> = match batch {
+                Ok(rows) => rows.into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            };
+            stream::iter(rows)
+        }))
+    }
+
+    /// Read every row selected by `options` into memory.
+    pub async fn read_rows(
+        &mut self,
+        options: &SasReadOptions,
+    ) -> Result<Vec<Vec<ReadStatValue>>, SasError> {
+        let mut rows = Vec::new();
+        let mut batches = self.batches(options)?;
+        while let Some(batch) = batches.next().await {
+            rows.extend(batch?);
+        }
+        Ok(rows)
+    }
+}
+
+/// Progress of a batch stream through the pages of the file.
+struct BatchState<'a, R> {
+    reader: &'a mut AsyncSas7bdatReader<R>,
+    selection: Vec<usize>,
+    next_page: u64,
+    rows_seen: u64,
+    rows_returned: u64,
+    row_offset: u64,
+    row_limit: Option<u64>,
+    failed: bool,
+}
+
+impl<R: AsyncRead + AsyncSeek + Unpin> BatchState<'_, R> {
+    /// Decode pages until one holds selected rows, skipping rows before the offset.
+    async fn next_batch(&mut self) -> Option<Result<Vec<Vec<ReadStatValue>>, SasError>> {
+        let ctx = &self.reader.context;
+        loop {
+            let remaining = match self.row_limit {
+                Some(limit) => limit.saturating_sub(self.rows_returned),
+                None => u64::MAX,
+            };
+            if self.failed
+                || remaining == 0
+                || self.next_page >= ctx.page_count
+                || self.rows_seen >= ctx.row_count
+            {
+                return None;
+            }
+
+            let index = self.next_page;
+            let page =
+                match read_at(&mut self.reader.io, ctx.page_offset(index), ctx.page_size).await {
+                    Ok(page) => page,
+                    Err(err) => return Some(Err(err.with_page(index))),
+                };
+            self.next_page += 1;
+            let rows = match page_rows(&page, ctx) {
+                Ok(rows) => rows,
+                Err(err) => return Some(Err(at_page(err, ctx, index, self.rows_seen))),
+            };
+
+            let mut batch = Vec::new();
+            for row in rows {
+                if self.rows_seen >= ctx.row_count || batch.len() as u64 >= remaining {
+                    break;
+                }
+                if self.rows_seen >= self.row_offset {
+                    batch.push(decode_row(
+                        &row,
+                        ctx,
+                        &self.reader.variables,
+                        &self.selection,
+                    ));
+                }
+                self.rows_seen += 1;
+            }
+            if let Some(handler) = &mut self.reader.progress {
+                let progress = progress_at(ctx, self.next_page, self.rows_seen);
+                if let Err(err) = handler.report(&progress) {
+                    return Some(Err(err.into()));
+                }
+            }
+            if !batch.is_empty() {
+                self.rows_returned += batch.len() as u64;
+                return Some(Ok(batch));
+            }
+        }
+    }
+}
+
+async fn read_at<R: AsyncRead + AsyncSeek + Unpin>(
+    io: &mut R,
+    offset: u64,
+    length: usize,
+) -> Result<Vec<u8>, SasError> {
+    io.seek(SeekFrom::Start(offset))
+        .await
+        .map_err(|err| SasError::io(ReadStatError::Seek, err).with_offset(offset))?;
+    let mut buffer = vec![0; length];
+    io.read_exact(&mut buffer)
+        .await
+        .map_err(|err| SasError::io(ReadStatError::Read, err).with_offset(offset))?;
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::sas::sas7bdat::Sas7bdatReader;
+    use crate::types::{ReadStatCompression, ReadStatValueType};
+    use std::io::Cursor;
+
+    fn fixture() -> SasFixture {
+        SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 8),
+            ])
+            .rows(
+                (0..300)
+                    .map(|i| {
+                        vec![
+                            ReadStatValueType::Double(i as f64),
+                            ReadStatValueType::String(format!("name{}", i)),
+                        ]
+                    })
+                    .collect(),
+            )
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_async_rows_match_sync_reader() {
+        for compression in [ReadStatCompression::None, ReadStatCompression::Binary] {
+            let fixture = fixture().with_compression(compression);
+            let mut sync_reader = Sas7bdatReader::from_bytes(fixture.to_bytes()).unwrap();
+            let mut reader = AsyncSas7bdatReader::from_reader(Cursor::new(fixture.to_bytes()))
+                .await
+                .unwrap();
+            assert_eq!(reader.metadata(), sync_reader.metadata());
+
+            let options = SasReadOptions::default();
+            let rows: Vec<_> = reader.rows(&options).unwrap().collect().await;
+            let rows: Vec<_> = rows.into_iter().map(Result::unwrap).collect();
+            assert_eq!(rows.len(), 300);
+            assert_eq!(rows, sync_reader.read_rows(&options).unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_batches_with_offset_and_limit() {
+        let path = fixture().write_temp("async_batches");
+        let mut reader = AsyncSas7bdatReader::open(&path).await.unwrap();
+        let options = SasReadOptions::new(Some(vec!["NAME".to_string()]), 100, Some(150));
+
+        let batches: Vec<_> = reader.batches(&options).unwrap().collect().await;
+        let rows: Vec<_> = batches.into_iter().flat_map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 150);
+        assert_eq!(
+            rows[0][0].value,
+            ReadStatValueType::String("name100".to_string())
+        );
+        assert_eq!(
+            rows[149][0].value,
+            ReadStatValueType::String("name249".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_progress_abort() {
+        let mut reader = AsyncSas7bdatReader::from_reader(Cursor::new(fixture().to_bytes()))
+            .await
+            .unwrap();
+        reader.set_progress_handler(1, |_| ReadStatHandler::Abort);
+        let batches: Vec<_> = reader
+            .batches(&SasReadOptions::default())
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(batches.len(), 1);
+        assert_eq!(
+            batches[0].as_ref().map_err(|err| &err.kind),
+            Err(&ReadStatError::UserAbort)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_async_truncated_file() {
+        let mut bytes = fixture().to_bytes();
+        bytes.truncate(bytes.len() - 100);
+        let mut reader = AsyncSas7bdatReader::from_reader(Cursor::new(bytes))
+            .await
+            .unwrap();
+        assert_eq!(
+            reader
+                .read_rows(&SasReadOptions::default())
+                .await
+                .err()
+                .map(|err| err.kind),
+            Some(ReadStatError::Read)
+        );
+        assert_eq!(
+            AsyncSas7bdatReader::open("/nonexistent/file.sas7bdat")
+                .await
+                .err()
+                .map(|err| err.kind),
+            Some(ReadStatError::Open)
+        );
+    }
+}
@@ -0,0 +1,230 @@
+use crate::sas::sas7bdat::page::page_rows;
+use crate::sas::sas7bdat::reader::{at_page, Sas7bdatReader};
+use crate::sas::sas7bdat::row::SasRowRef;
+use crate::types::{SasError, SasReadOptions};
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Where a row of the current page is kept: rows stored verbatim are found again by their
+/// position in the page buffer, while decompressed rows have a buffer of their own.
+#[derive(Debug)]
+enum RowSlot {
+    Page(Range<usize>),
+    Decompressed(Vec<u8>),
+}
+
+impl Sas7bdatReader {
+    /// Read the rows selected by `options` without decoding them into owned values. Each row
+    /// borrows from a page buffer that is reused from page to page, so it must be dropped
+    /// before the next one is asked for:
+    ///
+    /// ```no_run
+    /// use rust_sas::sas::sas7bdat::Sas7bdatReader;
+    /// use rust_sas::types::SasReadOptions;
+    ///
+    /// let mut reader = Sas7bdatReader::open("claims.sas7bdat").unwrap();
+    /// let mut rows = reader.borrowed_rows(&SasReadOptions::default()).unwrap();
+    /// while let Some(row) = rows.next() {
+    ///     for value in row.unwrap().values() {
+    ///         println!("{:?} {:?}", value.as_str(), value.as_f64());
+    ///     }
+    /// }
+    /// ```
+    pub fn borrowed_rows(
+        &mut self,
+        options: &SasReadOptions,
+    ) -> Result<Sas7bdatBorrowedRows<'_>, SasError> {
+        let selection = self.resolve_columns(&options.columns)?;
+        Ok(Sas7bdatBorrowedRows {
+            reader: self,
+            selection,
+            page: Vec::new(),
+            slots: Vec::new(),
+            next_slot: 0,
+            next_page: 0,
+            rows_seen: 0,
+            rows_returned: 0,
+            row_offset: options.row_offset,
+            row_limit: options.row_limit,
+        })
+    }
+}
+
+/// Rows of a sas7bdat file borrowed from the page they are stored in, created by
+/// `Sas7bdatReader::borrowed_rows`. Since each row borrows from the cursor, this is not an
+/// `Iterator`; call `next` until it returns `None`.
+///
+/// Each page is copied once into a buffer owned by the cursor, whatever the source, including
+/// memory-mapped files; rows are then read from that buffer without further copies, except
+/// compressed rows, which are decompressed into a buffer of their own.
+#[derive(Debug)]
+pub struct Sas7bdatBorrowedRows<'a> {
+    reader: &'a mut Sas7bdatReader,
+    selection: Vec<usize>,
+    page: Vec<u8>,
+    slots: Vec<RowSlot>,
+    next_slot: usize,
+    next_page: u64,
+    rows_seen: u64,
+    rows_returned: u64,
+    row_offset: u64,
+    row_limit: Option<u64>,
+}
+
+impl Sas7bdatBorrowedRows<'_> {
+    /// Return the next row, or `None` once every selected row has been returned. After an
+    /// error, no more rows are returned.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Option<Result<SasRowRef<'_>, SasError>> {
+        loop {
+            if self
+                .row_limit
+                .is_some_and(|limit| self.rows_returned >= limit)
+            {
+                return None;
+            }
+            if self.next_slot < self.slots.len() {
+                let row = match &self.slots[self.next_slot] {
+                    RowSlot::Page(range) => &self.page[range.clone()],
+                    RowSlot::Decompressed(row) => &row[..],
+                };
+                self.next_slot += 1;
+                self.rows_returned += 1;
+                return Some(Ok(SasRowRef::new(
+                    row,
+                    self.reader.context(),
+                    self.reader.variables(),
+                    &self.selection,
+                )));
+            }
+            let ctx = self.reader.context();
+            if self.next_page >= ctx.page_count || self.rows_seen >= ctx.row_count {
+                return None;
+            }
+            if let Err(err) = self.fill_page() {
+                self.next_page = self.reader.context().page_count;
+                self.slots.clear();
+                return Some(Err(err));
+            }
+        }
+    }
+
+    /// The number of rows left to return, assuming the file holds as many rows as its header
+    /// says.
+    pub fn remaining(&self) -> u64 {
+        let selected = self
+            .reader
+            .context()
+            .row_count
+            .saturating_sub(self.row_offset);
+        let selected = self.row_limit.map_or(selected, |limit| selected.min(limit));
+        selected.saturating_sub(self.rows_returned)
+    }
+
+    /// Read the next page into the page buffer and locate its rows, skipping rows before the
+    /// offset.
+    fn fill_page(&mut self) -> Result<(), SasError> {
+        let index = self.next_page;
+        let page = self.reader.page(index)?;
+        self.page.clear();
+        self.page.extend_from_slice(&page);
+        self.next_page += 1;
+        self.slots.clear();
+        self.next_slot = 0;
+
+        let ctx = self.reader.context();
+        let rows =
+            page_rows(&self.page, ctx).map_err(|err| at_page(err, ctx, index, self.rows_seen))?;
+        let page_start = self.page.as_ptr() as usize;
+        for row in rows {
+            if self.rows_seen >= ctx.row_count {
+                break;
+            }
+            if self.rows_seen >= self.row_offset {
+                self.slots.push(match row {
+                    Cow::Borrowed(row) => {
+                        let start = row.as_ptr() as usize - page_start;
+                        RowSlot::Page(start..start + row.len())
+                    }
+                    Cow::Owned(row) => RowSlot::Decompressed(row),
+                });
+            }
+            self.rows_seen += 1;
+        }
+        Ok(self
+            .reader
+            .report_progress(self.next_page, self.rows_seen)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::{ReadStatCompression, ReadStatValueType};
+
+    fn fixture(compression: ReadStatCompression) -> SasFixture {
+        SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 12),
+            ])
+            .rows(
+                (0..400)
+                    .map(|i| {
+                        vec![
+                            ReadStatValueType::Double(i as f64),
+                            ReadStatValueType::String(format!("caf\u{e9} {}", i)),
+                        ]
+                    })
+                    .collect(),
+            )
+            .build()
+            .with_compression(compression)
+    }
+
+    #[test]
+    fn test_borrowed_rows_match_owned_rows() {
+        for compression in [ReadStatCompression::None, ReadStatCompression::Rows] {
+            let bytes = fixture(compression).to_bytes();
+            let mut reader = Sas7bdatReader::from_bytes(bytes).unwrap();
+            let options = SasReadOptions::builder()
+                .row_offset(150)
+                .row_limit(Some(200))
+                .build()
+                .unwrap();
+            let expected = reader.read_rows(&options).unwrap();
+
+            let mut rows = reader.borrowed_rows(&options).unwrap();
+            let mut count = 0;
+            while let Some(row) = rows.next() {
+                assert_eq!(row.unwrap().to_values(), expected[count]);
+                count += 1;
+            }
+            assert_eq!(count, 200);
+        }
+    }
+
+    #[test]
+    fn test_borrowed_values() {
+        let mut reader =
+            Sas7bdatReader::from_bytes(fixture(ReadStatCompression::None).to_bytes()).unwrap();
+        let options = SasReadOptions::builder()
+            .columns(Some(vec!["NAME".to_string(), "ID".to_string()]))
+            .row_offset(7)
+            .build()
+            .unwrap();
+        let mut rows = reader.borrowed_rows(&options).unwrap();
+        let row = rows.next().unwrap().unwrap();
+
+        assert_eq!(row.len(), 2);
+        let name = row.get(0).unwrap();
+        assert_eq!(name.as_bytes(), "caf\u{e9} 7".as_bytes());
+        assert!(matches!(name.as_str(), Some(Cow::Borrowed("caf\u{e9} 7"))));
+        assert_eq!(name.as_f64(), None);
+        let id = row.get(1).unwrap();
+        assert_eq!(id.as_f64(), Some(7.0));
+        assert_eq!(id.as_str(), None);
+        assert!(row.get(2).is_none());
+    }
+}
@@ -0,0 +1,448 @@
+use crate::sas::sas7bdat::borrowed::Sas7bdatBorrowedRows;
+use crate::sas::sas7bdat::reader::Sas7bdatReader;
+use crate::sas::sas7bdat::row::SasRowRef;
+use crate::types::{ReadStatError, ReadStatType, SasError, SasMissing, SasReadOptions};
+
+/// A growable sequence of bits, packed 64 to a word with the first bit in the least significant
+/// bit of the first word.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SasBitmap {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl SasBitmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_capacity(bits: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(bits.div_ceil(64)),
+            len: 0,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn push(&mut self, bit: bool) {
+        if self.len.is_multiple_of(64) {
+            self.words.push(0);
+        }
+        if bit {
+            self.words[self.len / 64] |= 1 << (self.len % 64);
+        }
+        self.len += 1;
+    }
+
+    /// Return the bit at `index`, or false past the end.
+    pub fn get(&self, index: usize) -> bool {
+        index < self.len && self.words[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// The number of bits set.
+    pub fn count_ones(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The packed bits. Bits past `len` in the last word are zero.
+    pub fn as_words(&self) -> &[u64] {
+        &self.words
+    }
+}
+
+/// The values of a numeric variable. Missing values are NaN in `values` and set in `missing`;
+/// `missing_kinds` holds the kind of each missing value, and `None` for every other value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SasNumericColumn {
+    pub values: Vec<f64>,
+    pub missing: SasBitmap,
+    pub missing_kinds: Vec<Option<SasMissing>>,
+}
+
+impl SasNumericColumn {
+    pub fn with_capacity(rows: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(rows),
+            missing: SasBitmap::with_capacity(rows),
+            missing_kinds: Vec::with_capacity(rows),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Append a value, with its kind if it is missing. NaN without a kind is system missing.
+    pub fn push(&mut self, value: f64, missing: Option<SasMissing>) {
+        self.values.push(value);
+        self.missing.push(value.is_nan());
+        self.missing_kinds.push(
+            value
+                .is_nan()
+                .then(|| missing.unwrap_or(SasMissing::System)),
+        );
+    }
+
+    /// Return the value at `index`, or `None` if it is missing or past the end.
+    pub fn get(&self, index: usize) -> Option<f64> {
+        self.values
+            .get(index)
+            .copied()
+            .filter(|_| !self.missing.get(index))
+    }
+
+    /// Return the kind of missing value at `index`, or `None` if it is not missing.
+    pub fn missing_kind(&self, index: usize) -> Option<SasMissing> {
+        self.missing_kinds.get(index).copied().flatten()
+    }
+}
+
+/// The values of a character variable, without their padding: value `i` is the text between
+/// `offsets[i]` and `offsets[i + 1]` in `data`. `offsets` always starts with 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasCharacterColumn {
+    pub offsets: Vec<usize>,
+    pub data: String,
+}
+
+impl SasCharacterColumn {
+    /// Create an empty column with room for the offsets of `rows` values. The text grows as
+    /// values are pushed, since it is usually much shorter than the storage width.
+    pub fn with_capacity(rows: usize) -> Self {
+        let mut offsets = Vec::with_capacity(rows.saturating_add(1));
+        offsets.push(0);
+        Self {
+            offsets,
+            data: String::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn push(&mut self, value: &str) {
+        self.data.push_str(value);
+        self.offsets.push(self.data.len());
+    }
+
+    /// Return the value at `index`, or `None` past the end.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let start = *self.offsets.get(index)?;
+        let end = *self.offsets.get(index + 1)?;
+        Some(&self.data[start..end])
+    }
+
+    /// Iterate over the values in row order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        self.offsets
+            .windows(2)
+            .map(|bounds| &self.data[bounds[0]..bounds[1]])
+    }
+}
+
+impl Default for SasCharacterColumn {
+    fn default() -> Self {
+        Self::with_capacity(0)
+    }
+}
+
+/// The values of one variable in a batch.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SasColumnData {
+    Numeric(SasNumericColumn),
+    Character(SasCharacterColumn),
+}
+
+impl SasColumnData {
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Numeric(column) => column.len(),
+            Self::Character(column) => column.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn as_numeric(&self) -> Option<&SasNumericColumn> {
+        match self {
+            Self::Numeric(column) => Some(column),
+            Self::Character(_) => None,
+        }
+    }
+
+    pub fn as_character(&self) -> Option<&SasCharacterColumn> {
+        match self {
+            Self::Character(column) => Some(column),
+            Self::Numeric(_) => None,
+        }
+    }
+}
+
+/// A run of consecutive rows stored column by column. `names` and `columns` follow the order
+/// in which the variables were selected, and every column holds `row_count` values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasColumnBatch {
+    pub row_count: usize,
+    pub names: Vec<String>,
+    pub columns: Vec<SasColumnData>,
+}
+
+impl SasColumnBatch {
+    /// Return the column of the variable with the given name, compared case-insensitively as
+    /// SAS does.
+    pub fn column(&self, name: &str) -> Option<&SasColumnData> {
+        self.names
+            .iter()
+            .position(|known| known.eq_ignore_ascii_case(name))
+            .map(|index| &self.columns[index])
+    }
+
+    fn push_row(&mut self, row: &SasRowRef<'_>) {
+        for (column, value) in self.columns.iter_mut().zip(row.values()) {
+            match column {
+                SasColumnData::Numeric(column) => {
+                    column.push(value.as_f64().unwrap_or(f64::NAN), value.missing_kind())
+                }
+                SasColumnData::Character(column) => {
+                    column.push(&value.as_str().unwrap_or_default())
+                }
+            }
+        }
+        self.row_count += 1;
+    }
+}
+
+impl Sas7bdatReader {
+    /// Read the rows selected by `options` in batches of up to `batch_size` rows stored column
+    /// by column, for vectorised processing. Values are copied straight from the pages into the
+    /// columns, without going through `ReadStatValue`. Fails with `ReadStatError::BadBatchSize`
+    /// if `batch_size` is 0.
+    pub fn column_batches(
+        &mut self,
+        options: &SasReadOptions,
+        batch_size: usize,
+    ) -> Result<Sas7bdatColumnBatches<'_>, SasError> {
+        if batch_size == 0 {
+            return Err(SasError::new(ReadStatError::BadBatchSize));
+        }
+        let selection = self.resolve_columns(&options.columns)?;
+        let variables = self.variables();
+        let names = selection
+            .iter()
+            .map(|&index| variables[index].name.clone())
+            .collect();
+        let is_character = selection
+            .iter()
+            .map(|&index| variables[index].type_ == ReadStatType::String)
+            .collect();
+        Ok(Sas7bdatColumnBatches {
+            rows: self.borrowed_rows(options)?,
+            names,
+            is_character,
+            batch_size,
+            finished: false,
+        })
+    }
+}
+
+/// Iterator over the rows of a sas7bdat file in column batches, created by
+/// `Sas7bdatReader::column_batches`. Every batch but the last holds `batch_size` rows, and
+/// the iteration ends after the first error.
+#[derive(Debug)]
+pub struct Sas7bdatColumnBatches<'a> {
+    rows: Sas7bdatBorrowedRows<'a>,
+    names: Vec<String>,
+    /// Whether each selected variable is a character variable.
+    is_character: Vec<bool>,
+    batch_size: usize,
+    finished: bool,
+}
+
+impl Iterator for Sas7bdatColumnBatches<'_> {
+    type Item = Result<SasColumnBatch, SasError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        let rows = usize::try_from(self.rows.remaining())
+            .unwrap_or(usize::MAX)
+            .min(self.batch_size);
+        let mut batch = SasColumnBatch {
+            row_count: 0,
+            names: self.names.clone(),
+            columns: self
+                .is_character
+                .iter()
+                .map(|&is_character| match is_character {
+                    true => SasColumnData::Character(SasCharacterColumn::with_capacity(rows)),
+                    false => SasColumnData::Numeric(SasNumericColumn::with_capacity(rows)),
+                })
+                .collect(),
+        };
+        while batch.row_count < self.batch_size {
+            match self.rows.next() {
+                Some(Ok(row)) => batch.push_row(&row),
+                Some(Err(err)) => {
+                    self.finished = true;
+                    return Some(Err(err));
+                }
+                None => {
+                    self.finished = true;
+                    break;
+                }
+            }
+        }
+        (batch.row_count > 0).then_some(Ok(batch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::{ReadStatValueType, SasReadOptions};
+
+    fn reader() -> Sas7bdatReader {
+        let fixture = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 8),
+            ])
+            .rows(
+                (0..250)
+                    .map(|i| {
+                        vec![
+                            match i % 10 {
+                                0 => ReadStatValueType::Double(f64::NAN),
+                                _ => ReadStatValueType::Double(i as f64),
+                            },
+                            ReadStatValueType::String(format!("n{}", i)),
+                        ]
+                    })
+                    .collect(),
+            )
+            .build();
+        Sas7bdatReader::from_bytes(fixture.to_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_bitmap() {
+        let mut bitmap = SasBitmap::new();
+        for i in 0..130 {
+            bitmap.push(i % 3 == 0);
+        }
+        assert_eq!(bitmap.len(), 130);
+        assert!(bitmap.get(129));
+        assert!(!bitmap.get(128));
+        assert!(!bitmap.get(130));
+        assert_eq!(bitmap.count_ones(), 44);
+        assert_eq!(bitmap.as_words().len(), 3);
+        assert_eq!(bitmap.as_words()[2], 0b10);
+    }
+
+    #[test]
+    fn test_numeric_column_tags() {
+        let mut column = SasNumericColumn::default();
+        column.push(1.5, None);
+        column.push(f64::NAN, Some(SasMissing::Letter('R')));
+        column.push(f64::NAN, Some(SasMissing::Letter('D')));
+        column.push(f64::NAN, None);
+        assert_eq!(column.get(0), Some(1.5));
+        assert_eq!(column.get(1), None);
+        assert_eq!(column.missing_kind(0), None);
+        assert_eq!(column.missing_kind(1), Some(SasMissing::Letter('R')));
+        assert_eq!(column.missing_kind(2), Some(SasMissing::Letter('D')));
+        assert_eq!(column.missing_kind(3), Some(SasMissing::System));
+        assert_eq!(column.missing.count_ones(), 3);
+    }
+
+    #[test]
+    fn test_column_batches() {
+        let mut reader = reader();
+        let batches: Vec<SasColumnBatch> = reader
+            .column_batches(&SasReadOptions::default(), 100)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        let sizes: Vec<usize> = batches.iter().map(|batch| batch.row_count).collect();
+        assert_eq!(sizes, [100, 100, 50]);
+        let batch = &batches[1];
+        assert_eq!(batch.names, ["ID", "NAME"]);
+
+        let ids = batch.column("id").unwrap().as_numeric().unwrap();
+        assert_eq!(ids.len(), 100);
+        assert_eq!(ids.get(1), Some(101.0));
+        assert_eq!(ids.get(10), None);
+        assert_eq!(ids.missing.count_ones(), 10);
+
+        let names = batch.column("NAME").unwrap().as_character().unwrap();
+        assert_eq!(names.get(0), Some("n100"));
+        assert_eq!(names.iter().last(), Some("n199"));
+        assert_eq!(names.data.len(), 400);
+    }
+
+    #[test]
+    fn test_column_batches_with_options() {
+        let mut reader = reader();
+        let options = SasReadOptions::builder()
+            .columns(Some(vec!["NAME".to_string()]))
+            .row_offset(245)
+            .row_limit(Some(10))
+            .build()
+            .unwrap();
+        let batches: Vec<SasColumnBatch> = reader
+            .column_batches(&options, 1)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(batches.len(), 5);
+        assert_eq!(batches[0].columns.len(), 1);
+        assert_eq!(
+            batches[4].columns[0].as_character().unwrap().get(0),
+            Some("n249")
+        );
+    }
+
+    #[test]
+    fn test_column_batches_size() {
+        let mut reader = reader();
+        let err = reader
+            .column_batches(&SasReadOptions::default(), 0)
+            .unwrap_err();
+        assert_eq!(err.kind, ReadStatError::BadBatchSize);
+
+        let options = SasReadOptions::builder()
+            .row_limit(Some(3))
+            .build()
+            .unwrap();
+        let mut batches = reader.column_batches(&options, usize::MAX).unwrap();
+        let batch = batches.next().unwrap().unwrap();
+        assert_eq!(batch.row_count, 3);
+        assert!(batches.next().is_none());
+    }
+}
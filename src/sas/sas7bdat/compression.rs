@@ -0,0 +1,344 @@
+use crate::types::ReadStatError;
+
+// #define SAS_RLE_COMMAND_COPY64          0
+// #define SAS_RLE_COMMAND_COPY64_PLUS_4096 1
+// #define SAS_RLE_COMMAND_COPY96          2
+// #define SAS_RLE_COMMAND_INSERT_BYTE18   4
+// #define SAS_RLE_COMMAND_INSERT_AT17     5
+// #define SAS_RLE_COMMAND_INSERT_BLANK17  6
+// #define SAS_RLE_COMMAND_INSERT_ZERO17   7
+// #define SAS_RLE_COMMAND_COPY1           8
+// #define SAS_RLE_COMMAND_COPY17          9
+// #define SAS_RLE_COMMAND_COPY33         10
+// #define SAS_RLE_COMMAND_COPY49         11
+// #define SAS_RLE_COMMAND_INSERT_BYTE3   12
+// #define SAS_RLE_COMMAND_INSERT_AT2     13
+// #define SAS_RLE_COMMAND_INSERT_BLANK2  14
+// #define SAS_RLE_COMMAND_INSERT_ZERO2   15
+const SAS_RLE_COMMAND_COPY64: u8 = 0;
+const SAS_RLE_COMMAND_COPY64_PLUS_4096: u8 = 1;
+const SAS_RLE_COMMAND_COPY96: u8 = 2;
+const SAS_RLE_COMMAND_INSERT_BYTE18: u8 = 4;
+const SAS_RLE_COMMAND_INSERT_AT17: u8 = 5;
+const SAS_RLE_COMMAND_INSERT_BLANK17: u8 = 6;
+const SAS_RLE_COMMAND_INSERT_ZERO17: u8 = 7;
+const SAS_RLE_COMMAND_COPY1: u8 = 8;
+const SAS_RLE_COMMAND_COPY17: u8 = 9;
+const SAS_RLE_COMMAND_COPY33: u8 = 10;
+const SAS_RLE_COMMAND_COPY49: u8 = 11;
+const SAS_RLE_COMMAND_INSERT_BYTE3: u8 = 12;
+const SAS_RLE_COMMAND_INSERT_AT2: u8 = 13;
+const SAS_RLE_COMMAND_INSERT_BLANK2: u8 = 14;
+const SAS_RLE_COMMAND_INSERT_ZERO2: u8 = 15;
+
+/// The most bytes either compression scheme can produce per input byte: an RLE zero insertion
+/// writes up to 4112 bytes from 2. Longer rows are rejected before anything is allocated.
+const MAX_EXPANSION: usize = 2056;
+
+/// Decompress a row compressed with the SAS run-length encoding (`SASYZCRL`, also known as
+/// "CHAR" compression).
+///
+/// # Arguments
+/// * `input` - The bytes of the compressed row subheader.
+/// * `output_len` - The uncompressed row length from the row size subheader.
+///
+/// # Returns
+/// The decompressed row, or `ReadStatError::RowWidthMismatch` if it is not exactly
+/// `output_len` bytes long.
+///
+/// # Original C function
+/// `sas_rle_decompress` in readstat's `sas_rle.c`.
+pub fn rle_decompress(input: &[u8], output_len: usize) -> Result<Vec<u8>, ReadStatError> {
+    check_expansion(input, output_len)?;
+    let mut output = Vec::with_capacity(output_len);
+    let mut position = 0;
+
+    let next_byte = |position: &mut usize| -> Result<u8, ReadStatError> {
+        let byte = *input.get(*position).ok_or(ReadStatError::Parse)?;
+        *position += 1;
+        Ok(byte)
+    };
+
+    while position < input.len() {
+        let control = next_byte(&mut position)?;
+        let command = (control & 0xF0) >> 4;
+        let length = (control & 0x0F) as usize;
+
+        let mut copy_len = 0;
+        let mut insert_len = 0;
+        let mut insert_byte = 0;
+
+        match command {
+            SAS_RLE_COMMAND_COPY64 => {
+                copy_len = next_byte(&mut position)? as usize + 64 + length * 256;
+            }
+            SAS_RLE_COMMAND_COPY64_PLUS_4096 => {
+                copy_len = next_byte(&mut position)? as usize + 64 + length * 256 + 4096;
+            }
+            SAS_RLE_COMMAND_COPY96 => copy_len = length + 96,
+            SAS_RLE_COMMAND_INSERT_BYTE18 => {
+                insert_len = next_byte(&mut position)? as usize + 18 + length * 256;
+                insert_byte = next_byte(&mut position)?;
+            }
+            SAS_RLE_COMMAND_INSERT_AT17 => {
+                insert_len = next_byte(&mut position)? as usize + 17 + length * 256;
+                insert_byte = b'@';
+            }
+            SAS_RLE_COMMAND_INSERT_BLANK17 => {
+                insert_len = next_byte(&mut position)? as usize + 17 + length * 256;
+                insert_byte = b' ';
+            }
+            SAS_RLE_COMMAND_INSERT_ZERO17 => {
+                insert_len = next_byte(&mut position)? as usize + 17 + length * 256;
+            }
+            SAS_RLE_COMMAND_COPY1 => copy_len = length + 1,
+            SAS_RLE_COMMAND_COPY17 => copy_len = length + 17,
+            SAS_RLE_COMMAND_COPY33 => copy_len = length + 33,
+            SAS_RLE_COMMAND_COPY49 => copy_len = length + 49,
+            SAS_RLE_COMMAND_INSERT_BYTE3 => {
+                insert_byte = next_byte(&mut position)?;
+                insert_len = length + 3;
+            }
+            SAS_RLE_COMMAND_INSERT_AT2 => {
+                insert_byte = b'@';
+                insert_len = length + 2;
+            }
+            SAS_RLE_COMMAND_INSERT_BLANK2 => {
+                insert_byte = b' ';
+                insert_len = length + 2;
+            }
+            SAS_RLE_COMMAND_INSERT_ZERO2 => insert_len = length + 2,
+            _ => {}
+        }
+
+        if copy_len > 0 {
+            if output.len() + copy_len > output_len || position + copy_len > input.len() {
+                return Err(ReadStatError::RowWidthMismatch);
+            }
+            output.extend_from_slice(&input[position..position + copy_len]);
+            position += copy_len;
+        }
+
+        if insert_len > 0 {
+            if output.len() + insert_len > output_len {
+                return Err(ReadStatError::RowWidthMismatch);
+            }
+            output.resize(output.len() + insert_len, insert_byte);
+        }
+    }
+
+    if output.len() != output_len {
+        return Err(ReadStatError::RowWidthMismatch);
+    }
+
+    Ok(output)
+}
+
+/// Decompress a row compressed with Ross Data Compression (`SASYZCR2`, also known as "BINARY"
+/// compression).
+///
+/// # Arguments
+/// * `input` - The bytes of the compressed row subheader.
+/// * `output_len` - The uncompressed row length from the row size subheader.
+///
+/// # Returns
+/// The decompressed row, or `ReadStatError::RowWidthMismatch` if it is not exactly
+/// `output_len` bytes long.
+///
+/// # Original C function
+/// `sas_rdc_decompress` in readstat's `sas_rdc.c`.
+pub fn rdc_decompress(input: &[u8], output_len: usize) -> Result<Vec<u8>, ReadStatError> {
+    check_expansion(input, output_len)?;
+    let mut output: Vec<u8> = Vec::with_capacity(output_len);
+    let mut position = 0;
+    let mut control_bits: u16 = 0;
+    let mut control_mask: u16 = 0;
+
+    let next_byte = |position: &mut usize| -> Result<u8, ReadStatError> {
+        let byte = *input.get(*position).ok_or(ReadStatError::Parse)?;
+        *position += 1;
+        Ok(byte)
+    };
+
+    while position < input.len() {
+        control_mask >>= 1;
+        if control_mask == 0 {
+            let high = next_byte(&mut position)? as u16;
+            let low = next_byte(&mut position)? as u16;
+            control_bits = (high << 8) | low;
+            control_mask = 0x8000;
+        }
+
+        if control_bits & control_mask == 0 {
+            let byte = next_byte(&mut position)?;
+            output.push(byte);
+        } else {
+            let command_byte = next_byte(&mut position)?;
+            let command = (command_byte >> 4) & 0x0F;
+            let count = (command_byte & 0x0F) as usize;
+
+            match command {
+                // Short run: repeat the next byte 3 to 18 times
+                0 => {
+                    let byte = next_byte(&mut position)?;
+                    output.resize(output.len() + count + 3, byte);
+                }
+                // Long run: repeat the next byte 19 to 4114 times
+                1 => {
+                    let count = count + ((next_byte(&mut position)? as usize) << 4) + 19;
+                    let byte = next_byte(&mut position)?;
+                    output.resize(output.len() + count, byte);
+                }
+                // Long pattern: copy 16 to 271 earlier bytes
+                2 => {
+                    let offset = count + 3 + ((next_byte(&mut position)? as usize) << 4);
+                    let count = next_byte(&mut position)? as usize + 16;
+                    copy_pattern(&mut output, offset, count)?;
+                }
+                // Short pattern: copy 3 to 15 earlier bytes
+                _ => {
+                    let offset = count + 3 + ((next_byte(&mut position)? as usize) << 4);
+                    copy_pattern(&mut output, offset, command as usize)?;
+                }
+            }
+        }
+
+        if output.len() > output_len {
+            return Err(ReadStatError::RowWidthMismatch);
+        }
+    }
+
+    if output.len() != output_len {
+        return Err(ReadStatError::RowWidthMismatch);
+    }
+
+    Ok(output)
+}
+
+/// Refuse an `output_len` more than `MAX_EXPANSION` times the length of the compressed `input`.
+fn check_expansion(input: &[u8], output_len: usize) -> Result<(), ReadStatError> {
+    if output_len > input.len().saturating_mul(MAX_EXPANSION) {
+        return Err(ReadStatError::RowWidthMismatch);
+    }
+    Ok(())
+}
+
+/// Append `count` bytes to `output`, starting `offset` bytes back from its end. The source and
+/// destination may overlap, so the bytes are copied one at a time.
+fn copy_pattern(output: &mut Vec<u8>, offset: usize, count: usize) -> Result<(), ReadStatError> {
+    if offset > output.len() {
+        return Err(ReadStatError::Parse);
+    }
+    let start = output.len() - offset;
+    for i in 0..count {
+        let byte = output[start + i];
+        output.push(byte);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rle_copy_and_insert() {
+        // COPY1 of 3 bytes, then INSERT_BLANK2 of 4 blanks, then INSERT_ZERO2 of 2 zeros
+        let input = [0x82, b'a', b'b', b'c', 0xE2, 0xF0];
+        let output = rle_decompress(&input, 9).unwrap();
+        assert_eq!(output, b"abc    \0\0");
+    }
+
+    #[test]
+    fn test_rle_copy64() {
+        let mut input = vec![0x00, 0x00];
+        input.extend(std::iter::repeat_n(b'x', 64));
+        let output = rle_decompress(&input, 64).unwrap();
+        assert_eq!(output, vec![b'x'; 64]);
+    }
+
+    #[test]
+    fn test_rle_insert_byte18() {
+        let input = [0x40, 0x02, b'z'];
+        let output = rle_decompress(&input, 20).unwrap();
+        assert_eq!(output, vec![b'z'; 20]);
+    }
+
+    #[test]
+    fn test_rle_wrong_length() {
+        let input = [0x82, b'a', b'b', b'c'];
+        assert_eq!(
+            rle_decompress(&input, 4),
+            Err(ReadStatError::RowWidthMismatch)
+        );
+        assert_eq!(
+            rle_decompress(&input, 2),
+            Err(ReadStatError::RowWidthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_rle_truncated_input() {
+        let input = [0x40, 0x02];
+        assert_eq!(rle_decompress(&input, 20), Err(ReadStatError::Parse));
+    }
+
+    #[test]
+    fn test_impossible_expansion() {
+        // A zero insertion of the greatest length is the largest expansion possible
+        let input = [0x7F, 0xFF];
+        assert_eq!(rle_decompress(&input, 4112), Ok(vec![0; 4112]));
+        assert_eq!(
+            rle_decompress(&input, 4113),
+            Err(ReadStatError::RowWidthMismatch)
+        );
+        assert_eq!(
+            rdc_decompress(&input, usize::MAX),
+            Err(ReadStatError::RowWidthMismatch)
+        );
+    }
+
+    #[test]
+    fn test_rdc_literals() {
+        let input = [0x00, 0x00, b'a', b'b', b'c'];
+        let output = rdc_decompress(&input, 3).unwrap();
+        assert_eq!(output, b"abc");
+    }
+
+    #[test]
+    fn test_rdc_short_run() {
+        // One literal, then a short run of 5 'b's
+        let input = [0x40, 0x00, b'a', 0x02, b'b'];
+        let output = rdc_decompress(&input, 6).unwrap();
+        assert_eq!(output, b"abbbbb");
+    }
+
+    #[test]
+    fn test_rdc_long_run() {
+        let input = [0x80, 0x00, 0x10, 0x01, b'r'];
+        let output = rdc_decompress(&input, 35).unwrap();
+        assert_eq!(output, vec![b'r'; 35]);
+    }
+
+    #[test]
+    fn test_rdc_short_pattern() {
+        // Three literals, then copy them again with a short pattern (command 3, offset 3)
+        let input = [0x10, 0x00, b'a', b'b', b'c', 0x30, 0x00];
+        let output = rdc_decompress(&input, 6).unwrap();
+        assert_eq!(output, b"abcabc");
+    }
+
+    #[test]
+    fn test_rdc_long_pattern() {
+        // Three literals, then a long pattern copying 16 bytes from 3 back
+        let input = [0x10, 0x00, b'a', b'b', b'c', 0x20, 0x00, 0x00];
+        let output = rdc_decompress(&input, 19).unwrap();
+        assert_eq!(output, b"abcabcabcabcabcabca");
+    }
+
+    #[test]
+    fn test_rdc_pattern_before_start() {
+        let input = [0x80, 0x00, 0x30, 0x00];
+        assert_eq!(rdc_decompress(&input, 3), Err(ReadStatError::Parse));
+    }
+}
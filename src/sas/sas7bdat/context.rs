@@ -0,0 +1,155 @@
+use crate::sas::cursor::SasCursor;
+use crate::types::{
+    ReadStatCompression, ReadStatError, ReadStatVendor, ReaderLimits, SasEndian, SasHeaderInfo,
+    SasTextRef,
+};
+
+/// Parsing state shared by every page of a sas7bdat file. The page geometry comes from the file
+/// header; the row layout and compression are filled in once the metadata subheaders have been
+/// read. This plays the role of the `sas7bdat_ctx_t` struct that readstat threads through its
+/// parser, and it is all a page needs to be decoded independently of the others. The limits
+/// the file is read under travel with it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sas7bdatContext {
+    pub endianness: SasEndian,
+    pub is_64bit: bool,
+    pub vendor: ReadStatVendor,
+    pub header_size: u64,
+    pub page_size: usize,
+    pub page_count: u64,
+    pub page_header_size: usize,
+    pub subheader_pointer_size: usize,
+    pub encoding: String,
+    pub compression: ReadStatCompression,
+    pub row_length: usize,
+    pub row_count: u64,
+    pub mix_page_row_count: u64,
+    pub limits: ReaderLimits,
+}
+
+impl Sas7bdatContext {
+    /// Create a context from the file header. The row layout is empty until the metadata
+    /// subheaders have been parsed.
+    pub fn from_header(header: &SasHeaderInfo) -> Self {
+        Self {
+            endianness: header.endianness.clone(),
+            is_64bit: header.is_64bit,
+            vendor: header.vendor.clone(),
+            header_size: header.header_size,
+            page_size: header.page_size as usize,
+            page_count: header.page_count,
+            page_header_size: header.page_header_size as usize,
+            subheader_pointer_size: header.subheader_pointer_size as usize,
+            encoding: header.encoding.clone(),
+            compression: ReadStatCompression::None,
+            row_length: 0,
+            row_count: 0,
+            mix_page_row_count: 0,
+            limits: ReaderLimits::default(),
+        }
+    }
+
+    /// The size in bytes of integers, offsets and subheader signatures: 8 for 64-bit files and
+    /// 4 for 32-bit files.
+    pub fn int_size(&self) -> usize {
+        if self.is_64bit {
+            8
+        } else {
+            4
+        }
+    }
+
+    /// Byte offset of the given page from the start of the file. Offsets past the largest
+    /// possible file, which only corrupt page counts lead to, saturate.
+    pub fn page_offset(&self, index: u64) -> u64 {
+        index
+            .saturating_mul(self.page_size as u64)
+            .saturating_add(self.header_size)
+    }
+
+    /// Return a cursor reading `data` in the file's byte order, from `offset`.
+    pub fn cursor<'a>(
+        &self,
+        data: &'a [u8],
+        offset: usize,
+    ) -> Result<SasCursor<'a>, ReadStatError> {
+        SasCursor::at(data, offset, self.endianness.clone())
+    }
+
+    pub fn read2(&self, data: &[u8], offset: usize) -> Result<u16, ReadStatError> {
+        self.cursor(data, offset)?.read_u16()
+    }
+
+    pub fn read4(&self, data: &[u8], offset: usize) -> Result<u32, ReadStatError> {
+        self.cursor(data, offset)?.read_u32()
+    }
+
+    pub fn read8(&self, data: &[u8], offset: usize) -> Result<u64, ReadStatError> {
+        self.cursor(data, offset)?.read_u64()
+    }
+
+    /// Read an integer of the file's word size: 8 bytes for 64-bit files and 4 bytes otherwise.
+    pub fn read_int(&self, data: &[u8], offset: usize) -> Result<u64, ReadStatError> {
+        if self.is_64bit {
+            self.read8(data, offset)
+        } else {
+            self.read4(data, offset).map(u64::from)
+        }
+    }
+
+    /// Read the three 16-bit fields of a text reference.
+    pub fn read_text_ref(&self, data: &[u8], offset: usize) -> Result<SasTextRef, ReadStatError> {
+        let mut cursor = self.cursor(data, offset)?;
+        Ok(SasTextRef::new(
+            cursor.read_u16()?,
+            cursor.read_u16()?,
+            cursor.read_u16()?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_from_header() {
+        let header = SasHeaderInfo::builder()
+            .is_64bit(true)
+            .page_header_size(40)
+            .subheader_pointer_size(24)
+            .header_size(8192)
+            .build()
+            .unwrap();
+        let ctx = Sas7bdatContext::from_header(&header);
+        assert_eq!(ctx.int_size(), 8);
+        assert_eq!(ctx.page_offset(2), 8192 + 2 * 4096);
+        assert_eq!(ctx.compression, ReadStatCompression::None);
+    }
+
+    #[test]
+    fn test_context_reads_in_file_byte_order() {
+        let little = Sas7bdatContext::from_header(&SasHeaderInfo::builder().build().unwrap());
+        let big = Sas7bdatContext::from_header(
+            &SasHeaderInfo::builder()
+                .endianness(SasEndian::Big)
+                .build()
+                .unwrap(),
+        );
+        let data = [0x01, 0x02, 0x03, 0x04];
+        assert_eq!(little.read2(&data, 0), Ok(0x0201));
+        assert_eq!(big.read2(&data, 2), Ok(0x0304));
+        assert_eq!(little.read4(&data, 0), Ok(0x04030201));
+        assert_eq!(big.read_int(&data, 0), Ok(0x01020304));
+        assert_eq!(big.read4(&data, 1), Err(ReadStatError::Read));
+        assert_eq!(big.read2(&data, 5), Err(ReadStatError::Read));
+    }
+
+    #[test]
+    fn test_context_read_text_ref() {
+        let ctx = Sas7bdatContext::from_header(&SasHeaderInfo::builder().build().unwrap());
+        let data = [0x01, 0x00, 0x08, 0x00, 0x04, 0x00];
+        assert_eq!(ctx.read_text_ref(&data, 0), Ok(SasTextRef::new(1, 8, 4)));
+        assert_eq!(ctx.read_text_ref(&data, 2), Err(ReadStatError::Read));
+    }
+}
@@ -0,0 +1,463 @@
+use crate::sas::sas7bdat::reader::Sas7bdatReader;
+use crate::sas::temporal::decode_temporal_with;
+use crate::types::{
+    ReadStatError, ReadStatType, ReadStatValueType, ReadStatVariable, SasError, SasPolarsOptions,
+    SasReadOptions, SasTemporalKind, SasTemporalPolicy, SasTemporalValue,
+};
+use chrono::{NaiveDateTime, Timelike};
+use polars::prelude::*;
+use std::any::Any;
+use std::path::{Path, PathBuf};
+
+/// Read a sas7bdat file into a Polars `DataFrame`.
+///
+/// Numeric variables become `Float64` columns, with missing values (system and tagged) as
+/// nulls. Numerics with a date, datetime or time format become `Date`, `Datetime` (in
+/// microseconds, without a time zone) and `Time` columns; a value that cannot be represented,
+/// such as a time beyond 24 hours, fails the read with `ReadStatError::BadTimestampValue`. Use
+/// `read_sas_polars_with` to choose a different `SasTemporalPolicy`. Character variables
+/// become `String` columns with their trailing blanks removed, so a blank value is an empty
+/// string.
+///
+/// # Arguments
+/// * `path` - The path of the sas7bdat file.
+/// * `options` - The columns and rows to read. Only the selected columns are decoded.
+pub fn read_sas_polars<P: AsRef<Path>>(
+    path: P,
+    options: &SasReadOptions,
+) -> PolarsResult<DataFrame> {
+    read_sas_polars_with(path, options, SasPolarsOptions::default())
+}
+
+/// Read a sas7bdat file into a Polars `DataFrame` as `read_sas_polars` does, applying the
+/// temporal policy of `polars_options` to dates that cannot be decoded.
+///
+/// # Arguments
+/// * `path` - The path of the sas7bdat file.
+/// * `options` - The columns and rows to read. Only the selected columns are decoded.
+/// * `polars_options` - What happens to dates that cannot be decoded.
+pub fn read_sas_polars_with<P: AsRef<Path>>(
+    path: P,
+    options: &SasReadOptions,
+    polars_options: SasPolarsOptions,
+) -> PolarsResult<DataFrame> {
+    let mut reader = Sas7bdatReader::open(path).map_err(to_polars_error)?;
+    let selection = reader
+        .resolve_columns(&options.columns)
+        .map_err(to_polars_error)?;
+    let variables: Vec<ReadStatVariable> = selection
+        .iter()
+        .map(|&index| reader.variables()[index].clone())
+        .collect();
+
+    let mut buffers: Vec<ColumnBuffer> = variables
+        .iter()
+        .map(|variable| ColumnBuffer::new(variable, polars_options))
+        .collect();
+    for (index, row) in reader.rows(options).map_err(to_polars_error)?.enumerate() {
+        let row = row.map_err(to_polars_error)?;
+        for ((buffer, value), variable) in buffers.iter_mut().zip(row).zip(&variables) {
+            buffer.push(value.value).map_err(|err| {
+                to_polars_error(
+                    SasError::new(err)
+                        .with_variable(variable.name.as_str())
+                        .with_row(options.row_offset + index as u64),
+                )
+            })?;
+        }
+    }
+
+    let columns = variables
+        .iter()
+        .zip(buffers)
+        .map(|(variable, buffer)| buffer.into_column(&variable.name))
+        .collect::<PolarsResult<Vec<Column>>>()?;
+    DataFrame::new(columns)
+}
+
+/// Lazily scan a sas7bdat file. Column selections and row limits in the query are pushed down
+/// into the reader, so only the requested columns are decoded and reading stops once enough
+/// rows have been produced.
+pub fn scan_sas_polars<P: AsRef<Path>>(path: P) -> PolarsResult<LazyFrame> {
+    scan_sas_polars_with(path, SasPolarsOptions::default())
+}
+
+/// Lazily scan a sas7bdat file as `scan_sas_polars` does, applying the temporal policy of
+/// `polars_options` to dates that cannot be decoded.
+pub fn scan_sas_polars_with<P: AsRef<Path>>(
+    path: P,
+    polars_options: SasPolarsOptions,
+) -> PolarsResult<LazyFrame> {
+    let scan = Sas7bdatScan::new(path, polars_options)?;
+    let args = ScanArgsAnonymous {
+        schema: Some(scan.schema.clone()),
+        name: "SAS7BDAT SCAN",
+        ..ScanArgsAnonymous::default()
+    };
+    LazyFrame::anonymous_scan(Arc::new(scan), args)
+}
+
+/// Polars scan source over a sas7bdat file, used by `scan_sas_polars`.
+#[derive(Debug, Clone)]
+pub struct Sas7bdatScan {
+    path: PathBuf,
+    schema: SchemaRef,
+    polars_options: SasPolarsOptions,
+}
+
+impl Sas7bdatScan {
+    /// Read the metadata of the file at `path` to build the schema of the scan.
+    pub fn new<P: AsRef<Path>>(path: P, polars_options: SasPolarsOptions) -> PolarsResult<Self> {
+        let reader = Sas7bdatReader::open(&path).map_err(to_polars_error)?;
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            schema: Arc::new(polars_schema(reader.variables(), polars_options)),
+            polars_options,
+        })
+    }
+}
+
+impl AnonymousScan for Sas7bdatScan {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn scan(&self, scan_opts: AnonymousScanArgs) -> PolarsResult<DataFrame> {
+        let columns = scan_opts
+            .with_columns
+            .map(|columns| columns.iter().map(|column| column.to_string()).collect());
+        let options = SasReadOptions::new(columns, 0, scan_opts.n_rows.map(|n| n as u64));
+        read_sas_polars_with(&self.path, &options, self.polars_options)
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<SchemaRef> {
+        Ok(self.schema.clone())
+    }
+
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+
+    fn allows_slice_pushdown(&self) -> bool {
+        true
+    }
+}
+
+/// Build the Polars schema matching the given variables, read with `polars_options`.
+pub fn polars_schema(variables: &[ReadStatVariable], polars_options: SasPolarsOptions) -> Schema {
+    variables
+        .iter()
+        .map(|variable| {
+            Field::new(
+                variable.name.as_str().into(),
+                polars_dtype(variable, polars_options),
+            )
+        })
+        .collect()
+}
+
+/// Return the temporal kind a variable is read as: None for variables that are not temporal,
+/// and for every variable under `SasTemporalPolicy::Raw`, which keeps the stored numbers.
+fn column_kind(
+    variable: &ReadStatVariable,
+    polars_options: SasPolarsOptions,
+) -> Option<SasTemporalKind> {
+    match polars_options.temporal_policy {
+        SasTemporalPolicy::Raw => None,
+        _ => variable.temporal_kind(),
+    }
+}
+
+fn polars_dtype(variable: &ReadStatVariable, polars_options: SasPolarsOptions) -> DataType {
+    match variable.type_ {
+        ReadStatType::String | ReadStatType::StringRef => DataType::String,
+        _ => match column_kind(variable, polars_options) {
+            Some(SasTemporalKind::Date) => DataType::Date,
+            Some(SasTemporalKind::DateTime) => DataType::Datetime(TimeUnit::Microseconds, None),
+            Some(SasTemporalKind::Time) => DataType::Time,
+            None => DataType::Float64,
+        },
+    }
+}
+
+/// Values of one column, accumulated row by row. Temporal values are kept in the physical unit
+/// of their Polars type: days since 1970-01-01 for dates, microseconds since
+/// 1970-01-01T00:00:00 for datetimes and nanoseconds since midnight for times.
+enum ColumnBuffer {
+    Float64(Vec<Option<f64>>),
+    String(Vec<Option<String>>),
+    Temporal(SasTemporalKind, SasTemporalPolicy, Vec<Option<i64>>),
+}
+
+impl ColumnBuffer {
+    fn new(variable: &ReadStatVariable, polars_options: SasPolarsOptions) -> Self {
+        match (
+            polars_dtype(variable, polars_options),
+            column_kind(variable, polars_options),
+        ) {
+            (DataType::String, _) => ColumnBuffer::String(Vec::new()),
+            (_, Some(kind)) => {
+                ColumnBuffer::Temporal(kind, polars_options.temporal_policy, Vec::new())
+            }
+            _ => ColumnBuffer::Float64(Vec::new()),
+        }
+    }
+
+    fn push(&mut self, value: ReadStatValueType) -> Result<(), ReadStatError> {
+        match (self, value) {
+            (ColumnBuffer::Float64(values), ReadStatValueType::Double(value)) => {
+                values.push(if value.is_nan() { None } else { Some(value) })
+            }
+            (ColumnBuffer::String(values), ReadStatValueType::String(value)) => {
+                values.push(Some(value))
+            }
+            (ColumnBuffer::Temporal(kind, policy, values), ReadStatValueType::Double(value)) => {
+                let decoded = decode_temporal_with(value, *kind, *policy)?;
+                values.push(decoded.and_then(physical_value))
+            }
+            (ColumnBuffer::Float64(values), _) => values.push(None),
+            (ColumnBuffer::String(values), _) => values.push(None),
+            (ColumnBuffer::Temporal(_, _, values), _) => values.push(None),
+        }
+        Ok(())
+    }
+
+    fn into_column(self, name: &str) -> PolarsResult<Column> {
+        let column = match self {
+            ColumnBuffer::Float64(values) => Column::new(name.into(), values),
+            ColumnBuffer::String(values) => Column::new(name.into(), values),
+            ColumnBuffer::Temporal(SasTemporalKind::Date, _, values) => {
+                let days: Vec<Option<i32>> = values
+                    .into_iter()
+                    .map(|days| days.map(|days| days as i32))
+                    .collect();
+                Column::new(name.into(), days).cast(&DataType::Date)?
+            }
+            ColumnBuffer::Temporal(SasTemporalKind::DateTime, _, values) => {
+                Column::new(name.into(), values)
+                    .cast(&DataType::Datetime(TimeUnit::Microseconds, None))?
+            }
+            ColumnBuffer::Temporal(SasTemporalKind::Time, _, values) => {
+                Column::new(name.into(), values).cast(&DataType::Time)?
+            }
+        };
+        Ok(column)
+    }
+}
+
+/// Return a decoded value in the physical unit of its Polars type, as `ColumnBuffer` keeps it.
+fn physical_value(value: SasTemporalValue) -> Option<i64> {
+    let unix_epoch = NaiveDateTime::UNIX_EPOCH;
+    match value {
+        SasTemporalValue::Date(date) => Some((date - unix_epoch.date()).num_days()),
+        SasTemporalValue::DateTime(datetime) => (datetime - unix_epoch).num_microseconds(),
+        SasTemporalValue::Time(time) => Some(
+            i64::from(time.num_seconds_from_midnight()) * 1_000_000_000
+                + i64::from(time.nanosecond()),
+        ),
+        // Temporal columns are never decoded under `SasTemporalPolicy::Raw`
+        SasTemporalValue::Raw(_) => None,
+    }
+}
+
+fn to_polars_error(err: SasError) -> PolarsError {
+    PolarsError::ComputeError(err.to_string().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn, SasTempFile};
+
+    fn fixture_path(name: &str) -> SasTempFile {
+        SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 8),
+                SasFixtureColumn::numeric("SCORE", "", 0),
+            ])
+            .rows(
+                (0..20)
+                    .map(|i| {
+                        vec![
+                            ReadStatValueType::Double(i as f64),
+                            ReadStatValueType::String(format!("n{}", i)),
+                            ReadStatValueType::Double(if i % 2 == 0 { f64::NAN } else { 0.5 }),
+                        ]
+                    })
+                    .collect(),
+            )
+            .build()
+            .write_temp(name)
+    }
+
+    #[test]
+    fn test_read_sas_polars() {
+        let path = fixture_path("polars_read");
+        let df = read_sas_polars(&path, &SasReadOptions::default()).unwrap();
+
+        assert_eq!(df.shape(), (20, 3));
+        assert_eq!(df.column("ID").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("NAME").unwrap().dtype(), &DataType::String);
+        assert_eq!(df.column("SCORE").unwrap().null_count(), 10);
+        assert_eq!(df.column("NAME").unwrap().str().unwrap().get(3), Some("n3"));
+    }
+
+    #[test]
+    fn test_read_sas_polars_with_options() {
+        let path = fixture_path("polars_options");
+        let options = SasReadOptions::new(Some(vec!["NAME".to_string()]), 5, Some(2));
+        let df = read_sas_polars(&path, &options).unwrap();
+
+        assert_eq!(df.shape(), (2, 1));
+        assert_eq!(df.column("NAME").unwrap().str().unwrap().get(0), Some("n5"));
+    }
+
+    #[test]
+    fn test_scan_sas_polars_pushdown() {
+        let path = fixture_path("polars_scan");
+        let df = scan_sas_polars(&path)
+            .unwrap()
+            .select([col("ID")])
+            .limit(4)
+            .collect()
+            .unwrap();
+
+        assert_eq!(df.shape(), (4, 1));
+        assert_eq!(df.column("ID").unwrap().f64().unwrap().get(3), Some(3.0));
+    }
+
+    #[test]
+    fn test_read_sas_polars_temporal_columns() {
+        let path = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("VISIT", "DATE", 9),
+                SasFixtureColumn::numeric("AT", "DATETIME", 20),
+                SasFixtureColumn::numeric("START", "TIME", 8),
+            ])
+            .rows(vec![
+                vec![
+                    ReadStatValueType::Double(23_406.0),
+                    ReadStatValueType::Double(23_406.0 * 86_400.0 + 49_500.5),
+                    ReadStatValueType::Double(49_500.0),
+                ],
+                vec![
+                    ReadStatValueType::Double(f64::NAN),
+                    ReadStatValueType::Double(f64::NAN),
+                    ReadStatValueType::Double(f64::NAN),
+                ],
+            ])
+            .build()
+            .write_temp("polars_temporal");
+        let df = read_sas_polars(&path, &SasReadOptions::default()).unwrap();
+
+        assert_eq!(df.column("VISIT").unwrap().dtype(), &DataType::Date);
+        assert_eq!(
+            df.column("AT").unwrap().dtype(),
+            &DataType::Datetime(TimeUnit::Microseconds, None)
+        );
+        assert_eq!(df.column("START").unwrap().dtype(), &DataType::Time);
+        assert_eq!(
+            polars_schema(
+                &Sas7bdatReader::open(&path).unwrap().variables()[..1],
+                SasPolarsOptions::default()
+            )
+            .get("VISIT"),
+            Some(&DataType::Date)
+        );
+
+        // 2024-01-31 is 19,753 days after 1970-01-01
+        let physical = |name: &str, row| {
+            df.column(name)
+                .unwrap()
+                .to_physical_repr()
+                .cast(&DataType::Int64)
+                .unwrap()
+                .i64()
+                .unwrap()
+                .get(row)
+        };
+        assert_eq!(physical("VISIT", 0), Some(19_753));
+        assert_eq!(
+            physical("AT", 0),
+            Some((19_753 * 86_400 + 49_500) * 1_000_000 + 500_000)
+        );
+        assert_eq!(physical("START", 0), Some(49_500 * 1_000_000_000));
+        assert_eq!(physical("VISIT", 1), None);
+        assert_eq!(df.column("AT").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn test_read_sas_polars_temporal_policy() {
+        let path = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("VISIT", "DATE", 9),
+                SasFixtureColumn::numeric("START", "TIME", 8),
+            ])
+            .rows(vec![
+                vec![
+                    ReadStatValueType::Double(0.0),
+                    ReadStatValueType::Double(0.0),
+                ],
+                vec![
+                    ReadStatValueType::Double(1e13),
+                    ReadStatValueType::Double(90_000.0),
+                ],
+            ])
+            .build()
+            .write_temp("polars_temporal_policy");
+        let read = |policy| {
+            read_sas_polars_with(
+                &path,
+                &SasReadOptions::default(),
+                SasPolarsOptions::new(policy),
+            )
+        };
+
+        let message = read(SasTemporalPolicy::Error).unwrap_err().to_string();
+        assert!(message.contains("VISIT"), "{}", message);
+        assert!(message.contains("row 1"), "{}", message);
+
+        let df = read(SasTemporalPolicy::Missing).unwrap();
+        assert_eq!(df.column("VISIT").unwrap().dtype(), &DataType::Date);
+        assert_eq!(df.column("VISIT").unwrap().null_count(), 1);
+        assert_eq!(df.column("START").unwrap().null_count(), 1);
+
+        let df = read(SasTemporalPolicy::Clamp).unwrap();
+        assert_eq!(df.column("VISIT").unwrap().null_count(), 0);
+        assert_eq!(
+            df.column("START")
+                .unwrap()
+                .to_physical_repr()
+                .i64()
+                .unwrap()
+                .get(1),
+            Some(86_399_999_999_000)
+        );
+
+        let df = read(SasTemporalPolicy::Raw).unwrap();
+        assert_eq!(df.column("VISIT").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(
+            df.column("VISIT").unwrap().f64().unwrap().get(1),
+            Some(1e13)
+        );
+        assert_eq!(
+            df.column("START").unwrap().f64().unwrap().get(1),
+            Some(90_000.0)
+        );
+
+        let df = scan_sas_polars_with(&path, SasPolarsOptions::new(SasTemporalPolicy::Missing))
+            .unwrap()
+            .select([col("START")])
+            .collect()
+            .unwrap();
+        assert_eq!(df.column("START").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn test_read_sas_polars_unknown_column() {
+        let path = fixture_path("polars_unknown");
+        let options = SasReadOptions::new(Some(vec!["NOPE".to_string()]), 0, None);
+        assert!(read_sas_polars(&path, &options).is_err());
+    }
+}
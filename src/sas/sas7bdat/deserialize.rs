@@ -0,0 +1,722 @@
+use crate::export::csv::text_value;
+use crate::sas::sas7bdat::reader::{Sas7bdatReader, Sas7bdatRows};
+use crate::sas::temporal::format_iso8601_with;
+use crate::types::{
+    ReadStatValue, ReadStatValueType, ReadStatVariable, SasDeserializeOptions, SasError,
+    SasFieldNames, SasReadOptions, SasTemporalKind, SasTemporalPolicy, SasTemporalText,
+};
+use serde::de::{
+    self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+};
+use std::marker::PhantomData;
+
+impl Sas7bdatReader {
+    /// Iterate over every row deserialized into a `T`, matching the fields of `T` to variable
+    /// names case-insensitively:
+    ///
+    /// ```no_run
+    /// use chrono::NaiveDate;
+    /// use rust_sas::sas::sas7bdat::Sas7bdatReader;
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize)]
+    /// struct Claim {
+    ///     claim_id: String,
+    ///     amount: Option<f64>,
+    ///     filed: NaiveDate,
+    /// }
+    ///
+    /// let mut reader = Sas7bdatReader::open("claims.sas7bdat").unwrap();
+    /// for claim in reader.deserialize::<Claim>() {
+    ///     let claim = claim.unwrap();
+    ///     println!("{} {:?} {}", claim.claim_id, claim.amount, claim.filed);
+    /// }
+    /// ```
+    ///
+    /// Missing values become `None` in `Option` fields, and NaN or an empty string elsewhere.
+    /// Numerics with a date, datetime or time format deserialize from their ISO 8601 text, so
+    /// they can be read into `chrono::NaiveDate`, `NaiveDateTime` and `NaiveTime` fields, or
+    /// from the number of days or seconds they hold into numeric fields. A date that cannot be
+    /// decoded is an error; use `deserialize_with` to choose a different `SasTemporalPolicy`.
+    pub fn deserialize<T: DeserializeOwned>(&mut self) -> Sas7bdatRecords<'_, T> {
+        self.deserialize_with(&SasReadOptions::default(), SasDeserializeOptions::default())
+            .expect("selecting every variable cannot fail")
+    }
+
+    /// Iterate over the rows selected by `options` deserialized into a `T`, matching the fields
+    /// of `T` to variable names or labels, compared case-insensitively, as `record_options`
+    /// asks.
+    pub fn deserialize_with<T: DeserializeOwned>(
+        &mut self,
+        options: &SasReadOptions,
+        record_options: SasDeserializeOptions,
+    ) -> Result<Sas7bdatRecords<'_, T>, SasError> {
+        let selection = self.resolve_columns(&options.columns)?;
+        let variables: Vec<&ReadStatVariable> = selection
+            .iter()
+            .map(|&index| &self.variables()[index])
+            .collect();
+        let fields = SasRecordFields::new(&variables, record_options);
+        Ok(Sas7bdatRecords {
+            rows: self.rows(options)?,
+            fields,
+            next_row: options.row_offset,
+            _record: PhantomData,
+        })
+    }
+}
+
+/// Deserialize one decoded row into a `T`, as `Sas7bdatReader::deserialize` does.
+///
+/// # Arguments
+/// * `row` - The values of the row, in the order of `variables`.
+/// * `variables` - The variables the values belong to.
+/// * `options` - What the fields of `T` are matched against, and what happens to dates that
+///   cannot be decoded.
+pub fn from_row<T: DeserializeOwned>(
+    row: &[ReadStatValue],
+    variables: &[ReadStatVariable],
+    options: SasDeserializeOptions,
+) -> Result<T, SasError> {
+    let variables: Vec<&ReadStatVariable> = variables.iter().collect();
+    let fields = SasRecordFields::new(&variables, options);
+    T::deserialize(RowDeserializer {
+        values: row,
+        fields: &fields,
+    })
+}
+
+/// What the deserializer needs to know about the selected variables: the keys fields are
+/// matched against, the names errors are reported with, the temporal kind of each value and
+/// what happens to dates that cannot be decoded.
+#[derive(Debug)]
+struct SasRecordFields {
+    keys: Vec<String>,
+    names: Vec<String>,
+    kinds: Vec<Option<SasTemporalKind>>,
+    temporal_policy: SasTemporalPolicy,
+}
+
+impl SasRecordFields {
+    fn new(variables: &[&ReadStatVariable], options: SasDeserializeOptions) -> Self {
+        Self {
+            keys: variables
+                .iter()
+                .map(|variable| match options.field_names {
+                    SasFieldNames::Label if !variable.label.is_empty() => variable.label.clone(),
+                    _ => variable.name.clone(),
+                })
+                .collect(),
+            names: variables
+                .iter()
+                .map(|variable| variable.name.clone())
+                .collect(),
+            kinds: variables
+                .iter()
+                .map(|variable| variable.temporal_kind())
+                .collect(),
+            temporal_policy: options.temporal_policy,
+        }
+    }
+
+    fn key_index(&self, field: &str) -> Option<usize> {
+        self.keys
+            .iter()
+            .position(|key| key.eq_ignore_ascii_case(field))
+    }
+}
+
+/// Iterator over rows deserialized into records, created by `Sas7bdatReader::deserialize`. A
+/// row that does not fit the record is returned as an error carrying its row number, and the
+/// rows after it are still read.
+#[derive(Debug)]
+pub struct Sas7bdatRecords<'a, T> {
+    rows: Sas7bdatRows<'a>,
+    fields: SasRecordFields,
+    next_row: u64,
+    _record: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Iterator for Sas7bdatRecords<'_, T> {
+    type Item = Result<T, SasError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let row = match self.rows.next()? {
+            Ok(row) => row,
+            Err(err) => return Some(Err(err)),
+        };
+        let index = self.next_row;
+        self.next_row += 1;
+        Some(
+            T::deserialize(RowDeserializer {
+                values: &row,
+                fields: &self.fields,
+            })
+            .map_err(|err| err.with_row(index)),
+        )
+    }
+}
+
+/// Deserializes a row as a map from keys to values, a struct or a sequence of values.
+struct RowDeserializer<'a> {
+    values: &'a [ReadStatValue],
+    fields: &'a SasRecordFields,
+}
+
+impl<'de> de::Deserializer<'de> for RowDeserializer<'_> {
+    type Error = SasError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        let entries = self
+            .fields
+            .keys
+            .iter()
+            .enumerate()
+            .map(|(index, key)| (key.as_str(), index))
+            .collect::<Vec<_>>();
+        visitor.visit_map(RowMap {
+            entries: entries.into_iter(),
+            pending: None,
+            values: self.values,
+            fields: self.fields,
+        })
+    }
+
+    /// Only the fields with a matching variable are visited, leaving the others to their
+    /// defaults, so `Option` fields without a variable are `None`.
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SasError> {
+        let entries = fields
+            .iter()
+            .filter_map(|&field| Some((field, self.fields.key_index(field)?)))
+            .collect::<Vec<_>>();
+        visitor.visit_map(RowMap {
+            entries: entries.into_iter(),
+            pending: None,
+            values: self.values,
+            fields: self.fields,
+        })
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        visitor.visit_seq(RowSeq {
+            next: 0,
+            values: self.values,
+            fields: self.fields,
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SasError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SasError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SasError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct enum identifier ignored_any
+    }
+}
+
+/// Deserialize the value at `index` of a row, naming its variable in errors.
+fn value_seed<'de, S: DeserializeSeed<'de>>(
+    seed: S,
+    index: usize,
+    values: &[ReadStatValue],
+    fields: &SasRecordFields,
+) -> Result<S::Value, SasError> {
+    seed.deserialize(ValueDeserializer {
+        value: &values[index],
+        kind: fields.kinds[index],
+        policy: fields.temporal_policy,
+    })
+    .map_err(|err| err.with_variable(fields.names[index].as_str()))
+}
+
+struct RowMap<'a> {
+    entries: std::vec::IntoIter<(&'a str, usize)>,
+    pending: Option<usize>,
+    values: &'a [ReadStatValue],
+    fields: &'a SasRecordFields,
+}
+
+impl<'de> MapAccess<'de> for RowMap<'_> {
+    type Error = SasError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, SasError> {
+        match self.entries.next() {
+            Some((key, index)) => {
+                self.pending = Some(index);
+                seed.deserialize(key.into_deserializer()).map(Some)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, SasError> {
+        let index = self
+            .pending
+            .take()
+            .expect("next_value_seed is called after next_key_seed");
+        value_seed(seed, index, self.values, self.fields)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+struct RowSeq<'a> {
+    next: usize,
+    values: &'a [ReadStatValue],
+    fields: &'a SasRecordFields,
+}
+
+impl<'de> SeqAccess<'de> for RowSeq<'_> {
+    type Error = SasError;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>, SasError> {
+        if self.next >= self.values.len() {
+            return Ok(None);
+        }
+        self.next += 1;
+        value_seed(seed, self.next - 1, self.values, self.fields).map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len() - self.next)
+    }
+}
+
+/// Deserializes a single value, converting whole numbers to integers and temporal numbers to
+/// ISO 8601 text as the target type asks for them, applying `policy` to dates that cannot be
+/// decoded.
+struct ValueDeserializer<'a> {
+    value: &'a ReadStatValue,
+    kind: Option<SasTemporalKind>,
+    policy: SasTemporalPolicy,
+}
+
+impl<'de> ValueDeserializer<'_> {
+    /// Return true if the value is missing, or is a date the policy treats as missing.
+    fn is_missing(&self) -> Result<bool, SasError> {
+        match (self.kind, &self.value.value) {
+            (Some(kind), ReadStatValueType::Double(number)) if !self.value.is_missing() => {
+                Ok(format_iso8601_with(*number, kind, self.policy)?.is_none())
+            }
+            _ => Ok(self.value.is_missing()),
+        }
+    }
+
+    fn deserialize_integer<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        match self.value.value {
+            ReadStatValueType::Double(number)
+                if number.fract() == 0.0 && number >= i64::MIN as f64 && number < 0.0 =>
+            {
+                visitor.visit_i64(number as i64)
+            }
+            ReadStatValueType::Double(number)
+                if number.fract() == 0.0 && (0.0..u64::MAX as f64).contains(&number) =>
+            {
+                visitor.visit_u64(number as u64)
+            }
+            _ => de::Deserializer::deserialize_any(self, visitor),
+        }
+    }
+}
+
+impl<'de> de::Deserializer<'de> for ValueDeserializer<'_> {
+    type Error = SasError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        match &self.value.value {
+            ReadStatValueType::String(text) => visitor.visit_str(text),
+            ReadStatValueType::Double(_) if self.value.is_missing() => visitor.visit_none(),
+            ReadStatValueType::Double(number) => match self.kind {
+                Some(kind) => match format_iso8601_with(*number, kind, self.policy)? {
+                    Some(SasTemporalText::Iso8601(text)) => visitor.visit_string(text),
+                    Some(SasTemporalText::Raw(number)) => visitor.visit_f64(number),
+                    None => visitor.visit_none(),
+                },
+                None => visitor.visit_f64(*number),
+            },
+            ReadStatValueType::Float(number) => visitor.visit_f32(*number),
+            ReadStatValueType::Int8(number) => visitor.visit_i8(*number),
+            ReadStatValueType::Int16(number) => visitor.visit_i16(*number),
+            ReadStatValueType::Int32(number) => visitor.visit_i32(*number),
+            ReadStatValueType::Int64(number) => visitor.visit_i64(*number),
+            ReadStatValueType::UInt8(number) => visitor.visit_u8(*number),
+            ReadStatValueType::UInt16(number) => visitor.visit_u16(*number),
+            ReadStatValueType::UInt32(number) => visitor.visit_u32(*number),
+            ReadStatValueType::UInt64(number) => visitor.visit_u64(*number),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        if self.is_missing()? {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        if self.is_missing()? {
+            visitor.visit_unit()
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        match self.value.value {
+            ReadStatValueType::Double(number) if !self.value.is_missing() => {
+                visitor.visit_bool(number != 0.0)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        match self.value.value {
+            ReadStatValueType::Double(number) => visitor.visit_f64(number),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_i8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_i16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_i32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_i64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u8<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u16<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    fn deserialize_u64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_integer(visitor)
+    }
+
+    /// Numerics are rendered as the CSV export writes them: temporal values as ISO 8601,
+    /// missing values as an empty string and other numbers in their shortest form.
+    fn deserialize_str<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        match &self.value.value {
+            ReadStatValueType::String(text) => visitor.visit_str(text),
+            _ => visitor.visit_string(text_value(self.value, self.kind, self.policy)?),
+        }
+    }
+
+    fn deserialize_string<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SasError> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SasError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Character values select unit variants by name.
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SasError> {
+        match &self.value.value {
+            ReadStatValueType::String(text) => {
+                visitor.visit_enum(text.as_str().into_deserializer())
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 bytes byte_buf unit_struct seq tuple tuple_struct map struct ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::{ReadStatError, ReadStatType};
+    use chrono::{NaiveDate, NaiveDateTime};
+    use serde::Deserialize;
+    use std::collections::BTreeMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Claim {
+        claim_id: String,
+        amount: Option<f64>,
+        units: u32,
+        filed: NaiveDate,
+        settled: Option<NaiveDateTime>,
+        region: Option<String>,
+    }
+
+    fn reader() -> Sas7bdatReader {
+        let fixture = SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::character("CLAIM_ID", 8),
+                SasFixtureColumn::numeric("AMOUNT", "BEST", 12),
+                SasFixtureColumn::numeric("UNITS", "BEST", 8),
+                SasFixtureColumn::numeric("FILED", "DATE", 9),
+                SasFixtureColumn::numeric("SETTLED", "DATETIME", 20),
+            ])
+            .rows(vec![
+                vec![
+                    ReadStatValueType::String("C1".to_string()),
+                    ReadStatValueType::Double(125.5),
+                    ReadStatValueType::Double(3.0),
+                    ReadStatValueType::Double(23406.0),
+                    ReadStatValueType::Double(2022327900.0),
+                ],
+                vec![
+                    ReadStatValueType::String("C2".to_string()),
+                    ReadStatValueType::Double(f64::NAN),
+                    ReadStatValueType::Double(1.5),
+                    ReadStatValueType::Double(0.0),
+                    ReadStatValueType::Double(f64::NAN),
+                ],
+            ])
+            .build();
+        Sas7bdatReader::from_bytes(fixture.to_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_deserialize_struct() {
+        let mut reader = reader();
+        let mut claims = reader.deserialize::<Claim>();
+
+        assert_eq!(
+            claims.next().unwrap().unwrap(),
+            Claim {
+                claim_id: "C1".to_string(),
+                amount: Some(125.5),
+                units: 3,
+                filed: NaiveDate::from_ymd_opt(2024, 1, 31).unwrap(),
+                settled: NaiveDate::from_ymd_opt(2024, 1, 31)
+                    .unwrap()
+                    .and_hms_opt(13, 45, 0),
+                region: None,
+            }
+        );
+
+        let err = claims.next().unwrap().unwrap_err();
+        assert_eq!(err.kind, ReadStatError::ValueTypeMismatch);
+        assert_eq!(err.row, Some(1));
+        assert_eq!(err.variable.as_deref(), Some("UNITS"));
+        assert!(claims.next().is_none());
+    }
+
+    #[test]
+    fn test_deserialize_missing_values() {
+        #[derive(Debug, Deserialize)]
+        struct Amounts {
+            amount: Option<f64>,
+            settled: Option<f64>,
+            filed: f64,
+        }
+
+        let mut reader = reader();
+        let options = SasReadOptions::builder().row_offset(1).build().unwrap();
+        let amounts: Vec<Amounts> = reader
+            .deserialize_with(&options, SasDeserializeOptions::default())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(amounts.len(), 1);
+        assert_eq!(amounts[0].amount, None);
+        assert_eq!(amounts[0].settled, None);
+        assert_eq!(amounts[0].filed, 0.0);
+    }
+
+    #[test]
+    fn test_deserialize_missing_field() {
+        #[derive(Debug, Deserialize)]
+        #[allow(dead_code)]
+        struct Unknown {
+            policy: String,
+        }
+
+        let mut reader = reader();
+        let err = reader.deserialize::<Unknown>().next().unwrap().unwrap_err();
+        assert_eq!(err.kind, ReadStatError::UnknownColumn);
+        assert_eq!(err.message(), Some("missing field `policy`"));
+    }
+
+    #[test]
+    fn test_deserialize_map_and_tuple() {
+        let mut reader = reader();
+        let row: BTreeMap<String, serde_json::Value> =
+            reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(row["CLAIM_ID"], "C1");
+        assert_eq!(row["FILED"], "2024-01-31");
+        assert_eq!(row["AMOUNT"], 125.5);
+
+        let options = SasReadOptions::builder()
+            .columns(Some(vec!["units".to_string(), "claim_id".to_string()]))
+            .build()
+            .unwrap();
+        let row: (f64, String) = reader
+            .deserialize_with(&options, SasDeserializeOptions::default())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(row, (3.0, "C1".to_string()));
+    }
+
+    #[test]
+    fn test_from_row_by_label() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Labelled {
+            #[serde(rename = "Claim amount")]
+            amount: f64,
+            code: String,
+        }
+
+        let variables = vec![
+            ReadStatVariable::builder()
+                .name("AMT".to_string())
+                .label("Claim amount".to_string())
+                .build()
+                .unwrap(),
+            ReadStatVariable::builder()
+                .type_(ReadStatType::String)
+                .name("CODE".to_string())
+                .build()
+                .unwrap(),
+        ];
+        let row = vec![
+            ReadStatValue::new(ReadStatType::Double, None, ReadStatValueType::Double(10.0)),
+            ReadStatValue::new(
+                ReadStatType::String,
+                None,
+                ReadStatValueType::String("X".to_string()),
+            ),
+        ];
+        let record: Labelled = from_row(
+            &row,
+            &variables,
+            SasDeserializeOptions::new(SasFieldNames::Label, SasTemporalPolicy::Error),
+        )
+        .unwrap();
+        assert_eq!(
+            record,
+            Labelled {
+                amount: 10.0,
+                code: "X".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_from_row_temporal_policy() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Visit {
+            visit: Option<NaiveDate>,
+        }
+
+        let variables = vec![ReadStatVariable::builder()
+            .name("VISIT".to_string())
+            .format("DATE9.".to_string())
+            .build()
+            .unwrap()];
+        let row = vec![ReadStatValue::new(
+            ReadStatType::Double,
+            None,
+            ReadStatValueType::Double(1e13),
+        )];
+        let options = |policy| SasDeserializeOptions::new(SasFieldNames::Name, policy);
+
+        let err =
+            from_row::<Visit>(&row, &variables, options(SasTemporalPolicy::Error)).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::BadTimestampValue);
+        assert_eq!(err.variable.as_deref(), Some("VISIT"));
+        assert_eq!(
+            from_row::<Visit>(&row, &variables, options(SasTemporalPolicy::Missing)).unwrap(),
+            Visit { visit: None }
+        );
+        assert_eq!(
+            from_row::<Visit>(&row, &variables, options(SasTemporalPolicy::Clamp)).unwrap(),
+            Visit {
+                visit: Some(NaiveDate::MAX)
+            }
+        );
+
+        let record: BTreeMap<String, serde_json::Value> =
+            from_row(&row, &variables, options(SasTemporalPolicy::Raw)).unwrap();
+        assert_eq!(record["VISIT"], 1e13);
+        let record: BTreeMap<String, serde_json::Value> =
+            from_row(&row, &variables, options(SasTemporalPolicy::Missing)).unwrap();
+        assert_eq!(record["VISIT"], serde_json::Value::Null);
+    }
+}
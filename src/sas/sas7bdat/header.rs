@@ -0,0 +1,231 @@
+use crate::sas::cursor::SasCursor;
+use crate::types::{
+    build_charset_lookup, ReadStatError, ReadStatVendor, SasAlignmentOffset, SasEndian,
+    SasFileMagicNumber, SasHeaderInfo, SasPageHeaderSize, SasSubheaderPointerSize,
+    SAS_EPOCH_UNIX_SECONDS,
+};
+
+/// Size of `sas_header_start_t`, the fixed-layout part of the header that precedes the
+/// alignment padding.
+pub const SAS_HEADER_START_SIZE: usize = 164;
+
+/// Size of `sas_header_end_t`, which holds the SAS release and host strings.
+pub const SAS_HEADER_END_SIZE: usize = 120;
+
+/// Smallest and largest header and page sizes readstat accepts.
+const MIN_HEADER_OR_PAGE_SIZE: u64 = 1 << 10;
+const MAX_HEADER_OR_PAGE_SIZE: u64 = 1 << 24;
+
+/// Parse the header at the start of a sas7bdat (or sas7bcat) file.
+///
+/// # Arguments
+/// * `data` - The first bytes of the file. 1024 bytes, the smallest legal header, is always
+///   enough.
+///
+/// # Original C function
+/// `sas_read_header` in readstat's `sas.c`.
+pub fn parse_header(data: &[u8]) -> Result<SasHeaderInfo, ReadStatError> {
+    if data.len() < SAS_HEADER_START_SIZE {
+        return Err(ReadStatError::Read);
+    }
+
+    let magic = &data[0..32];
+    if magic != SasFileMagicNumber::Sas7bdat.get_array()
+        && magic != SasFileMagicNumber::Sas7bcat.get_array()
+    {
+        return Err(ReadStatError::Parse);
+    }
+
+    let is_64bit = data[32] == SasAlignmentOffset::Offset4 as u8;
+    let pad1: usize = if data[35] == SasAlignmentOffset::Offset4 as u8 {
+        4
+    } else {
+        0
+    };
+
+    let endianness = match data[37] {
+        0x00 => SasEndian::Big,
+        0x01 => SasEndian::Little,
+        _ => return Err(ReadStatError::Parse),
+    };
+
+    let encoding_code = data[70];
+    let encoding = build_charset_lookup()
+        .remove(&(encoding_code as u32))
+        .ok_or(ReadStatError::UnsupportedCharset)?;
+
+    let table_name = trim_header_string(&data[92..124]);
+
+    let offset = SAS_HEADER_START_SIZE + pad1;
+    let mut cursor = SasCursor::at(data, offset, endianness.clone())?;
+    let creation_time = cursor.read_f64()?;
+    let modification_time = cursor.read_f64()?;
+    let creation_time_diff = cursor.read_f64()?;
+    let modification_time_diff = cursor.read_f64()?;
+
+    let header_size = cursor.read_u32()? as u64;
+    let page_size = cursor.read_u32()? as u64;
+
+    if header_size < MIN_HEADER_OR_PAGE_SIZE || page_size < MIN_HEADER_OR_PAGE_SIZE {
+        return Err(ReadStatError::Parse);
+    }
+    if header_size > MAX_HEADER_OR_PAGE_SIZE || page_size > MAX_HEADER_OR_PAGE_SIZE {
+        return Err(ReadStatError::Parse);
+    }
+
+    let page_count = if is_64bit {
+        cursor.read_u64()?
+    } else {
+        cursor.read_u32()? as u64
+    };
+    cursor.skip(8)?;
+
+    let header_end = cursor.read_bytes(SAS_HEADER_END_SIZE)?;
+    let (major_version, minor_version, revision) = parse_release(&header_end[0..8])?;
+    let vendor =
+        if (major_version == 8 || major_version == 9) && minor_version == 0 && revision == 0 {
+            // A bit of a hack, but most SAS installations are running a minor update
+            ReadStatVendor::StatTransfer
+        } else {
+            ReadStatVendor::Sas
+        };
+
+    let (page_header_size, subheader_pointer_size) = if is_64bit {
+        (SasPageHeaderSize::Bit64, SasSubheaderPointerSize::Bit64)
+    } else {
+        (SasPageHeaderSize::Bit32, SasSubheaderPointerSize::Bit32)
+    };
+
+    Ok(SasHeaderInfo::new(
+        endianness,
+        is_64bit,
+        vendor,
+        major_version,
+        minor_version,
+        revision,
+        pad1 as u64,
+        page_size,
+        page_header_size as u64,
+        subheader_pointer_size as u64,
+        page_count,
+        header_size,
+        sas_convert_time(creation_time, creation_time_diff),
+        sas_convert_time(modification_time, modification_time_diff),
+        table_name,
+        encoding_code,
+        encoding,
+        trim_header_string(&header_end[0..8]),
+        trim_header_string(&header_end[8..24]),
+        trim_header_string(&header_end[56..72]),
+    ))
+}
+
+/// Parse a SAS release string such as `9.0401M2` into its major version, minor version and
+/// revision, the way readstat's `sscanf(release, "%c.%04d%c%1d", ...)` does.
+fn parse_release(release: &[u8]) -> Result<(u8, u16, u8), ReadStatError> {
+    if release.len() < 8 || release[1] != b'.' || !release[7].is_ascii_digit() {
+        return Err(ReadStatError::Parse);
+    }
+
+    let major_version = match release[0] {
+        major @ b'1'..=b'9' => major - b'0',
+        // It's unclear what version "V" refers to, but readstat treats it as version 9
+        b'V' => 9,
+        _ => return Err(ReadStatError::Parse),
+    };
+
+    let minor_version = std::str::from_utf8(&release[2..6])
+        .ok()
+        .and_then(|minor| minor.parse::<u16>().ok())
+        .ok_or(ReadStatError::Parse)?;
+
+    Ok((major_version, minor_version, release[7] - b'0'))
+}
+
+/// Convert a timestamp stored in the header, in seconds since the SAS epoch, to seconds since
+/// the Unix epoch. Values that cannot be represented are returned as zero, as readstat does.
+fn sas_convert_time(time: f64, time_diff: f64) -> i64 {
+    let time = time - time_diff + SAS_EPOCH_UNIX_SECONDS as f64;
+    if time.is_nan() || !(-1e14..=1e14).contains(&time) {
+        return 0;
+    }
+    time as i64
+}
+
+/// Decode a fixed-width, space or NUL padded string from the header.
+fn trim_header_string(data: &[u8]) -> String {
+    let end = data
+        .iter()
+        .rposition(|&byte| byte != b' ' && byte != 0)
+        .map_or(0, |position| position + 1);
+    String::from_utf8_lossy(&data[..end]).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::SasFixture;
+
+    #[test]
+    fn test_parse_header_32bit_little_endian() {
+        let file = SasFixture::builder().build().to_bytes();
+        let header = parse_header(&file).unwrap();
+        assert_eq!(header.endianness, SasEndian::Little);
+        assert!(!header.is_64bit);
+        assert_eq!(header.header_size, 1024);
+        assert_eq!(header.page_size, 4096);
+        assert_eq!(header.page_header_size, 24);
+        assert_eq!(header.subheader_pointer_size, 12);
+        assert_eq!(header.table_name, "FIXTURE");
+        assert_eq!(header.encoding, "UTF-8");
+        assert_eq!(header.major_version, 9);
+        assert_eq!(header.minor_version, 401);
+        assert_eq!(header.revision, 2);
+        assert_eq!(header.vendor, ReadStatVendor::Sas);
+        assert_eq!(header.release, "9.0401M2");
+        assert_eq!(header.creation_time, 0);
+    }
+
+    #[test]
+    fn test_parse_header_64bit_big_endian() {
+        let file = SasFixture::builder()
+            .is_64bit(true)
+            .endianness(SasEndian::Big)
+            .build()
+            .to_bytes();
+        let header = parse_header(&file).unwrap();
+        assert_eq!(header.endianness, SasEndian::Big);
+        assert!(header.is_64bit);
+        assert_eq!(header.pad1, 4);
+        assert_eq!(header.header_size, 8192);
+        assert_eq!(header.page_header_size, 40);
+        assert_eq!(header.subheader_pointer_size, 24);
+        assert_eq!(header.table_name, "FIXTURE");
+    }
+
+    #[test]
+    fn test_parse_header_bad_magic() {
+        let mut file = SasFixture::builder().build().to_bytes();
+        file[12] = 0;
+        assert_eq!(parse_header(&file), Err(ReadStatError::Parse));
+    }
+
+    #[test]
+    fn test_parse_header_too_short() {
+        assert_eq!(parse_header(&[0; 100]), Err(ReadStatError::Read));
+    }
+
+    #[test]
+    fn test_parse_release() {
+        assert_eq!(parse_release(b"9.0401M2"), Ok((9, 401, 2)));
+        assert_eq!(parse_release(b"V.0000M0"), Ok((9, 0, 0)));
+        assert_eq!(parse_release(b"garbage!"), Err(ReadStatError::Parse));
+    }
+
+    #[test]
+    fn test_sas_convert_time() {
+        assert_eq!(sas_convert_time(0.0, 0.0), SAS_EPOCH_UNIX_SECONDS);
+        assert_eq!(sas_convert_time(f64::NAN, 0.0), 0);
+        assert_eq!(sas_convert_time(1e20, 0.0), 0);
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(feature = "async")]
+pub mod async_reader;
+pub mod borrowed;
+pub mod columnar;
+pub mod compression;
+pub mod context;
+#[cfg(feature = "polars")]
+pub mod dataframe;
+#[cfg(feature = "serde")]
+pub mod deserialize;
+pub mod header;
+pub mod page;
+#[cfg(feature = "parallel")]
+pub mod parallel;
+pub mod reader;
+pub mod row;
+pub mod source;
+pub mod subheader;
+pub mod text;
+pub mod validate;
+
+#[cfg(test)]
+pub(crate) mod test_fixtures;
+
+#[cfg(feature = "async")]
+pub use async_reader::AsyncSas7bdatReader;
+pub use borrowed::Sas7bdatBorrowedRows;
+pub use columnar::{
+    Sas7bdatColumnBatches, SasBitmap, SasCharacterColumn, SasColumnBatch, SasColumnData,
+    SasNumericColumn,
+};
+pub use context::Sas7bdatContext;
+#[cfg(feature = "polars")]
+pub use dataframe::{
+    read_sas_polars, read_sas_polars_with, scan_sas_polars, scan_sas_polars_with, Sas7bdatScan,
+};
+#[cfg(feature = "serde")]
+pub use deserialize::{from_row, Sas7bdatRecords};
+#[cfg(feature = "parallel")]
+pub use parallel::Sas7bdatParRows;
+pub use reader::{Sas7bdatReader, Sas7bdatRows};
+pub use row::{SasRowRef, SasValueRef};
+pub use source::Sas7bdatSource;
+pub use validate::{
+    validate, validate_source, SasValidationIssue, SasValidationIssueKind, SasValidationReport,
+};
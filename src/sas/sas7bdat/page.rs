@@ -0,0 +1,299 @@
+use crate::sas::sas7bdat::compression::{rdc_decompress, rle_decompress};
+use crate::sas::sas7bdat::context::Sas7bdatContext;
+use crate::sas::sas7bdat::subheader::{
+    classify_subheader, read_subheader_pointers, subheader_data, SasSubheaderKind,
+};
+use crate::types::{ReadStatCompression, ReadStatError, ReadStatVendor, SasError, SasPageType};
+use std::borrow::Cow;
+
+// #define SAS_PAGE_TYPE_MASK   0x0F00
+pub const SAS_PAGE_TYPE_MASK: u16 = 0x0F00;
+
+/// The type, block count and subheader count stored in the header of every page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasPageHeader {
+    pub page_type: u16,
+    pub block_count: u16,
+    pub subheader_count: u16,
+}
+
+impl SasPageHeader {
+    /// Return true for pages holding only rows.
+    pub fn is_data(&self) -> bool {
+        self.page_type & SAS_PAGE_TYPE_MASK == SasPageType::Data as u16
+    }
+
+    /// Return true for pages holding subheaders followed by rows.
+    pub fn is_mix(&self) -> bool {
+        self.page_type & SAS_PAGE_TYPE_MASK == SasPageType::Mix as u16
+    }
+
+    /// Return true for pages holding only subheaders, some of which may be compressed rows.
+    pub fn is_meta(&self) -> bool {
+        self.page_type & SAS_PAGE_TYPE_MASK == SasPageType::Meta as u16 && !self.is_comp()
+    }
+
+    /// Return true for the pages SAS uses for internal bookkeeping, which never hold rows or
+    /// metadata.
+    pub fn is_comp(&self) -> bool {
+        self.page_type & SasPageType::Comp as u16 == SasPageType::Comp as u16
+    }
+
+    /// Return true if the page is of one of the types SAS writes.
+    pub fn is_known_type(&self) -> bool {
+        let page_type = self.page_type & SAS_PAGE_TYPE_MASK;
+        self.is_comp()
+            || [
+                SasPageType::Meta,
+                SasPageType::Data,
+                SasPageType::Mix,
+                SasPageType::Amd,
+            ]
+            .into_iter()
+            .any(|known| page_type == known as u16)
+    }
+
+    /// Return true if the page has a subheader pointer table.
+    pub fn has_subheaders(&self) -> bool {
+        self.is_meta() || self.is_mix()
+    }
+}
+
+/// Read the header at the start of a page.
+pub fn read_page_header(
+    page: &[u8],
+    ctx: &Sas7bdatContext,
+) -> Result<SasPageHeader, ReadStatError> {
+    if page.len() < ctx.page_header_size {
+        return Err(ReadStatError::Read);
+    }
+    let offset = ctx
+        .page_header_size
+        .checked_sub(8)
+        .ok_or(ReadStatError::Parse)?;
+    let mut cursor = ctx.cursor(page, offset)?;
+    Ok(SasPageHeader {
+        page_type: cursor.read_u16()?,
+        block_count: cursor.read_u16()?,
+        subheader_count: cursor.read_u16()?,
+    })
+}
+
+/// Extract the rows stored on a page, in file order. Rows stored verbatim are borrowed from the
+/// page; compressed rows are decompressed into owned buffers. A page can only be decoded once
+/// the row layout in `ctx` is known, but does not depend on any other page.
+///
+/// The number of rows returned is bounded by what the page declares and what fits in it; the
+/// caller is responsible for stopping at the file's total row count.
+///
+/// # Returns
+/// The rows, or an error naming the subheader and the row, counted from the start of the page,
+/// that could not be extracted.
+///
+/// # Original C function
+/// `sas7bdat_parse_page_pass2` in readstat's `sas7bdat_read.c`.
+pub fn page_rows<'a>(
+    page: &'a [u8],
+    ctx: &Sas7bdatContext,
+) -> Result<Vec<Cow<'a, [u8]>>, SasError> {
+    page_row_results(page, ctx)?.into_iter().collect()
+}
+
+/// A row extracted from a page, or the error that prevented it from being extracted.
+pub(crate) type PageRow<'a> = Result<Cow<'a, [u8]>, SasError>;
+
+/// Extract the rows stored on a page like `page_rows`, but carry on past rows that cannot be
+/// extracted, returning each one as an error of its own.
+///
+/// # Returns
+/// The rows and row errors in file order, or an error if the page header or the subheader
+/// pointer table cannot be read.
+pub(crate) fn page_row_results<'a>(
+    page: &'a [u8],
+    ctx: &Sas7bdatContext,
+) -> Result<Vec<PageRow<'a>>, SasError> {
+    let header = read_page_header(page, ctx)?;
+    let mut rows = Vec::new();
+    if ctx.row_length == 0 {
+        return Ok(rows);
+    }
+
+    if header.is_data() {
+        let data = &page[ctx.page_header_size..];
+        let row_count = (header.block_count as usize).min(data.len() / ctx.row_length);
+        rows.extend(
+            data.chunks_exact(ctx.row_length)
+                .take(row_count)
+                .map(|row| Ok(Cow::Borrowed(row))),
+        );
+        return Ok(rows);
+    }
+
+    if !header.has_subheaders() {
+        return Ok(rows);
+    }
+
+    let subheader_count = header.subheader_count as usize;
+    let pointers = read_subheader_pointers(page, subheader_count, ctx)?;
+    for (index, pointer) in pointers.iter().enumerate() {
+        let data = subheader_data(page, pointer);
+        let row = rows.len() as u64;
+        let row_error =
+            |err: ReadStatError| SasError::from(err).with_subheader(index).with_row(row);
+        match classify_subheader(page, pointer, ctx) {
+            SasSubheaderKind::CompressedRow => {
+                rows.push(decompress_row(data, ctx).map(Cow::Owned).map_err(row_error));
+            }
+            SasSubheaderKind::Row => {
+                rows.push(
+                    data.get(..ctx.row_length)
+                        .map(Cow::Borrowed)
+                        .ok_or_else(|| row_error(ReadStatError::RowWidthMismatch)),
+                );
+            }
+            _ => {}
+        }
+    }
+
+    if header.is_mix() {
+        let mut data_start = ctx.page_header_size + subheader_count * ctx.subheader_pointer_size;
+        // This is supposed to obey 8-byte boundaries, but some files created by Stat/Transfer
+        // don't. So verify that the padding is blank (or that the file is not from
+        // Stat/Transfer) before skipping it.
+        if data_start % 8 == 4 && data_start + 4 <= page.len() {
+            let padding = &page[data_start..data_start + 4];
+            if padding == [0, 0, 0, 0]
+                || padding == b"    "
+                || ctx.vendor != ReadStatVendor::StatTransfer
+            {
+                data_start += 4;
+            }
+        }
+
+        let data = &page[data_start.min(page.len())..];
+        let row_count = (ctx.mix_page_row_count as usize).min(data.len() / ctx.row_length);
+        rows.extend(
+            data.chunks_exact(ctx.row_length)
+                .take(row_count)
+                .map(|row| Ok(Cow::Borrowed(row))),
+        );
+    }
+
+    Ok(rows)
+}
+
+/// Decompress a single row with the file's compression scheme.
+fn decompress_row(data: &[u8], ctx: &Sas7bdatContext) -> Result<Vec<u8>, ReadStatError> {
+    match ctx.compression {
+        ReadStatCompression::Rows => rle_decompress(data, ctx.row_length),
+        ReadStatCompression::Binary => rdc_decompress(data, ctx.row_length),
+        ReadStatCompression::None => Err(ReadStatError::UnsupportedCompression),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SasHeaderInfo;
+
+    fn context() -> Sas7bdatContext {
+        let mut ctx = Sas7bdatContext::from_header(&SasHeaderInfo::builder().build().unwrap());
+        ctx.page_size = 64;
+        ctx.row_length = 8;
+        ctx.mix_page_row_count = 2;
+        ctx
+    }
+
+    #[test]
+    fn test_page_header_types() {
+        let header = SasPageHeader {
+            page_type: 0x0100,
+            block_count: 0,
+            subheader_count: 0,
+        };
+        assert!(header.is_data());
+        assert!(!header.has_subheaders());
+
+        let header = SasPageHeader {
+            page_type: 0x4000,
+            block_count: 0,
+            subheader_count: 0,
+        };
+        assert!(header.is_meta());
+
+        let header = SasPageHeader {
+            page_type: 0x9000,
+            block_count: 0,
+            subheader_count: 0,
+        };
+        assert!(header.is_comp());
+        assert!(!header.is_meta());
+        assert!(header.is_known_type());
+
+        let header = SasPageHeader {
+            page_type: 0x0800,
+            block_count: 0,
+            subheader_count: 0,
+        };
+        assert!(!header.is_known_type());
+    }
+
+    #[test]
+    fn test_data_page_rows() {
+        let ctx = context();
+        let mut page = vec![0u8; 64];
+        page[16] = 0x00;
+        page[17] = 0x01;
+        page[18] = 3;
+        for (i, byte) in page[24..48].iter_mut().enumerate() {
+            *byte = i as u8;
+        }
+        let rows = page_rows(&page, &ctx).unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(&rows[1][..], &[8, 9, 10, 11, 12, 13, 14, 15]);
+    }
+
+    #[test]
+    fn test_data_page_rows_bounded_by_page() {
+        let ctx = context();
+        let mut page = vec![0u8; 64];
+        page[17] = 0x01;
+        page[18] = 100;
+        let rows = page_rows(&page, &ctx).unwrap();
+        assert_eq!(rows.len(), 5);
+    }
+
+    #[test]
+    fn test_mix_page_rows_are_aligned() {
+        let ctx = context();
+        let mut page = vec![b'.'; 64];
+        page[16] = 0x00;
+        page[17] = 0x02;
+        // One empty subheader pointer, ending 4 bytes short of an 8-byte boundary
+        page[20] = 1;
+        page[21] = 0;
+        page[24..40].copy_from_slice(&[0; 16]);
+        let rows = page_rows(&page, &ctx).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(&rows[0][..], b"........");
+    }
+
+    #[test]
+    fn test_truncated_subheader_outside_page() {
+        let ctx = context();
+        let mut page = vec![0u8; 64];
+        page[17] = 0x40;
+        page[20] = 1;
+        // Truncated pointers are skipped, wherever they point
+        page[24..28].copy_from_slice(&0xFFFF_FFF0u32.to_le_bytes());
+        page[28] = 16;
+        page[32] = 1;
+        assert_eq!(page_rows(&page, &ctx).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_read_page_header_too_short() {
+        let ctx = context();
+        assert_eq!(read_page_header(&[0; 8], &ctx), Err(ReadStatError::Read));
+    }
+}
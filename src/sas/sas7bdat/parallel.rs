@@ -0,0 +1,323 @@
+use crate::sas::sas7bdat::context::Sas7bdatContext;
+use crate::sas::sas7bdat::page::page_rows;
+use crate::sas::sas7bdat::reader::{at_page, Sas7bdatReader};
+use crate::sas::sas7bdat::row::decode_row;
+use crate::types::{ReadStatValue, ReadStatVariable, SasError, SasReadOptions, SasRowOrder};
+use std::collections::BTreeMap;
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+
+/// Number of pages kept in flight per thread of the pool. Pages are read from the file on the
+/// calling thread while the pool decodes earlier ones, so a little slack keeps every thread busy.
+const PAGES_PER_THREAD: usize = 4;
+
+type DecodedPage = Result<Vec<Vec<ReadStatValue>>, SasError>;
+
+/// What every page task needs to decode a page on its own.
+#[derive(Debug)]
+struct PageDecoder {
+    context: Sas7bdatContext,
+    variables: Vec<ReadStatVariable>,
+    selection: Vec<usize>,
+}
+
+impl PageDecoder {
+    /// Decode the page with the given index. Rows in errors are counted from the start of the
+    /// page, since the rows of earlier pages may not have been counted yet.
+    fn decode(&self, index: u64, page: &[u8]) -> DecodedPage {
+        Ok(page_rows(page, &self.context)
+            .map_err(|err| at_page(err, &self.context, index, 0))?
+            .iter()
+            .map(|row| decode_row(row, &self.context, &self.variables, &self.selection))
+            .collect())
+    }
+}
+
+impl Sas7bdatReader {
+    /// Iterate over the rows selected by `options`, decompressing and decoding pages on the
+    /// rayon global thread pool. Pages are read from the file on the calling thread.
+    ///
+    /// With `SasRowOrder::Ordered` the rows come back in the order `rows` returns them. With
+    /// `SasRowOrder::Unordered` the rows of each page stay together but pages are returned as
+    /// soon as they are decoded, and `row_offset` and `row_limit` count rows in that order.
+    pub fn par_rows(
+        &mut self,
+        options: &SasReadOptions,
+        order: SasRowOrder,
+    ) -> Result<Sas7bdatParRows<'_>, SasError> {
+        let selection = self.resolve_columns(&options.columns)?;
+        let decoder = Arc::new(PageDecoder {
+            context: self.context().clone(),
+            variables: self.variables().to_vec(),
+            selection,
+        });
+        let (sender, receiver) = channel();
+        Ok(Sas7bdatParRows {
+            reader: self,
+            decoder,
+            order,
+            sender,
+            receiver,
+            window: rayon::current_num_threads() * PAGES_PER_THREAD,
+            next_page: 0,
+            in_flight: 0,
+            next_to_emit: 0,
+            pages_done: 0,
+            finished: BTreeMap::new(),
+            buffered: Vec::new().into_iter(),
+            rows_seen: 0,
+            rows_returned: 0,
+            row_offset: options.row_offset,
+            row_limit: options.row_limit,
+        })
+    }
+
+    /// Read every row selected by `options` into memory, decoding pages in parallel.
+    pub fn par_read_rows(
+        &mut self,
+        options: &SasReadOptions,
+        order: SasRowOrder,
+    ) -> Result<Vec<Vec<ReadStatValue>>, SasError> {
+        self.par_rows(options, order)?.collect()
+    }
+}
+
+/// Iterator over the rows of a sas7bdat file decoded in parallel, created by
+/// `Sas7bdatReader::par_rows`.
+#[derive(Debug)]
+pub struct Sas7bdatParRows<'a> {
+    reader: &'a mut Sas7bdatReader,
+    decoder: Arc<PageDecoder>,
+    order: SasRowOrder,
+    sender: Sender<(u64, DecodedPage)>,
+    receiver: Receiver<(u64, DecodedPage)>,
+    window: usize,
+    next_page: u64,
+    in_flight: usize,
+    next_to_emit: u64,
+    pages_done: u64,
+    finished: BTreeMap<u64, DecodedPage>,
+    buffered: std::vec::IntoIter<Vec<ReadStatValue>>,
+    rows_seen: u64,
+    rows_returned: u64,
+    row_offset: u64,
+    row_limit: Option<u64>,
+}
+
+impl Sas7bdatParRows<'_> {
+    /// Read pages and hand them to the thread pool until the window is full.
+    fn submit_pages(&mut self) -> Result<(), SasError> {
+        while self.in_flight < self.window && self.next_page < self.decoder.context.page_count {
+            let index = self.next_page;
+            let page = self.reader.read_page(index)?;
+            self.next_page += 1;
+            self.in_flight += 1;
+
+            let decoder = Arc::clone(&self.decoder);
+            let sender = self.sender.clone();
+            rayon::spawn(move || {
+                // The receiver is gone if the iterator was dropped early; nothing is waiting.
+                let _ = sender.send((index, decoder.decode(index, &page)));
+            });
+        }
+        Ok(())
+    }
+
+    /// Wait for the next page to return, according to the row order.
+    fn next_decoded(&mut self) -> Option<DecodedPage> {
+        let page = self.wait_for_page()?;
+        self.pages_done += 1;
+        Some(page)
+    }
+
+    fn wait_for_page(&mut self) -> Option<DecodedPage> {
+        loop {
+            if self.order == SasRowOrder::Ordered {
+                if let Some(page) = self.finished.remove(&self.next_to_emit) {
+                    self.next_to_emit += 1;
+                    return Some(page);
+                }
+            }
+            if self.in_flight == 0 {
+                return None;
+            }
+            let (index, page) = self.receiver.recv().ok()?;
+            self.in_flight -= 1;
+            match self.order {
+                SasRowOrder::Ordered => {
+                    self.finished.insert(index, page);
+                }
+                SasRowOrder::Unordered => return Some(page),
+            }
+        }
+    }
+
+    /// Move the rows of the next decoded page into the buffer, skipping rows before the offset
+    /// and past the file's row count.
+    fn fill_buffer(&mut self, rows: Vec<Vec<ReadStatValue>>) -> Result<(), SasError> {
+        let row_count = self.decoder.context.row_count;
+        let mut kept = Vec::with_capacity(rows.len());
+        for row in rows {
+            if self.rows_seen >= row_count {
+                break;
+            }
+            if self.rows_seen >= self.row_offset {
+                kept.push(row);
+            }
+            self.rows_seen += 1;
+        }
+        self.buffered = kept.into_iter();
+        Ok(self
+            .reader
+            .report_progress(self.pages_done, self.rows_seen)?)
+    }
+
+    fn stop(&mut self) {
+        self.next_page = self.decoder.context.page_count;
+        self.in_flight = 0;
+        self.finished.clear();
+        self.buffered = Vec::new().into_iter();
+    }
+}
+
+impl Iterator for Sas7bdatParRows<'_> {
+    type Item = Result<Vec<ReadStatValue>, SasError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self
+                .row_limit
+                .is_some_and(|limit| self.rows_returned >= limit)
+            {
+                return None;
+            }
+            if let Some(row) = self.buffered.next() {
+                self.rows_returned += 1;
+                return Some(Ok(row));
+            }
+            if self.rows_seen >= self.decoder.context.row_count {
+                return None;
+            }
+            if let Err(err) = self.submit_pages() {
+                self.stop();
+                return Some(Err(err));
+            }
+            let decoded = self.next_decoded()?.map_err(|mut err| {
+                // Only in file order do the rows already seen all come before the failed page
+                err.row = match self.order {
+                    SasRowOrder::Ordered => err.row.map(|row| self.rows_seen + row),
+                    SasRowOrder::Unordered => None,
+                };
+                err
+            });
+            if let Err(err) = decoded.and_then(|rows| self.fill_buffer(rows)) {
+                self.stop();
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::{ReadStatCompression, ReadStatError, ReadStatHandler, ReadStatValueType};
+    use std::sync::Mutex;
+
+    fn fixture(compression: ReadStatCompression) -> SasFixture {
+        let rows = (0..500)
+            .map(|i| {
+                vec![
+                    ReadStatValueType::Double(i as f64),
+                    ReadStatValueType::String(format!("row {}", i)),
+                ]
+            })
+            .collect();
+        SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 12),
+            ])
+            .rows(rows)
+            .build()
+            .with_compression(compression)
+    }
+
+    fn ids(rows: &[Vec<ReadStatValue>]) -> Vec<f64> {
+        rows.iter()
+            .map(|row| match row[0].value {
+                ReadStatValueType::Double(id) => id,
+                _ => panic!("ID should be numeric"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_par_rows_ordered() {
+        for (name, compression) in [
+            ("par_rows_plain", ReadStatCompression::None),
+            ("par_rows_rle", ReadStatCompression::Rows),
+        ] {
+            let path = fixture(compression).write_temp(name);
+            let mut reader = Sas7bdatReader::open(&path).unwrap();
+            let options = SasReadOptions::default();
+            let sequential = reader.read_rows(&options).unwrap();
+            let parallel = reader
+                .par_read_rows(&options, SasRowOrder::Ordered)
+                .unwrap();
+            assert_eq!(parallel.len(), 500);
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[test]
+    fn test_par_rows_unordered() {
+        let path = fixture(ReadStatCompression::None).write_temp("par_rows_unordered");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let rows = reader
+            .par_read_rows(&SasReadOptions::default(), SasRowOrder::Unordered)
+            .unwrap();
+        let mut ids = ids(&rows);
+        ids.sort_by(f64::total_cmp);
+        assert_eq!(ids, (0..500).map(|i| i as f64).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_par_rows_progress() {
+        let path = fixture(ReadStatCompression::None).write_temp("par_rows_progress");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let last = Arc::new(Mutex::new(None));
+        let log = Arc::clone(&last);
+        reader.set_progress_handler(2, move |progress| {
+            *log.lock().unwrap() = Some(*progress);
+            ReadStatHandler::Ok
+        });
+        reader
+            .par_read_rows(&SasReadOptions::default(), SasRowOrder::Unordered)
+            .unwrap();
+        let last = last.lock().unwrap().unwrap();
+        assert_eq!((last.rows_read, last.row_count), (500, 500));
+    }
+
+    #[test]
+    fn test_par_rows_options() {
+        let path = fixture(ReadStatCompression::None).write_temp("par_rows_options");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let options = SasReadOptions::new(Some(vec!["id".to_string()]), 240, Some(20));
+        let rows = reader
+            .par_read_rows(&options, SasRowOrder::Ordered)
+            .unwrap();
+        assert_eq!(ids(&rows), (240..260).map(|i| i as f64).collect::<Vec<_>>());
+        assert!(rows.iter().all(|row| row.len() == 1));
+
+        let options = SasReadOptions::new(Some(vec!["AGE".to_string()]), 0, None);
+        assert_eq!(
+            reader
+                .par_rows(&options, SasRowOrder::Ordered)
+                .err()
+                .map(|err| err.kind),
+            Some(ReadStatError::UnknownColumn)
+        );
+    }
+}
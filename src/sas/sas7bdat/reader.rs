@@ -0,0 +1,790 @@
+use crate::sas::sas7bdat::context::Sas7bdatContext;
+use crate::sas::sas7bdat::header::parse_header;
+use crate::sas::sas7bdat::page::{page_rows, read_page_header};
+use crate::sas::sas7bdat::row::decode_row;
+use crate::sas::sas7bdat::source::Sas7bdatSource;
+use crate::sas::sas7bdat::subheader::{
+    classify_subheader, read_subheader_pointers, subheader_data, Sas7bdatMetadataParser,
+};
+use crate::types::{
+    ReadStatEncoding, ReadStatEndianness, ReadStatError, ReadStatFileLabel, ReadStatHandler,
+    ReadStatIO, ReadStatMetadata, ReadStatProgress, ReadStatProgressHandler, ReadStatValue,
+    ReadStatVariable, ReaderLimits, SasEndian, SasError, SasHeaderInfo, SasReadOptions,
+    SasTableName,
+};
+use chrono::DateTime;
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::Cursor;
+use std::path::Path;
+
+/// The smallest legal header, which always contains every field `parse_header` needs.
+pub(crate) const MIN_HEADER_SIZE: usize = 1024;
+
+/// Reader for sas7bdat files. Opening a file parses its header and metadata; rows are decoded
+/// page by page as they are iterated.
+///
+/// ```no_run
+/// use rust_sas::sas::sas7bdat::Sas7bdatReader;
+/// use rust_sas::types::SasReadOptions;
+///
+/// let mut reader = Sas7bdatReader::open("claims.sas7bdat").unwrap();
+/// for row in reader.rows(&SasReadOptions::default()).unwrap() {
+///     println!("{:?}", row.unwrap());
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Sas7bdatReader {
+    source: Sas7bdatSource,
+    header: SasHeaderInfo,
+    context: Sas7bdatContext,
+    metadata: ReadStatMetadata,
+    variables: Vec<ReadStatVariable>,
+    file_label: String,
+    progress: Option<ReadStatProgressHandler>,
+}
+
+impl Sas7bdatReader {
+    /// Open the sas7bdat file at the given path and parse its header and metadata.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, SasError> {
+        Self::open_with_limits(path, ReaderLimits::default())
+    }
+
+    /// Open the sas7bdat file at the given path, refusing files that exceed `limits`.
+    pub fn open_with_limits<P: AsRef<Path>>(
+        path: P,
+        limits: ReaderLimits,
+    ) -> Result<Self, SasError> {
+        let file = File::open(path).map_err(|err| SasError::io(ReadStatError::Open, err))?;
+        Self::from_reader_with_limits(file, limits)
+    }
+
+    /// Parse the header and metadata of an already opened sas7bdat file.
+    pub fn from_file(file: File) -> Result<Self, SasError> {
+        Self::from_reader(file)
+    }
+
+    /// Parse the header and metadata of a sas7bdat file read from any seekable source, such as
+    /// a decompression stream or a user-defined `ReadStatIO`.
+    pub fn from_reader<R: ReadStatIO + Send + 'static>(reader: R) -> Result<Self, SasError> {
+        Self::from_reader_with_limits(reader, ReaderLimits::default())
+    }
+
+    /// Parse a sas7bdat file read from any seekable source, refusing files that exceed
+    /// `limits`.
+    pub fn from_reader_with_limits<R: ReadStatIO + Send + 'static>(
+        reader: R,
+        limits: ReaderLimits,
+    ) -> Result<Self, SasError> {
+        Self::from_source_with_limits(Sas7bdatSource::Io(Box::new(reader)), limits)
+    }
+
+    /// Parse the header and metadata of a sas7bdat file held in memory, eg a dataset received
+    /// over the network, without writing it to disk.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, SasError> {
+        Self::from_reader(Cursor::new(bytes))
+    }
+
+    /// Parse a sas7bdat file held in memory, refusing files that exceed `limits`, eg an upload
+    /// that has not been vetted yet.
+    pub fn from_bytes_with_limits(bytes: Vec<u8>, limits: ReaderLimits) -> Result<Self, SasError> {
+        Self::from_reader_with_limits(Cursor::new(bytes), limits)
+    }
+
+    /// Memory-map the sas7bdat file at the given path and parse its header and metadata. Pages
+    /// are then borrowed from the map instead of being copied into buffers, which makes random
+    /// access to large files cheap.
+    ///
+    /// The file must not be truncated or modified by another process while it is mapped.
+    #[cfg(feature = "mmap")]
+    pub fn open_mmap<P: AsRef<Path>>(path: P) -> Result<Self, SasError> {
+        let file = File::open(path).map_err(|err| SasError::io(ReadStatError::Open, err))?;
+        // SAFETY: the map is read-only; like every memory map, it is only sound as long as no
+        // other process truncates or writes to the file, which the caller is told to ensure.
+        let map =
+            unsafe { Mmap::map(&file) }.map_err(|err| SasError::io(ReadStatError::Open, err))?;
+        Self::from_mmap(map)
+    }
+
+    /// Parse the header and metadata of a memory-mapped sas7bdat file.
+    #[cfg(feature = "mmap")]
+    pub fn from_mmap(map: Mmap) -> Result<Self, SasError> {
+        Self::from_source(Sas7bdatSource::Mmap(map))
+    }
+
+    /// Parse the header and metadata of a sas7bdat file read from the given source.
+    pub fn from_source(source: Sas7bdatSource) -> Result<Self, SasError> {
+        Self::from_source_with_limits(source, ReaderLimits::default())
+    }
+
+    /// Parse the header and metadata of a sas7bdat file read from the given source. Files that
+    /// exceed `limits` are refused as soon as the offending header or subheader is read, before
+    /// anything is allocated for it.
+    pub fn from_source_with_limits(
+        mut source: Sas7bdatSource,
+        limits: ReaderLimits,
+    ) -> Result<Self, SasError> {
+        let header_bytes = source.read_at(0, MIN_HEADER_SIZE)?.into_owned();
+        let header =
+            parse_header(&header_bytes).map_err(|err| SasError::from(err).with_offset(0))?;
+        limits
+            .check_pages(header.page_count)
+            .map_err(|err| SasError::from(err).with_offset(0))?;
+
+        let mut context = Sas7bdatContext::from_header(&header);
+        context.limits = limits;
+        let mut parser = Sas7bdatMetadataParser::new();
+        for index in 0..header.page_count {
+            let page = source
+                .read_at(context.page_offset(index), context.page_size)
+                .map_err(|err| err.with_page(index))?;
+            if parse_metadata_page(&page, index, &mut parser, &mut context)? {
+                break;
+            }
+        }
+
+        let (variables, file_label) = parser.finish(&mut context)?;
+        let metadata = build_metadata(&header, &context, variables.len());
+
+        Ok(Self {
+            source,
+            header,
+            context,
+            metadata,
+            variables,
+            file_label,
+            progress: None,
+        })
+    }
+
+    pub fn header(&self) -> &SasHeaderInfo {
+        &self.header
+    }
+
+    pub fn context(&self) -> &Sas7bdatContext {
+        &self.context
+    }
+
+    pub fn metadata(&self) -> &ReadStatMetadata {
+        &self.metadata
+    }
+
+    pub fn variables(&self) -> &[ReadStatVariable] {
+        &self.variables
+    }
+
+    /// The label of the dataset, as given by the `LABEL=` data set option in SAS.
+    pub fn file_label(&self) -> &str {
+        &self.file_label
+    }
+
+    /// Return the index of the variable with the given name, compared case-insensitively as
+    /// SAS does.
+    pub fn variable_index(&self, name: &str) -> Option<usize> {
+        variable_index(&self.variables, name)
+    }
+
+    /// Resolve a selection of variable names to indexes into `variables`. `None` selects every
+    /// variable in file order.
+    pub fn resolve_columns(&self, columns: &Option<Vec<String>>) -> Result<Vec<usize>, SasError> {
+        resolve_columns(&self.variables, columns)
+    }
+
+    /// Register a callback reporting the progress of row reads, and of the writers reading
+    /// from this reader, every `granularity` pages and once the last row has been read. The
+    /// callback can return `ReadStatHandler::Abort` to stop the read with
+    /// `ReadStatError::UserAbort`.
+    pub fn set_progress_handler<F>(&mut self, granularity: u64, callback: F)
+    where
+        F: FnMut(&ReadStatProgress) -> ReadStatHandler + Send + 'static,
+    {
+        self.progress = Some(ReadStatProgressHandler::new(
+            granularity,
+            Box::new(callback),
+        ));
+    }
+
+    /// Report to the progress handler, if any, that `pages_read` pages and `rows_read` rows
+    /// have been read.
+    pub(crate) fn report_progress(
+        &mut self,
+        pages_read: u64,
+        rows_read: u64,
+    ) -> Result<(), ReadStatError> {
+        match &mut self.progress {
+            Some(handler) => handler.report(&progress_at(&self.context, pages_read, rows_read)),
+            None => Ok(()),
+        }
+    }
+
+    /// Read the raw bytes of the page with the given index.
+    pub fn read_page(&mut self, index: u64) -> Result<Vec<u8>, SasError> {
+        self.page(index).map(Cow::into_owned)
+    }
+
+    /// Return the raw bytes of the page with the given index, borrowed from the memory map when
+    /// the file was opened with `open_mmap`.
+    pub fn page(&mut self, index: u64) -> Result<Cow<'_, [u8]>, SasError> {
+        self.source
+            .read_at(self.context.page_offset(index), self.context.page_size)
+            .map_err(|err| err.with_page(index))
+    }
+
+    /// Iterate over the rows selected by `options`. Each row holds the values of the selected
+    /// variables, in the order they were selected.
+    pub fn rows(&mut self, options: &SasReadOptions) -> Result<Sas7bdatRows<'_>, SasError> {
+        let selection = self.resolve_columns(&options.columns)?;
+        Ok(Sas7bdatRows {
+            reader: self,
+            selection,
+            next_page: 0,
+            buffered: Vec::new().into_iter(),
+            rows_seen: 0,
+            rows_returned: 0,
+            row_offset: options.row_offset,
+            row_limit: options.row_limit,
+        })
+    }
+
+    /// Read every row selected by `options` into memory.
+    pub fn read_rows(
+        &mut self,
+        options: &SasReadOptions,
+    ) -> Result<Vec<Vec<ReadStatValue>>, SasError> {
+        self.rows(options)?.collect()
+    }
+}
+
+/// Iterator over the rows of a sas7bdat file, created by `Sas7bdatReader::rows`.
+#[derive(Debug)]
+pub struct Sas7bdatRows<'a> {
+    reader: &'a mut Sas7bdatReader,
+    selection: Vec<usize>,
+    next_page: u64,
+    buffered: std::vec::IntoIter<Vec<ReadStatValue>>,
+    rows_seen: u64,
+    rows_returned: u64,
+    row_offset: u64,
+    row_limit: Option<u64>,
+}
+
+impl Sas7bdatRows<'_> {
+    /// Decode the rows of the next page into the buffer, skipping rows before the offset.
+    fn fill_buffer(&mut self) -> Result<(), SasError> {
+        let ctx = &self.reader.context;
+        let index = self.next_page;
+        let page = self
+            .reader
+            .source
+            .read_at(ctx.page_offset(index), ctx.page_size)
+            .map_err(|err| err.with_page(index))?;
+        self.next_page += 1;
+
+        let rows = page_rows(&page, ctx).map_err(|err| at_page(err, ctx, index, self.rows_seen))?;
+        let mut decoded = Vec::new();
+        for row in rows {
+            if self.rows_seen >= ctx.row_count {
+                break;
+            }
+            if self.rows_seen >= self.row_offset {
+                decoded.push(decode_row(
+                    &row,
+                    ctx,
+                    &self.reader.variables,
+                    &self.selection,
+                ));
+            }
+            self.rows_seen += 1;
+        }
+        self.buffered = decoded.into_iter();
+        Ok(self
+            .reader
+            .report_progress(self.next_page, self.rows_seen)?)
+    }
+}
+
+impl Iterator for Sas7bdatRows<'_> {
+    type Item = Result<Vec<ReadStatValue>, SasError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self
+                .row_limit
+                .is_some_and(|limit| self.rows_returned >= limit)
+            {
+                return None;
+            }
+            if let Some(row) = self.buffered.next() {
+                self.rows_returned += 1;
+                return Some(Ok(row));
+            }
+            if self.next_page >= self.reader.context.page_count
+                || self.rows_seen >= self.reader.context.row_count
+            {
+                return None;
+            }
+            if let Err(err) = self.fill_buffer() {
+                self.next_page = self.reader.context.page_count;
+                self.buffered = Vec::new().into_iter();
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+/// Feed the metadata subheaders of the page with the given index to the parser, in file order.
+///
+/// # Returns
+/// True once the metadata is complete and the page holds rows, after which no later page can
+/// hold metadata.
+pub(crate) fn parse_metadata_page(
+    page: &[u8],
+    index: u64,
+    parser: &mut Sas7bdatMetadataParser,
+    context: &mut Sas7bdatContext,
+) -> Result<bool, SasError> {
+    let page_offset = context.page_offset(index);
+    let page_error = |err: ReadStatError| {
+        SasError::from(err)
+            .with_page(index)
+            .with_offset(page_offset)
+    };
+    let page_header = read_page_header(page, context).map_err(page_error)?;
+    let mut has_rows = page_header.is_data() || page_header.is_mix();
+
+    if page_header.has_subheaders() {
+        let subheader_count = page_header.subheader_count as usize;
+        let pointers =
+            read_subheader_pointers(page, subheader_count, context).map_err(page_error)?;
+        for (subheader, pointer) in pointers.iter().enumerate() {
+            let kind = classify_subheader(page, pointer, context);
+            if kind.is_row() {
+                has_rows = true;
+                continue;
+            }
+            let data = subheader_data(page, pointer);
+            parser
+                .parse_subheader(&kind, data, context)
+                .map_err(|err| {
+                    SasError::from(err)
+                        .with_page(index)
+                        .with_subheader(subheader)
+                        .with_offset(page_offset + pointer.offset)
+                })?;
+            if let Some(compression) = &parser.compression {
+                context.compression = compression.clone();
+            }
+        }
+    }
+
+    Ok(has_rows && parser.is_complete())
+}
+
+/// Add the page with the given index to an error from `page_rows`, whose row is counted from
+/// the start of the page, `rows_before` being the number of rows on earlier pages.
+pub(crate) fn at_page(
+    mut err: SasError,
+    ctx: &Sas7bdatContext,
+    index: u64,
+    rows_before: u64,
+) -> SasError {
+    err.row = err.row.map(|row| rows_before + row);
+    err.with_page(index).with_offset(ctx.page_offset(index))
+}
+
+/// Measure progress through the pages and rows of a file.
+pub(crate) fn progress_at(
+    ctx: &Sas7bdatContext,
+    pages_read: u64,
+    rows_read: u64,
+) -> ReadStatProgress {
+    ReadStatProgress::new(
+        ctx.page_offset(pages_read),
+        ctx.page_offset(ctx.page_count),
+        pages_read,
+        ctx.page_count,
+        rows_read.min(ctx.row_count),
+        ctx.row_count,
+    )
+}
+
+pub(crate) fn variable_index(variables: &[ReadStatVariable], name: &str) -> Option<usize> {
+    variables
+        .iter()
+        .position(|variable| variable.name.eq_ignore_ascii_case(name))
+}
+
+pub(crate) fn resolve_columns(
+    variables: &[ReadStatVariable],
+    columns: &Option<Vec<String>>,
+) -> Result<Vec<usize>, SasError> {
+    match columns {
+        None => Ok((0..variables.len()).collect()),
+        Some(names) => names
+            .iter()
+            .map(|name| {
+                variable_index(variables, name)
+                    .ok_or_else(|| SasError::new(ReadStatError::UnknownColumn).with_variable(name))
+            })
+            .collect(),
+    }
+}
+
+pub(crate) fn build_metadata(
+    header: &SasHeaderInfo,
+    ctx: &Sas7bdatContext,
+    var_count: usize,
+) -> ReadStatMetadata {
+    ReadStatMetadata::new(
+        ctx.row_count as u128,
+        var_count as u128,
+        DateTime::from_timestamp(header.creation_time, 0).unwrap_or_default(),
+        DateTime::from_timestamp(header.modification_time, 0).unwrap_or_default(),
+        header.major_version as u16,
+        ctx.compression.clone(),
+        match header.endianness {
+            SasEndian::Little => ReadStatEndianness::Little,
+            SasEndian::Big => ReadStatEndianness::Big,
+        },
+        SasTableName::new(header.table_name.clone()),
+        ReadStatFileLabel::Sas,
+        encoding_from_charset(&header.encoding),
+        header.is_64bit,
+    )
+}
+
+pub(crate) fn encoding_from_charset(name: &str) -> ReadStatEncoding {
+    match name {
+        "UTF-8" => ReadStatEncoding::Utf8,
+        "ISO-8859-1" => ReadStatEncoding::Latin1,
+        "WINDOWS-1252" => ReadStatEncoding::Windows1252,
+        "US-ASCII" => ReadStatEncoding::Ascii,
+        _ => ReadStatEncoding::Other(name.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::sas::reader_limits::ReaderLimitsBuilder;
+    use crate::types::{ReadStatCompression, ReadStatType, ReadStatValueType};
+    use std::sync::{Arc, Mutex};
+
+    fn sample_fixture() -> SasFixture {
+        SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 8).with_label("Full name"),
+                SasFixtureColumn::numeric("AMOUNT", "COMMA", 10).with_decimals(2),
+            ])
+            .rows(
+                (0..250)
+                    .map(|i| {
+                        vec![
+                            ReadStatValueType::Double(i as f64),
+                            ReadStatValueType::String(format!("name{}", i)),
+                            ReadStatValueType::Double(i as f64 * 1.5),
+                        ]
+                    })
+                    .collect(),
+            )
+            .build()
+    }
+
+    #[test]
+    fn test_open_reads_metadata() {
+        let path = sample_fixture().write_temp("reader_metadata");
+        let reader = Sas7bdatReader::open(&path).unwrap();
+
+        assert_eq!(reader.metadata().row_count, 250);
+        assert_eq!(reader.metadata().var_count, 3);
+        assert_eq!(reader.metadata().compression, ReadStatCompression::None);
+        assert_eq!(reader.metadata().file_encoding, ReadStatEncoding::Utf8);
+        assert_eq!(reader.metadata().table_name.name, "FIXTURE");
+        assert_eq!(reader.file_label(), "Fixture dataset");
+
+        let variables = reader.variables();
+        assert_eq!(variables[0].name, "ID");
+        assert_eq!(variables[0].type_, ReadStatType::Double);
+        assert_eq!(variables[0].format, "BEST12.");
+        assert_eq!(variables[1].name, "NAME");
+        assert_eq!(variables[1].type_, ReadStatType::String);
+        assert_eq!(variables[1].label, "Full name");
+        assert_eq!(variables[1].storage_width, 8);
+        assert_eq!(variables[2].format, "COMMA10.2");
+    }
+
+    #[test]
+    fn test_read_all_rows() {
+        let path = sample_fixture().write_temp("reader_all_rows");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let rows = reader.read_rows(&SasReadOptions::default()).unwrap();
+
+        assert_eq!(rows.len(), 250);
+        assert_eq!(rows[0][0].value, ReadStatValueType::Double(0.0));
+        assert_eq!(
+            rows[249][1].value,
+            ReadStatValueType::String("name249".to_string())
+        );
+        assert_eq!(rows[100][2].value, ReadStatValueType::Double(150.0));
+    }
+
+    #[test]
+    fn test_read_rows_with_projection_offset_and_limit() {
+        let path = sample_fixture().write_temp("reader_projection");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let options = SasReadOptions::builder()
+            .columns(Some(vec!["amount".to_string(), "ID".to_string()]))
+            .row_offset(200)
+            .row_limit(Some(3))
+            .build()
+            .unwrap();
+        let rows = reader.read_rows(&options).unwrap();
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0].len(), 2);
+        assert_eq!(rows[0][0].value, ReadStatValueType::Double(300.0));
+        assert_eq!(rows[2][1].value, ReadStatValueType::Double(202.0));
+    }
+
+    #[test]
+    fn test_unknown_column() {
+        let path = sample_fixture().write_temp("reader_unknown_column");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let options = SasReadOptions::builder()
+            .columns(Some(vec!["MISSING".to_string()]))
+            .build()
+            .unwrap();
+        let err = reader.rows(&options).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::UnknownColumn);
+        assert_eq!(err.variable.as_deref(), Some("MISSING"));
+        assert_eq!(err.to_string(), "Unknown column at variable MISSING");
+    }
+
+    #[test]
+    fn test_read_64bit_big_endian() {
+        let path = SasFixture::builder()
+            .is_64bit(true)
+            .endianness(SasEndian::Big)
+            .columns(vec![
+                SasFixtureColumn::numeric("X", "", 8),
+                SasFixtureColumn::character("S", 3),
+            ])
+            .rows(vec![
+                vec![
+                    ReadStatValueType::Double(-1.25),
+                    ReadStatValueType::String("abc".to_string()),
+                ],
+                vec![
+                    ReadStatValueType::Double(f64::NAN),
+                    ReadStatValueType::String(String::new()),
+                ],
+            ])
+            .build()
+            .write_temp("reader_64bit");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        assert!(reader.metadata().is_64bit);
+        assert_eq!(reader.metadata().endianness, ReadStatEndianness::Big);
+
+        let rows = reader.read_rows(&SasReadOptions::default()).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0][0].value, ReadStatValueType::Double(-1.25));
+        assert_eq!(
+            rows[0][1].value,
+            ReadStatValueType::String("abc".to_string())
+        );
+        assert!(rows[1][0].is_system_missing());
+        assert!(rows[1][1].is_system_missing());
+    }
+
+    #[test]
+    fn test_read_compressed_rows() {
+        for compression in [ReadStatCompression::Rows, ReadStatCompression::Binary] {
+            let path = sample_fixture()
+                .with_compression(compression.clone())
+                .write_temp(&format!("reader_compressed_{:?}", compression));
+            let mut reader = Sas7bdatReader::open(&path).unwrap();
+            assert_eq!(reader.metadata().compression, compression);
+
+            let rows = reader.read_rows(&SasReadOptions::default()).unwrap();
+            assert_eq!(rows.len(), 250);
+            assert_eq!(
+                rows[123][1].value,
+                ReadStatValueType::String("name123".to_string())
+            );
+        }
+    }
+
+    #[cfg(feature = "mmap")]
+    #[test]
+    fn test_open_mmap() {
+        for compression in [ReadStatCompression::None, ReadStatCompression::Rows] {
+            let path = sample_fixture()
+                .with_compression(compression.clone())
+                .write_temp(&format!("reader_mmap_{:?}", compression));
+            let mut file_reader = Sas7bdatReader::open(&path).unwrap();
+            let mut mmap_reader = Sas7bdatReader::open_mmap(&path).unwrap();
+            assert_eq!(mmap_reader.metadata(), file_reader.metadata());
+            assert_eq!(mmap_reader.variables(), file_reader.variables());
+
+            let page = mmap_reader.page(1).unwrap();
+            assert!(matches!(page, Cow::Borrowed(_)));
+            assert_eq!(page.into_owned(), file_reader.read_page(1).unwrap());
+
+            let options = SasReadOptions::default();
+            assert_eq!(
+                mmap_reader.read_rows(&options).unwrap(),
+                file_reader.read_rows(&options).unwrap()
+            );
+            let page_count = mmap_reader.context().page_count;
+            assert_eq!(
+                mmap_reader.page(page_count).err().map(|err| err.kind),
+                Some(ReadStatError::Read)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        let fixture = sample_fixture();
+        let path = fixture.clone().write_temp("reader_from_bytes");
+        let mut file_reader = Sas7bdatReader::open(&path).unwrap();
+        let mut bytes_reader = Sas7bdatReader::from_bytes(fixture.to_bytes()).unwrap();
+        assert_eq!(bytes_reader.metadata(), file_reader.metadata());
+
+        let options = SasReadOptions::default();
+        assert_eq!(
+            bytes_reader.read_rows(&options).unwrap(),
+            file_reader.read_rows(&options).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_truncated() {
+        let mut bytes = sample_fixture().to_bytes();
+        let err = Sas7bdatReader::from_bytes(bytes[..512].to_vec()).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::Read);
+        assert_eq!(err.offset, Some(0));
+
+        bytes.truncate(bytes.len() - 100);
+        let mut reader = Sas7bdatReader::from_bytes(bytes).unwrap();
+        let last_page = reader.context().page_count - 1;
+        let result: Result<Vec<_>, _> = reader.rows(&SasReadOptions::default()).unwrap().collect();
+        let err = result.unwrap_err();
+        assert_eq!(err.kind, ReadStatError::Read);
+        assert_eq!(err.page, Some(last_page));
+        assert_eq!(err.offset, Some(1024 + last_page * 4096));
+        assert!(err.io_error().is_some());
+    }
+
+    #[test]
+    fn test_from_bytes_with_limits() {
+        let bytes = sample_fixture().to_bytes();
+        let open = |limits: ReaderLimitsBuilder| {
+            Sas7bdatReader::from_bytes_with_limits(bytes.clone(), limits.build().unwrap())
+        };
+
+        let mut limits = ReaderLimits::builder();
+        limits
+            .max_columns(Some(3))
+            .max_row_length(Some(24))
+            .max_pages(Some(16));
+        assert!(open(limits).is_ok());
+
+        let mut limits = ReaderLimits::builder();
+        limits.max_columns(Some(2));
+        let err = open(limits).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::TooManyColumns);
+        assert_eq!(err.page, Some(0));
+
+        let mut limits = ReaderLimits::builder();
+        limits.max_row_length(Some(16));
+        assert_eq!(
+            open(limits).err().map(|err| err.kind),
+            Some(ReadStatError::RowIsTooWideForPage)
+        );
+
+        let mut limits = ReaderLimits::builder();
+        limits.max_decompressed_row_size(Some(16));
+        assert!(open(limits.clone()).is_ok());
+        let compressed = sample_fixture()
+            .with_compression(ReadStatCompression::Rows)
+            .to_bytes();
+        assert_eq!(
+            Sas7bdatReader::from_bytes_with_limits(compressed, limits.build().unwrap())
+                .err()
+                .map(|err| err.kind),
+            Some(ReadStatError::RowIsTooWideForPage)
+        );
+
+        let mut limits = ReaderLimits::builder();
+        limits.max_column_text_size(Some(16));
+        assert_eq!(
+            open(limits).err().map(|err| err.kind),
+            Some(ReadStatError::ColumnTextTooLarge)
+        );
+
+        let mut limits = ReaderLimits::builder();
+        limits.max_pages(Some(1));
+        let err = open(limits).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::TooManyPages);
+        assert_eq!(err.offset, Some(0));
+    }
+
+    #[test]
+    fn test_progress_handler() {
+        let path = sample_fixture().write_temp("reader_progress");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        let page_count = reader.context().page_count;
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        reader.set_progress_handler(1, move |progress| {
+            log.lock().unwrap().push(*progress);
+            ReadStatHandler::Ok
+        });
+        reader.read_rows(&SasReadOptions::default()).unwrap();
+
+        let seen = seen.lock().unwrap();
+        let last = seen.last().unwrap();
+        assert_eq!(last.rows_read, 250);
+        assert_eq!(last.row_count, 250);
+        assert_eq!(last.page_count, page_count);
+        assert_eq!(last.fraction(), 1.0);
+        assert_eq!(last.bytes_read, 1024 + last.pages_read * 4096);
+        assert!(seen
+            .windows(2)
+            .all(|pair| pair[0].rows_read <= pair[1].rows_read));
+    }
+
+    #[test]
+    fn test_progress_handler_abort() {
+        let path = sample_fixture().write_temp("reader_progress_abort");
+        let mut reader = Sas7bdatReader::open(&path).unwrap();
+        reader.set_progress_handler(1, |progress| {
+            if progress.rows_read > 0 {
+                ReadStatHandler::Abort
+            } else {
+                ReadStatHandler::Ok
+            }
+        });
+        let mut rows = reader.rows(&SasReadOptions::default()).unwrap();
+        assert_eq!(
+            rows.next().map(|row| row.map_err(|err| err.kind)),
+            Some(Err(ReadStatError::UserAbort))
+        );
+        assert!(rows.next().is_none());
+    }
+
+    #[test]
+    fn test_open_missing_file() {
+        let err = Sas7bdatReader::open("/nonexistent/file.sas7bdat").unwrap_err();
+        assert_eq!(err.kind, ReadStatError::Open);
+        assert_eq!(
+            err.io_error().map(|err| err.kind()),
+            Some(std::io::ErrorKind::NotFound)
+        );
+    }
+}
@@ -0,0 +1,280 @@
+use crate::sas::cursor::SasCursor;
+use crate::sas::sas7bdat::context::Sas7bdatContext;
+use crate::sas::sas7bdat::text::{decode_sas_str, trim_padding};
+use crate::types::{
+    ReadStatType, ReadStatValue, ReadStatValueType, ReadStatVariable, SasEndian, SasMissing,
+};
+use std::borrow::Cow;
+
+/// Decode a numeric value stored in `data`. SAS drops the least significant bytes of doubles
+/// stored in fewer than 8 bytes, so the bytes present are the most significant ones. Bytes past
+/// the eighth are ignored.
+pub fn decode_sas_double(data: &[u8], endianness: &SasEndian) -> u64 {
+    let width = data.len().min(8);
+    SasCursor::new(data, endianness.clone())
+        .read_truncated_f64(width)
+        .map_or(0, f64::to_bits)
+}
+
+/// Decode the selected variables of a single row.
+///
+/// # Arguments
+/// * `row` - The uncompressed bytes of the row, `ctx.row_length` long.
+/// * `variables` - The variables of the file, in file order.
+/// * `selection` - Indexes into `variables` of the values to decode, in output order.
+///
+/// # Original C function
+/// `sas7bdat_parse_single_row` in readstat's `sas7bdat_read.c`.
+pub fn decode_row(
+    row: &[u8],
+    ctx: &Sas7bdatContext,
+    variables: &[ReadStatVariable],
+    selection: &[usize],
+) -> Vec<ReadStatValue> {
+    selection
+        .iter()
+        .map(|&index| decode_value(row, ctx, &variables[index]))
+        .collect()
+}
+
+/// Decode the value of one variable from a row.
+pub fn decode_value(
+    row: &[u8],
+    ctx: &Sas7bdatContext,
+    variable: &ReadStatVariable,
+) -> ReadStatValue {
+    SasValueRef::new(row, ctx, variable).to_value()
+}
+
+/// The selected values of a row, borrowed from the page or decompression buffer the row is
+/// stored in. Nothing is decoded until a value is asked for.
+#[derive(Debug, Clone, Copy)]
+pub struct SasRowRef<'a> {
+    row: &'a [u8],
+    ctx: &'a Sas7bdatContext,
+    variables: &'a [ReadStatVariable],
+    selection: &'a [usize],
+}
+
+impl<'a> SasRowRef<'a> {
+    /// # Arguments
+    /// * `row` - The uncompressed bytes of the row, `ctx.row_length` long.
+    /// * `variables` - The variables of the file, in file order.
+    /// * `selection` - Indexes into `variables` of the selected values, in output order.
+    pub fn new(
+        row: &'a [u8],
+        ctx: &'a Sas7bdatContext,
+        variables: &'a [ReadStatVariable],
+        selection: &'a [usize],
+    ) -> Self {
+        Self {
+            row,
+            ctx,
+            variables,
+            selection,
+        }
+    }
+
+    /// The bytes of the whole row, as stored once decompressed.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.row
+    }
+
+    /// The number of selected values.
+    pub fn len(&self) -> usize {
+        self.selection.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.selection.is_empty()
+    }
+
+    /// Return the selected value at `index`, in output order.
+    pub fn get(&self, index: usize) -> Option<SasValueRef<'a>> {
+        let variable = &self.variables[*self.selection.get(index)?];
+        Some(SasValueRef::new(self.row, self.ctx, variable))
+    }
+
+    /// Iterate over the selected values, in output order.
+    pub fn values(&self) -> impl Iterator<Item = SasValueRef<'a>> + 'a {
+        let (row, ctx, variables) = (self.row, self.ctx, self.variables);
+        self.selection
+            .iter()
+            .map(move |&index| SasValueRef::new(row, ctx, &variables[index]))
+    }
+
+    /// Decode every selected value into an owned row, as `Sas7bdatReader::rows` returns it.
+    pub fn to_values(&self) -> Vec<ReadStatValue> {
+        self.values().map(|value| value.to_value()).collect()
+    }
+}
+
+/// The value of one variable, borrowed from the row it is stored in and decoded on demand.
+#[derive(Debug, Clone, Copy)]
+pub struct SasValueRef<'a> {
+    data: &'a [u8],
+    ctx: &'a Sas7bdatContext,
+    variable: &'a ReadStatVariable,
+}
+
+impl<'a> SasValueRef<'a> {
+    /// Borrow the value of `variable` from `row`.
+    pub fn new(row: &'a [u8], ctx: &'a Sas7bdatContext, variable: &'a ReadStatVariable) -> Self {
+        let start = variable.offset as usize;
+        Self {
+            data: &row[start..start + variable.storage_width as usize],
+            ctx,
+            variable,
+        }
+    }
+
+    pub fn variable(&self) -> &'a ReadStatVariable {
+        self.variable
+    }
+
+    pub fn is_string(&self) -> bool {
+        self.variable.type_ == ReadStatType::String
+    }
+
+    /// The stored bytes of the value: the text of character values without its padding, and
+    /// the possibly truncated double of numeric values.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        if self.is_string() {
+            trim_padding(self.data)
+        } else {
+            self.data
+        }
+    }
+
+    /// Decode a character value, borrowing it when its bytes are already UTF-8.
+    ///
+    /// # Returns
+    /// `None` for numeric values.
+    pub fn as_str(&self) -> Option<Cow<'a, str>> {
+        self.is_string()
+            .then(|| decode_sas_str(self.as_bytes(), &self.ctx.encoding))
+    }
+
+    /// Decode a numeric value. Missing values, tagged or not, are NaN.
+    ///
+    /// # Returns
+    /// `None` for character values.
+    pub fn as_f64(&self) -> Option<f64> {
+        if self.is_string() {
+            return None;
+        }
+        let value = f64::from_bits(decode_sas_double(self.data, &self.ctx.endianness));
+        Some(if value.is_nan() { f64::NAN } else { value })
+    }
+
+    /// The kind of missing value a numeric value holds, or `None` for character values and
+    /// numbers that are not missing.
+    pub fn missing_kind(&self) -> Option<SasMissing> {
+        if self.is_string() {
+            return None;
+        }
+        SasMissing::from_nan_bits(decode_sas_double(self.data, &self.ctx.endianness))
+    }
+
+    /// Decode the value into an owned `ReadStatValue`.
+    pub fn to_value(&self) -> ReadStatValue {
+        match self.as_f64() {
+            Some(value) => ReadStatValue::new(
+                ReadStatType::Double,
+                self.missing_kind(),
+                ReadStatValueType::Double(value),
+            ),
+            None => ReadStatValue::new(
+                ReadStatType::String,
+                None,
+                ReadStatValueType::String(self.as_str().map(Cow::into_owned).unwrap_or_default()),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SasHeaderInfo;
+
+    fn context(endianness: SasEndian) -> Sas7bdatContext {
+        Sas7bdatContext::from_header(
+            &SasHeaderInfo::builder()
+                .endianness(endianness)
+                .encoding("UTF-8".to_string())
+                .build()
+                .unwrap(),
+        )
+    }
+
+    fn variable(type_: ReadStatType, offset: u64, width: u64) -> ReadStatVariable {
+        ReadStatVariable::builder()
+            .type_(type_)
+            .offset(offset)
+            .storage_width(width)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_decode_sas_double() {
+        let bits = 1.5f64.to_bits();
+        assert_eq!(
+            decode_sas_double(&bits.to_le_bytes(), &SasEndian::Little),
+            bits
+        );
+        assert_eq!(
+            decode_sas_double(&bits.to_be_bytes(), &SasEndian::Big),
+            bits
+        );
+    }
+
+    #[test]
+    fn test_decode_truncated_double() {
+        // 1.5 is exactly representable in the 3 most significant bytes
+        let bits = 1.5f64.to_bits();
+        assert_eq!(
+            decode_sas_double(&bits.to_le_bytes()[5..], &SasEndian::Little),
+            bits
+        );
+        assert_eq!(
+            decode_sas_double(&bits.to_be_bytes()[..3], &SasEndian::Big),
+            bits
+        );
+    }
+
+    #[test]
+    fn test_decode_row() {
+        let ctx = context(SasEndian::Little);
+        let variables = vec![
+            variable(ReadStatType::Double, 0, 8),
+            variable(ReadStatType::String, 8, 4),
+        ];
+        let mut row = 2.25f64.to_le_bytes().to_vec();
+        row.extend_from_slice(b"ab  ");
+
+        let values = decode_row(&row, &ctx, &variables, &[1, 0]);
+        assert_eq!(values[0].value, ReadStatValueType::String("ab".to_string()));
+        assert_eq!(values[1].value, ReadStatValueType::Double(2.25));
+        assert_eq!(values[1].missing, None);
+    }
+
+    #[test]
+    fn test_decode_missing_values() {
+        let ctx = context(SasEndian::Big);
+        let variables = vec![
+            variable(ReadStatType::Double, 0, 8),
+            variable(ReadStatType::Double, 8, 8),
+        ];
+        // System missing (.) and .A as SAS writes them
+        let mut row = vec![0xFF, 0xFF, 0xFE, 0, 0, 0, 0, 0];
+        row.extend_from_slice(&[0xFF, 0xFF, 0xFD, 0, 0, 0, 0, 0]);
+
+        let values = decode_row(&row, &ctx, &variables, &[0, 1]);
+        assert!(values[0].is_system_missing());
+        assert_eq!(values[0].missing_kind(), Some(SasMissing::System));
+        assert!(values[1].is_missing());
+        assert_eq!(values[1].missing, Some(SasMissing::Letter('A')));
+    }
+}
@@ -0,0 +1,70 @@
+use crate::types::{ReadStatError, ReadStatIO, ReadStatIoFlags, SasError};
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Where the bytes of a sas7bdat file come from. This plays the role of readstat's
+/// `readstat_io_t`: the parser only ever asks for a range of bytes, so the backend is chosen
+/// once, when the reader is opened.
+pub enum Sas7bdatSource {
+    /// Ranges are read into freshly allocated buffers from any `Read + Seek` source, such as a
+    /// file or an in-memory `Cursor<Vec<u8>>`.
+    Io(Box<dyn ReadStatIO + Send>),
+
+    /// Ranges are borrowed from a memory map of the whole file, without copying.
+    #[cfg(feature = "mmap")]
+    Mmap(Mmap),
+}
+
+impl Sas7bdatSource {
+    /// Return the `length` bytes starting at `offset`.
+    ///
+    /// # Returns
+    /// An error of kind `ReadStatError::Seek` if the offset cannot be reached, or
+    /// `ReadStatError::Read` if the file ends before the range does, carrying the offset.
+    pub fn read_at(&mut self, offset: u64, length: usize) -> Result<Cow<'_, [u8]>, SasError> {
+        let at_offset = |err: SasError| err.with_offset(offset);
+        match self {
+            Self::Io(io) => {
+                let start = i64::try_from(offset)
+                    .map_err(|_| SasError::new(ReadStatError::Seek))
+                    .map_err(at_offset)?;
+                io.seek(start, ReadStatIoFlags::SeekSet)
+                    .map_err(at_offset)?;
+                let mut buffer = vec![0; length];
+                io.read_exact(&mut buffer).map_err(at_offset)?;
+                Ok(Cow::Owned(buffer))
+            }
+            #[cfg(feature = "mmap")]
+            Self::Mmap(map) => {
+                let start =
+                    usize::try_from(offset).map_err(|_| at_offset(ReadStatError::Seek.into()))?;
+                start
+                    .checked_add(length)
+                    .and_then(|end| map.get(start..end))
+                    .map(Cow::Borrowed)
+                    .ok_or_else(|| at_offset(ReadStatError::Read.into()))
+            }
+        }
+    }
+
+    /// Return the size of the file in bytes.
+    pub fn size(&mut self) -> Result<u64, SasError> {
+        match self {
+            Self::Io(io) => io.size(),
+            #[cfg(feature = "mmap")]
+            Self::Mmap(map) => Ok(map.len() as u64),
+        }
+    }
+}
+
+impl fmt::Debug for Sas7bdatSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(_) => f.write_str("Io"),
+            #[cfg(feature = "mmap")]
+            Self::Mmap(map) => f.debug_tuple("Mmap").field(map).finish(),
+        }
+    }
+}
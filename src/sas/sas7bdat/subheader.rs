@@ -0,0 +1,610 @@
+use crate::sas::sas7bdat::context::Sas7bdatContext;
+use crate::sas::sas7bdat::text::resolve_text_ref;
+use crate::types::{
+    ReadStatCompression, ReadStatError, ReadStatType, ReadStatVariable, SasColumnType,
+    SasCompressionSignature, SasEndian, SasError, SasSubheaderPointer, SasSubheaderSignature,
+    SasTextRef,
+};
+
+/// What a subheader pointer refers to, decided from the pointer flags and the signature at the
+/// start of the subheader.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SasSubheaderKind {
+    RowSize,
+    ColumnSize,
+    Counts,
+    ColumnFormat,
+    ColumnAttributes,
+    ColumnText,
+    ColumnList,
+    ColumnName,
+
+    /// A column-level signature that readstat recognises but does not use (eg 0xFFFFFFFA).
+    Unknown,
+
+    /// A single uncompressed row stored as a subheader of a compressed file.
+    Row,
+
+    /// A single row compressed with the file's compression scheme.
+    CompressedRow,
+
+    /// An empty or truncated pointer that should be skipped.
+    Empty,
+}
+
+impl SasSubheaderKind {
+    /// Return true if the subheader holds an observation rather than metadata.
+    pub fn is_row(&self) -> bool {
+        matches!(
+            self,
+            SasSubheaderKind::Row | SasSubheaderKind::CompressedRow
+        )
+    }
+}
+
+/// Read the subheader pointer table that follows the header of a meta or mix page.
+///
+/// # Original C function
+/// `sas7bdat_parse_subheader_pointer` and `sas7bdat_validate_subheader_pointer` in readstat's
+/// `sas7bdat_read.c`.
+pub fn read_subheader_pointers(
+    page: &[u8],
+    subheader_count: usize,
+    ctx: &Sas7bdatContext,
+) -> Result<Vec<SasSubheaderPointer>, ReadStatError> {
+    let pointers = read_subheader_pointer_table(page, subheader_count, ctx)?;
+    if pointers
+        .iter()
+        .any(|pointer| !is_valid_subheader_pointer(pointer, page, subheader_count, ctx))
+    {
+        return Err(ReadStatError::Parse);
+    }
+    Ok(pointers)
+}
+
+/// Read the subheader pointer table of a page without checking where the pointers point.
+///
+/// # Returns
+/// `ReadStatError::Parse` if the table does not fit in the page.
+pub(crate) fn read_subheader_pointer_table(
+    page: &[u8],
+    subheader_count: usize,
+    ctx: &Sas7bdatContext,
+) -> Result<Vec<SasSubheaderPointer>, ReadStatError> {
+    if subheader_table_end(subheader_count, ctx) > page.len() {
+        return Err(ReadStatError::Parse);
+    }
+
+    let mut pointers = Vec::with_capacity(subheader_count);
+    for i in 0..subheader_count {
+        let offset = ctx.page_header_size + i * ctx.subheader_pointer_size;
+        let int_size = ctx.int_size();
+        let mut flags = ctx.cursor(page, offset + 2 * int_size)?;
+        pointers.push(SasSubheaderPointer::new(
+            ctx.read_int(page, offset)?,
+            ctx.read_int(page, offset + int_size)?,
+            flags.read_u8()?,
+            flags.read_u8()? == 1,
+        ));
+    }
+    Ok(pointers)
+}
+
+/// Return true if `pointer` is empty or points between the end of the pointer table and the
+/// end of the page.
+pub(crate) fn is_valid_subheader_pointer(
+    pointer: &SasSubheaderPointer,
+    page: &[u8],
+    subheader_count: usize,
+    ctx: &Sas7bdatContext,
+) -> bool {
+    let end = pointer.offset.checked_add(pointer.length);
+    pointer.is_empty()
+        || (pointer.offset >= subheader_table_end(subheader_count, ctx) as u64
+            && end.is_some_and(|end| end <= page.len() as u64))
+}
+
+fn subheader_table_end(subheader_count: usize, ctx: &Sas7bdatContext) -> usize {
+    ctx.page_header_size + subheader_count * ctx.subheader_pointer_size
+}
+
+/// Return the bytes of the subheader behind `pointer`. The bounds of empty pointers are not
+/// validated by `read_subheader_pointers`, so they get no bytes.
+pub fn subheader_data<'a>(page: &'a [u8], pointer: &SasSubheaderPointer) -> &'a [u8] {
+    if pointer.is_empty() {
+        return &[];
+    }
+    let start = pointer.offset as usize;
+    let end = start.saturating_add(pointer.length as usize);
+    page.get(start..end).unwrap_or_default()
+}
+
+/// Decide what the subheader behind `pointer` holds.
+pub fn classify_subheader(
+    page: &[u8],
+    pointer: &SasSubheaderPointer,
+    ctx: &Sas7bdatContext,
+) -> SasSubheaderKind {
+    if pointer.is_empty() {
+        return SasSubheaderKind::Empty;
+    }
+    if pointer.is_compressed_row() {
+        return SasSubheaderKind::CompressedRow;
+    }
+
+    let kind = if pointer.length >= ctx.int_size() as u64 {
+        read_signature(page, pointer.offset as usize, ctx)
+            .ok()
+            .and_then(signature_kind)
+    } else {
+        None
+    };
+
+    match kind {
+        Some(kind) => kind,
+        None if ctx.compression != ReadStatCompression::None && pointer.is_compressed_data => {
+            SasSubheaderKind::Row
+        }
+        None => SasSubheaderKind::Unknown,
+    }
+}
+
+/// Read the signature at the start of a subheader. Signatures are stored in the file's word
+/// size; in 64-bit big-endian files the meaningful half of a column signature comes second.
+fn read_signature(page: &[u8], offset: usize, ctx: &Sas7bdatContext) -> Result<u32, ReadStatError> {
+    let signature = ctx.read4(page, offset)?;
+    if ctx.is_64bit && ctx.endianness == SasEndian::Big && signature == 0xFFFFFFFF {
+        return ctx.read4(page, offset + 4);
+    }
+    Ok(signature)
+}
+
+fn signature_kind(signature: u32) -> Option<SasSubheaderKind> {
+    let signatures = SasSubheaderSignature::default();
+    let kind = if signature == signatures.row_size {
+        SasSubheaderKind::RowSize
+    } else if signature == signatures.column_size {
+        SasSubheaderKind::ColumnSize
+    } else if signature == signatures.counts {
+        SasSubheaderKind::Counts
+    } else if signature == signatures.column_format {
+        SasSubheaderKind::ColumnFormat
+    } else if signature == signatures.column_attrs {
+        SasSubheaderKind::ColumnAttributes
+    } else if signature == signatures.column_text {
+        SasSubheaderKind::ColumnText
+    } else if signature == signatures.column_list {
+        SasSubheaderKind::ColumnList
+    } else if signature == signatures.column_name {
+        SasSubheaderKind::ColumnName
+    } else if signature & signatures.column_mask == signatures.column_mask {
+        SasSubheaderKind::Unknown
+    } else {
+        return None;
+    };
+    Some(kind)
+}
+
+/// Offset, width and type of one column within a row, from the column attributes subheader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasColumnAttributes {
+    pub offset: u64,
+    pub width: u32,
+    pub type_: ReadStatType,
+}
+
+/// Format and label of one column, from the column format subheader.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasColumnFormat {
+    pub format_ref: SasTextRef,
+    pub informat_ref: SasTextRef,
+    pub label_ref: SasTextRef,
+    pub width: u16,
+    pub decimals: u16,
+    pub informat_width: u16,
+    pub informat_decimals: u16,
+}
+
+/// Accumulates the metadata subheaders of a sas7bdat file, which may be spread over several
+/// pages, and turns them into variables once they have all been seen.
+#[derive(Debug, Clone, Default)]
+pub struct Sas7bdatMetadataParser {
+    pub text_blobs: Vec<Vec<u8>>,
+    pub compression: Option<ReadStatCompression>,
+    pub row_length: Option<u64>,
+    pub row_count: Option<u64>,
+    pub mix_page_row_count: Option<u64>,
+    pub file_label_ref: Option<SasTextRef>,
+    pub column_count: Option<u64>,
+    pub name_refs: Vec<SasTextRef>,
+    pub attributes: Vec<SasColumnAttributes>,
+    pub formats: Vec<SasColumnFormat>,
+}
+
+impl Sas7bdatMetadataParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse one metadata subheader. Row subheaders and unused subheaders are ignored.
+    pub fn parse_subheader(
+        &mut self,
+        kind: &SasSubheaderKind,
+        data: &[u8],
+        ctx: &Sas7bdatContext,
+    ) -> Result<(), ReadStatError> {
+        match kind {
+            SasSubheaderKind::RowSize => self.parse_row_size(data, ctx),
+            SasSubheaderKind::ColumnSize => self.parse_column_size(data, ctx),
+            SasSubheaderKind::ColumnText => self.parse_column_text(data, ctx),
+            SasSubheaderKind::ColumnName => self.parse_column_name(data, ctx),
+            SasSubheaderKind::ColumnAttributes => self.parse_column_attributes(data, ctx),
+            SasSubheaderKind::ColumnFormat => self.parse_column_format(data, ctx),
+            _ => Ok(()),
+        }
+    }
+
+    /// Return true once the row layout and the name and attributes of every column are known.
+    pub fn is_complete(&self) -> bool {
+        match (self.row_length, self.column_count) {
+            (Some(_), Some(column_count)) => {
+                self.name_refs.len() as u64 >= column_count
+                    && self.attributes.len() as u64 >= column_count
+            }
+            _ => false,
+        }
+    }
+
+    /// # Original C function
+    /// `sas7bdat_parse_row_size_subheader` in readstat's `sas7bdat_read.c`.
+    fn parse_row_size(&mut self, data: &[u8], ctx: &Sas7bdatContext) -> Result<(), ReadStatError> {
+        let int_size = ctx.int_size();
+        if data.len() < 16 * int_size {
+            return Err(ReadStatError::Parse);
+        }
+
+        let row_length = ctx.read_int(data, 5 * int_size)?;
+        // Whether rows are compressed is only known from a later subheader, see `finish`
+        ctx.limits.check_row_length(row_length, false)?;
+        self.row_length = Some(row_length);
+        self.row_count = Some(ctx.read_int(data, 6 * int_size)?);
+        self.mix_page_row_count = Some(ctx.read_int(data, 15 * int_size)?);
+        if data.len() >= 130 {
+            self.file_label_ref = Some(ctx.read_text_ref(data, data.len() - 130)?);
+        }
+        Ok(())
+    }
+
+    /// # Original C function
+    /// `sas7bdat_parse_column_size_subheader` in readstat's `sas7bdat_read.c`.
+    fn parse_column_size(
+        &mut self,
+        data: &[u8],
+        ctx: &Sas7bdatContext,
+    ) -> Result<(), ReadStatError> {
+        let int_size = ctx.int_size();
+        if data.len() < 2 * int_size {
+            return Err(ReadStatError::Parse);
+        }
+        let column_count = ctx.read_int(data, int_size)?;
+        ctx.limits.check_columns(column_count)?;
+        self.column_count = Some(column_count);
+        Ok(())
+    }
+
+    /// # Original C function
+    /// `sas7bdat_parse_column_text_subheader` in readstat's `sas7bdat_read.c`.
+    fn parse_column_text(
+        &mut self,
+        data: &[u8],
+        ctx: &Sas7bdatContext,
+    ) -> Result<(), ReadStatError> {
+        let blob = data.get(ctx.int_size()..).ok_or(ReadStatError::Parse)?;
+        let text_size: usize = self.text_blobs.iter().map(Vec::len).sum();
+        ctx.limits
+            .check_column_text_size(text_size.saturating_add(blob.len()))?;
+        let blob = blob.to_vec();
+
+        if self.compression.is_none() && blob.len() >= 20 {
+            let signature = &blob[12..20];
+            if signature == SasCompressionSignature::Rle.to_string().as_bytes() {
+                self.compression = Some(ReadStatCompression::Rows);
+            } else if signature == SasCompressionSignature::Rdc.to_string().as_bytes() {
+                self.compression = Some(ReadStatCompression::Binary);
+            }
+        }
+
+        self.text_blobs.push(blob);
+        Ok(())
+    }
+
+    /// # Original C function
+    /// `sas7bdat_parse_column_name_subheader` in readstat's `sas7bdat_read.c`.
+    fn parse_column_name(
+        &mut self,
+        data: &[u8],
+        ctx: &Sas7bdatContext,
+    ) -> Result<(), ReadStatError> {
+        let int_size = ctx.int_size();
+        let count = data.len().saturating_sub(2 * int_size + 12) / 8;
+        ctx.limits
+            .check_columns((self.name_refs.len() + count) as u64)?;
+        for i in 0..count {
+            let offset = int_size + 8 + i * 8;
+            self.name_refs.push(ctx.read_text_ref(data, offset)?);
+        }
+        Ok(())
+    }
+
+    /// # Original C function
+    /// `sas7bdat_parse_column_attributes_subheader` in readstat's `sas7bdat_read.c`.
+    fn parse_column_attributes(
+        &mut self,
+        data: &[u8],
+        ctx: &Sas7bdatContext,
+    ) -> Result<(), ReadStatError> {
+        let int_size = ctx.int_size();
+        let entry_size = int_size + 8;
+        let count = data.len().saturating_sub(2 * int_size + 12) / entry_size;
+        ctx.limits
+            .check_columns((self.attributes.len() + count) as u64)?;
+        for i in 0..count {
+            let offset = int_size + 8 + i * entry_size;
+            let type_ = match ctx.cursor(data, offset + int_size + 6)?.read_u8()? {
+                t if t == SasColumnType::Numeric as u8 => ReadStatType::Double,
+                t if t == SasColumnType::Character as u8 => ReadStatType::String,
+                _ => return Err(ReadStatError::Parse),
+            };
+            self.attributes.push(SasColumnAttributes {
+                offset: ctx.read_int(data, offset)?,
+                width: ctx.read4(data, offset + int_size)?,
+                type_,
+            });
+        }
+        Ok(())
+    }
+
+    /// # Original C function
+    /// `sas7bdat_parse_column_format_subheader` in readstat's `sas7bdat_read.c`.
+    fn parse_column_format(
+        &mut self,
+        data: &[u8],
+        ctx: &Sas7bdatContext,
+    ) -> Result<(), ReadStatError> {
+        let int_size = ctx.int_size();
+        if data.len() < 34 + 3 * int_size {
+            return Err(ReadStatError::Parse);
+        }
+        // readstat ignores informats; their reference precedes the format and label references,
+        // and their width and decimals follow those of the format
+        let mut widths = ctx.cursor(data, 3 * int_size)?;
+        self.formats.push(SasColumnFormat {
+            format_ref: ctx.read_text_ref(data, 22 + 3 * int_size)?,
+            informat_ref: ctx.read_text_ref(data, 16 + 3 * int_size)?,
+            label_ref: ctx.read_text_ref(data, 28 + 3 * int_size)?,
+            width: widths.read_u16()?,
+            decimals: widths.read_u16()?,
+            informat_width: widths.read_u16()?,
+            informat_decimals: widths.read_u16()?,
+        });
+        Ok(())
+    }
+
+    /// Fill in the row layout of `ctx` and build the file's variables.
+    ///
+    /// # Returns
+    /// The variables in file order and the file label. Errors about a single variable name it.
+    pub fn finish(
+        &self,
+        ctx: &mut Sas7bdatContext,
+    ) -> Result<(Vec<ReadStatVariable>, String), SasError> {
+        let row_length = self.row_length.ok_or(ReadStatError::Parse)?;
+        let column_count = self.column_count.unwrap_or(self.attributes.len() as u64) as usize;
+        if self.name_refs.len() < column_count || self.attributes.len() < column_count {
+            return Err(ReadStatError::ColumnCountMismatch.into());
+        }
+
+        ctx.compression = self
+            .compression
+            .clone()
+            .unwrap_or(ReadStatCompression::None);
+        // Uncompressed rows are stored whole on a page, so a longer row can only be corrupt
+        if ctx.compression == ReadStatCompression::None
+            && row_length > ctx.page_size.saturating_sub(ctx.page_header_size) as u64
+        {
+            return Err(ReadStatError::RowIsTooWideForPage.into());
+        }
+        ctx.limits
+            .check_row_length(row_length, ctx.compression != ReadStatCompression::None)?;
+        ctx.row_length = row_length as usize;
+        ctx.row_count = self.row_count.unwrap_or(0);
+        ctx.mix_page_row_count = self.mix_page_row_count.unwrap_or(0);
+
+        let mut variables = Vec::with_capacity(column_count);
+        for index in 0..column_count {
+            let name = resolve_text_ref(&self.text_blobs, &self.name_refs[index], &ctx.encoding)?;
+            let variable = self
+                .build_variable(index, &name, row_length, ctx)
+                .map_err(|err| SasError::from(err).with_variable(name))?;
+            variables.push(variable);
+        }
+
+        let file_label = match &self.file_label_ref {
+            Some(text_ref) => resolve_text_ref(&self.text_blobs, text_ref, &ctx.encoding)?,
+            None => String::new(),
+        };
+
+        Ok((variables, file_label))
+    }
+
+    /// Build the variable at `index` in file order, named `name`.
+    fn build_variable(
+        &self,
+        index: usize,
+        name: &str,
+        row_length: u64,
+        ctx: &Sas7bdatContext,
+    ) -> Result<ReadStatVariable, ReadStatError> {
+        let attributes = &self.attributes[index];
+        let end = attributes.offset.checked_add(attributes.width as u64);
+        if end.is_none_or(|end| end > row_length) {
+            return Err(ReadStatError::RowWidthMismatch);
+        }
+        if attributes.type_ == ReadStatType::Double && attributes.width > 8 {
+            return Err(ReadStatError::Parse);
+        }
+
+        let (format, informat, label, display_width, display_decimals) = match self
+            .formats
+            .get(index)
+        {
+            Some(format) => (
+                format_string(
+                    &resolve_text_ref(&self.text_blobs, &format.format_ref, &ctx.encoding)?,
+                    format.width,
+                    format.decimals,
+                ),
+                // Informats are informational only, so an unreadable reference is not
+                // worth failing the whole file over
+                resolve_text_ref(&self.text_blobs, &format.informat_ref, &ctx.encoding)
+                    .map(|informat| {
+                        format_string(&informat, format.informat_width, format.informat_decimals)
+                    })
+                    .unwrap_or_default(),
+                resolve_text_ref(&self.text_blobs, &format.label_ref, &ctx.encoding)?,
+                format.width as u64,
+                format.decimals as u64,
+            ),
+            None => (String::new(), String::new(), String::new(), 0, 0),
+        };
+
+        ReadStatVariable::builder()
+            .type_(attributes.type_.clone())
+            .index(index as u32)
+            .name(name.to_string())
+            .format(format)
+            .informat(informat)
+            .label(label)
+            .offset(attributes.offset)
+            .storage_width(attributes.width as u64)
+            .user_width(attributes.width as u64)
+            .measure(None)
+            .alignment(None)
+            .display_width(display_width)
+            .display_decimals(display_decimals)
+            .index_after_skipping(index as u32)
+            .build()
+            .map_err(|_| ReadStatError::Parse)
+    }
+}
+
+/// Combine a format name with its width and number of decimals the way SAS displays it, eg
+/// `DATE9.`, `BEST12.` or `COMMA12.2`.
+fn format_string(name: &str, width: u16, decimals: u16) -> String {
+    if name.is_empty() || (width == 0 && decimals == 0) {
+        return name.to_string();
+    }
+
+    let mut format = name.to_string();
+    if width > 0 {
+        format.push_str(&width.to_string());
+    }
+    format.push('.');
+    if decimals > 0 {
+        format.push_str(&decimals.to_string());
+    }
+    format
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SasHeaderInfo;
+
+    fn context(is_64bit: bool, endianness: SasEndian) -> Sas7bdatContext {
+        let header = if is_64bit {
+            SasHeaderInfo::builder()
+                .is_64bit(true)
+                .page_header_size(40)
+                .subheader_pointer_size(24)
+                .endianness(endianness)
+                .build()
+                .unwrap()
+        } else {
+            SasHeaderInfo::builder()
+                .endianness(endianness)
+                .build()
+                .unwrap()
+        };
+        Sas7bdatContext::from_header(&header)
+    }
+
+    #[test]
+    fn test_signature_kind() {
+        assert_eq!(signature_kind(0xF7F7F7F7), Some(SasSubheaderKind::RowSize));
+        assert_eq!(
+            signature_kind(0xFFFFFFFD),
+            Some(SasSubheaderKind::ColumnText)
+        );
+        assert_eq!(signature_kind(0xFFFFFFFA), Some(SasSubheaderKind::Unknown));
+        assert_eq!(signature_kind(0x12345678), None);
+    }
+
+    #[test]
+    fn test_read_signature_64bit() {
+        let little = context(true, SasEndian::Little);
+        let big = context(true, SasEndian::Big);
+        let little_text = [0xFD, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF];
+        let big_text = [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFD];
+        assert_eq!(read_signature(&little_text, 0, &little), Ok(0xFFFFFFFD));
+        assert_eq!(read_signature(&big_text, 0, &big), Ok(0xFFFFFFFD));
+        assert_eq!(read_signature(&big_text, 6, &big), Err(ReadStatError::Read));
+    }
+
+    #[test]
+    fn test_classify_compressed_row() {
+        let ctx = context(false, SasEndian::Little);
+        let page = vec![0; 64];
+        let pointer = SasSubheaderPointer::new(40, 10, 4, true);
+        assert_eq!(
+            classify_subheader(&page, &pointer, &ctx),
+            SasSubheaderKind::CompressedRow
+        );
+        let pointer = SasSubheaderPointer::new(40, 0, 0, false);
+        assert_eq!(
+            classify_subheader(&page, &pointer, &ctx),
+            SasSubheaderKind::Empty
+        );
+    }
+
+    #[test]
+    fn test_format_string() {
+        assert_eq!(format_string("DATE", 9, 0), "DATE9.");
+        assert_eq!(format_string("COMMA", 12, 2), "COMMA12.2");
+        assert_eq!(format_string("$CHAR", 20, 0), "$CHAR20.");
+        assert_eq!(format_string("BEST", 0, 0), "BEST");
+        assert_eq!(format_string("", 8, 0), "");
+    }
+
+    #[test]
+    fn test_finish_requires_row_size() {
+        let mut ctx = context(false, SasEndian::Little);
+        let parser = Sas7bdatMetadataParser::new();
+        assert_eq!(
+            parser.finish(&mut ctx).map_err(|err| err.kind),
+            Err(ReadStatError::Parse)
+        );
+    }
+
+    #[test]
+    fn test_finish_column_count_mismatch() {
+        let mut ctx = context(false, SasEndian::Little);
+        let mut parser = Sas7bdatMetadataParser::new();
+        parser.row_length = Some(8);
+        parser.column_count = Some(2);
+        assert_eq!(
+            parser.finish(&mut ctx).map_err(|err| err.kind),
+            Err(ReadStatError::ColumnCountMismatch)
+        );
+    }
+}
@@ -0,0 +1,613 @@
+//! Synthetic sas7bdat files for tests. The layout follows what SAS writes closely enough for
+//! readstat to read them: one meta page holding the metadata subheaders, followed by data pages
+//! (or, for compressed files, meta pages of compressed row subheaders).
+
+use crate::sas::sas7bdat::header::SAS_HEADER_START_SIZE;
+use crate::types::{
+    ReadStatCompression, ReadStatType, ReadStatValueType, SasCompression, SasCompressionSignature,
+    SasEndian, SasFileMagicNumber, SasPageType, SasSubheaderSignature, SasTextRef,
+};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// A fixture file in a temporary directory, which is removed with the file when this is dropped.
+#[derive(Debug)]
+pub struct SasTempFile {
+    path: PathBuf,
+    _dir: TempDir,
+}
+
+impl Deref for SasTempFile {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl AsRef<Path> for SasTempFile {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// A column of a fixture file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasFixtureColumn {
+    pub name: String,
+    pub type_: ReadStatType,
+    pub width: u32,
+    pub format: String,
+    pub format_width: u16,
+    pub decimals: u16,
+    pub informat: String,
+    pub informat_width: u16,
+    pub label: String,
+}
+
+impl SasFixtureColumn {
+    /// An 8-byte numeric column displayed with the given format.
+    pub fn numeric(name: &str, format: &str, format_width: u16) -> Self {
+        Self {
+            name: name.to_string(),
+            type_: ReadStatType::Double,
+            width: 8,
+            format: format.to_string(),
+            format_width: if format.is_empty() { 0 } else { format_width },
+            decimals: 0,
+            informat: String::new(),
+            informat_width: 0,
+            label: String::new(),
+        }
+    }
+
+    /// A character column `width` bytes wide, displayed with the `$` format.
+    pub fn character(name: &str, width: u32) -> Self {
+        Self {
+            name: name.to_string(),
+            type_: ReadStatType::String,
+            width,
+            format: "$".to_string(),
+            format_width: width as u16,
+            decimals: 0,
+            informat: String::new(),
+            informat_width: 0,
+            label: String::new(),
+        }
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = label.to_string();
+        self
+    }
+
+    pub fn with_informat(mut self, informat: &str, width: u16) -> Self {
+        self.informat = informat.to_string();
+        self.informat_width = width;
+        self
+    }
+
+    pub fn with_decimals(mut self, decimals: u16) -> Self {
+        self.decimals = decimals;
+        self
+    }
+}
+
+/// A complete synthetic sas7bdat file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasFixture {
+    pub is_64bit: bool,
+    pub endianness: SasEndian,
+    pub compression: ReadStatCompression,
+    pub page_size: usize,
+    pub table_name: String,
+    pub file_label: String,
+    pub columns: Vec<SasFixtureColumn>,
+    pub rows: Vec<Vec<ReadStatValueType>>,
+}
+
+/// Builder for `SasFixture`. Unlike the derived builders, `build` cannot fail.
+#[derive(Debug, Clone)]
+pub struct SasFixtureBuilder {
+    fixture: SasFixture,
+}
+
+impl SasFixtureBuilder {
+    #[allow(clippy::wrong_self_convention)]
+    pub fn is_64bit(mut self, is_64bit: bool) -> Self {
+        self.fixture.is_64bit = is_64bit;
+        self
+    }
+
+    pub fn endianness(mut self, endianness: SasEndian) -> Self {
+        self.fixture.endianness = endianness;
+        self
+    }
+
+    pub fn columns(mut self, columns: Vec<SasFixtureColumn>) -> Self {
+        self.fixture.columns = columns;
+        self
+    }
+
+    pub fn rows(mut self, rows: Vec<Vec<ReadStatValueType>>) -> Self {
+        self.fixture.rows = rows;
+        self
+    }
+
+    pub fn build(self) -> SasFixture {
+        self.fixture
+    }
+}
+
+/// A subheader to be written on a meta page.
+struct FixtureSubheader {
+    data: Vec<u8>,
+    compression: u8,
+    is_compressed_data: bool,
+}
+
+impl SasFixture {
+    /// A fixture with a numeric and a character column and two rows.
+    pub fn builder() -> SasFixtureBuilder {
+        SasFixtureBuilder {
+            fixture: SasFixture {
+                is_64bit: false,
+                endianness: SasEndian::Little,
+                compression: ReadStatCompression::None,
+                page_size: 4096,
+                table_name: "FIXTURE".to_string(),
+                file_label: "Fixture dataset".to_string(),
+                columns: vec![
+                    SasFixtureColumn::numeric("X", "BEST", 12),
+                    SasFixtureColumn::character("NAME", 8),
+                ],
+                rows: vec![
+                    vec![
+                        ReadStatValueType::Double(1.0),
+                        ReadStatValueType::String("one".to_string()),
+                    ],
+                    vec![
+                        ReadStatValueType::Double(2.0),
+                        ReadStatValueType::String("two".to_string()),
+                    ],
+                ],
+            },
+        }
+    }
+
+    pub fn with_compression(mut self, compression: ReadStatCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    fn int_size(&self) -> usize {
+        if self.is_64bit {
+            8
+        } else {
+            4
+        }
+    }
+
+    fn header_size(&self) -> usize {
+        if self.is_64bit {
+            8192
+        } else {
+            1024
+        }
+    }
+
+    fn page_header_size(&self) -> usize {
+        if self.is_64bit {
+            40
+        } else {
+            24
+        }
+    }
+
+    fn pointer_size(&self) -> usize {
+        if self.is_64bit {
+            24
+        } else {
+            12
+        }
+    }
+
+    fn row_length(&self) -> usize {
+        self.columns
+            .iter()
+            .map(|column| column.width as usize)
+            .sum()
+    }
+
+    fn put(&self, data: &mut [u8], offset: usize, value: u64, size: usize) {
+        let bytes = match self.endianness {
+            SasEndian::Little => value.to_le_bytes()[..size].to_vec(),
+            SasEndian::Big => value.to_be_bytes()[8 - size..].to_vec(),
+        };
+        data[offset..offset + size].copy_from_slice(&bytes);
+    }
+
+    fn put_int(&self, data: &mut [u8], offset: usize, value: u64) {
+        self.put(data, offset, value, self.int_size());
+    }
+
+    fn put_text_ref(&self, data: &mut [u8], offset: usize, text_ref: &SasTextRef) {
+        self.put(data, offset, text_ref.index as u64, 2);
+        self.put(data, offset + 2, text_ref.offset as u64, 2);
+        self.put(data, offset + 4, text_ref.length as u64, 2);
+    }
+
+    /// Write a subheader signature in the file's word size. Column signatures are sign
+    /// extended in 64-bit files; the others repeat.
+    fn put_signature(&self, data: &mut [u8], signature: u32) {
+        let value = if !self.is_64bit {
+            signature as u64
+        } else if signature >= 0xFFFFFC00 {
+            0xFFFFFFFF_00000000 | signature as u64
+        } else {
+            ((signature as u64) << 32) | signature as u64
+        };
+        self.put_int(data, 0, value);
+    }
+
+    /// Serialize the fixture into the bytes of a sas7bdat file.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut pages = vec![self.meta_page()];
+        match self.compression {
+            ReadStatCompression::None => pages.extend(self.data_pages()),
+            _ => pages.extend(self.compressed_row_pages()),
+        }
+
+        let mut file = self.header(pages.len());
+        for page in pages {
+            file.extend(page);
+        }
+        file
+    }
+
+    /// Write the fixture to a file in a new temporary directory, which is removed again when
+    /// the returned `SasTempFile` is dropped.
+    pub fn write_temp(&self, name: &str) -> SasTempFile {
+        let dir = TempDir::new().expect("Could not create temporary directory");
+        let path = dir.path().join(format!("{}.sas7bdat", name));
+        std::fs::write(&path, self.to_bytes()).expect("Could not write fixture");
+        SasTempFile { path, _dir: dir }
+    }
+
+    pub(crate) fn header(&self, page_count: usize) -> Vec<u8> {
+        let mut header = vec![0u8; self.header_size()];
+        header[0..32].copy_from_slice(&SasFileMagicNumber::Sas7bdat.get_array());
+        header[32] = if self.is_64bit { 0x33 } else { 0x22 };
+        header[35] = if self.is_64bit { 0x33 } else { 0x22 };
+        header[37] = match self.endianness {
+            SasEndian::Big => 0x00,
+            SasEndian::Little => 0x01,
+        };
+        header[39] = b'1';
+        header[70] = 20;
+        header[84..92].copy_from_slice(b"SAS FILE");
+        header[92..124].fill(b' ');
+        header[92..92 + self.table_name.len()].copy_from_slice(self.table_name.as_bytes());
+        header[156..164].copy_from_slice(b"DATA    ");
+
+        let mut offset = SAS_HEADER_START_SIZE + if self.is_64bit { 4 } else { 0 };
+        // 1970-01-01T00:00:00Z in seconds since the SAS epoch
+        let timestamp = 315_619_200f64.to_bits();
+        self.put(&mut header, offset, timestamp, 8);
+        self.put(&mut header, offset + 8, timestamp, 8);
+        offset += 32;
+
+        self.put(&mut header, offset, self.header_size() as u64, 4);
+        self.put(&mut header, offset + 4, self.page_size as u64, 4);
+        offset += 8;
+        self.put_int(&mut header, offset, page_count as u64);
+        offset += self.int_size() + 8;
+
+        header[offset..offset + 8].copy_from_slice(b"9.0401M2");
+        header[offset + 8..offset + 24].copy_from_slice(b"X64_7PRO        ");
+        header[offset + 56..offset + 72].copy_from_slice(b"x86_64          ");
+        header
+    }
+
+    fn meta_page(&self) -> Vec<u8> {
+        let int_size = self.int_size();
+        let signatures = SasSubheaderSignature::default();
+        let column_count = self.columns.len();
+
+        let mut text = TextBlob::new(&self.compression);
+        let file_label_ref = text.add(&self.file_label);
+        let name_refs: Vec<SasTextRef> = self.columns.iter().map(|c| text.add(&c.name)).collect();
+        let format_refs: Vec<SasTextRef> =
+            self.columns.iter().map(|c| text.add(&c.format)).collect();
+        let informat_refs: Vec<SasTextRef> =
+            self.columns.iter().map(|c| text.add(&c.informat)).collect();
+        let label_refs: Vec<SasTextRef> = self.columns.iter().map(|c| text.add(&c.label)).collect();
+
+        let mut subheaders = Vec::new();
+
+        let row_size_len = if self.is_64bit { 808 } else { 480 };
+        let mut row_size = vec![0u8; row_size_len];
+        self.put_signature(&mut row_size, signatures.row_size);
+        self.put_int(&mut row_size, 5 * int_size, self.row_length() as u64);
+        self.put_int(&mut row_size, 6 * int_size, self.rows.len() as u64);
+        self.put_text_ref(&mut row_size, row_size_len - 130, &file_label_ref);
+        subheaders.push(row_size);
+
+        let mut column_size = vec![0u8; 3 * int_size];
+        self.put_signature(&mut column_size, signatures.column_size);
+        self.put_int(&mut column_size, int_size, column_count as u64);
+        subheaders.push(column_size);
+
+        let blob = text.finish(&self.endianness);
+        let mut column_text = vec![0u8; int_size + blob.len()];
+        self.put_signature(&mut column_text, signatures.column_text);
+        column_text[int_size..].copy_from_slice(&blob);
+        subheaders.push(column_text);
+
+        let mut column_name = vec![0u8; 2 * int_size + 12 + 8 * column_count];
+        self.put_signature(&mut column_name, signatures.column_name);
+        for (i, name_ref) in name_refs.iter().enumerate() {
+            self.put_text_ref(&mut column_name, int_size + 8 + 8 * i, name_ref);
+        }
+        subheaders.push(column_name);
+
+        let entry_size = int_size + 8;
+        let mut column_attrs = vec![0u8; 2 * int_size + 12 + entry_size * column_count];
+        self.put_signature(&mut column_attrs, signatures.column_attrs);
+        let mut column_offset = 0;
+        for (i, column) in self.columns.iter().enumerate() {
+            let entry = int_size + 8 + entry_size * i;
+            self.put_int(&mut column_attrs, entry, column_offset);
+            self.put(&mut column_attrs, entry + int_size, column.width as u64, 4);
+            column_attrs[entry + int_size + 6] = match column.type_ {
+                ReadStatType::String => 0x02,
+                _ => 0x01,
+            };
+            column_offset += column.width as u64;
+        }
+        subheaders.push(column_attrs);
+
+        for (i, column) in self.columns.iter().enumerate() {
+            let mut column_format = vec![0u8; 34 + 3 * int_size];
+            self.put_signature(&mut column_format, signatures.column_format);
+            self.put(
+                &mut column_format,
+                3 * int_size,
+                column.format_width as u64,
+                2,
+            );
+            self.put(
+                &mut column_format,
+                3 * int_size + 2,
+                column.decimals as u64,
+                2,
+            );
+            self.put(
+                &mut column_format,
+                3 * int_size + 4,
+                column.informat_width as u64,
+                2,
+            );
+            self.put_text_ref(&mut column_format, 16 + 3 * int_size, &informat_refs[i]);
+            self.put_text_ref(&mut column_format, 22 + 3 * int_size, &format_refs[i]);
+            self.put_text_ref(&mut column_format, 28 + 3 * int_size, &label_refs[i]);
+            subheaders.push(column_format);
+        }
+
+        self.subheader_page(
+            subheaders
+                .into_iter()
+                .map(|data| FixtureSubheader {
+                    data,
+                    compression: SasCompression::None as u8,
+                    is_compressed_data: false,
+                })
+                .collect(),
+        )
+        .expect("Fixture metadata does not fit on one page")
+    }
+
+    /// Lay out subheaders on a meta page, with the pointer table after the page header and the
+    /// subheaders after the pointer table. Returns None if they do not fit.
+    fn subheader_page(&self, subheaders: Vec<FixtureSubheader>) -> Option<Vec<u8>> {
+        let phs = self.page_header_size();
+        let int_size = self.int_size();
+        let mut page = vec![0u8; self.page_size];
+        self.put(&mut page, phs - 8, SasPageType::Meta as u64, 2);
+        self.put(&mut page, phs - 6, subheaders.len() as u64, 2);
+        self.put(&mut page, phs - 4, subheaders.len() as u64, 2);
+
+        let mut offset = align8(phs + subheaders.len() * self.pointer_size());
+        for (i, subheader) in subheaders.iter().enumerate() {
+            if offset + subheader.data.len() > self.page_size {
+                return None;
+            }
+            let pointer = phs + i * self.pointer_size();
+            self.put_int(&mut page, pointer, offset as u64);
+            self.put_int(&mut page, pointer + int_size, subheader.data.len() as u64);
+            page[pointer + 2 * int_size] = subheader.compression;
+            page[pointer + 2 * int_size + 1] = subheader.is_compressed_data as u8;
+            page[offset..offset + subheader.data.len()].copy_from_slice(&subheader.data);
+            offset = align8(offset + subheader.data.len());
+        }
+        Some(page)
+    }
+
+    fn encode_row(&self, row: &[ReadStatValueType]) -> Vec<u8> {
+        let mut data = Vec::with_capacity(self.row_length());
+        for (column, value) in self.columns.iter().zip(row) {
+            let width = column.width as usize;
+            match value {
+                ReadStatValueType::String(text) => {
+                    let mut bytes = text.as_bytes().to_vec();
+                    bytes.resize(width, b' ');
+                    data.extend(bytes);
+                }
+                ReadStatValueType::Double(number) => {
+                    // System missing is stored as a NaN with tag byte 0xFE
+                    let bits = if number.is_nan() {
+                        0xFFFFFE00_00000000
+                    } else {
+                        number.to_bits()
+                    };
+                    let bytes = match self.endianness {
+                        SasEndian::Little => bits.to_le_bytes()[8 - width..].to_vec(),
+                        SasEndian::Big => bits.to_be_bytes()[..width].to_vec(),
+                    };
+                    data.extend(bytes);
+                }
+                _ => panic!("Fixture values must be doubles or strings"),
+            }
+        }
+        data
+    }
+
+    fn data_pages(&self) -> Vec<Vec<u8>> {
+        let phs = self.page_header_size();
+        let row_length = self.row_length().max(1);
+        let rows_per_page = (self.page_size - phs) / row_length;
+
+        self.rows
+            .chunks(rows_per_page.max(1))
+            .map(|rows| {
+                let mut page = vec![0u8; self.page_size];
+                self.put(&mut page, phs - 8, SasPageType::Data as u64, 2);
+                self.put(&mut page, phs - 6, rows.len() as u64, 2);
+                for (i, row) in rows.iter().enumerate() {
+                    let offset = phs + i * row_length;
+                    page[offset..offset + row_length].copy_from_slice(&self.encode_row(row));
+                }
+                page
+            })
+            .collect()
+    }
+
+    fn compressed_row_pages(&self) -> Vec<Vec<u8>> {
+        let compressed: Vec<Vec<u8>> = self
+            .rows
+            .iter()
+            .map(|row| {
+                let row = self.encode_row(row);
+                match self.compression {
+                    ReadStatCompression::Binary => rdc_compress(&row),
+                    _ => rle_compress(&row),
+                }
+            })
+            .collect();
+
+        let mut pages = Vec::new();
+        let mut pending: Vec<Vec<u8>> = Vec::new();
+        for row in compressed {
+            pending.push(row);
+            if self.compressed_rows_page(&pending).is_none() {
+                let row = pending.pop().unwrap();
+                pages.push(self.compressed_rows_page(&pending).unwrap());
+                pending = vec![row];
+            }
+        }
+        if !pending.is_empty() {
+            pages.push(self.compressed_rows_page(&pending).unwrap());
+        }
+        pages
+    }
+
+    fn compressed_rows_page(&self, rows: &[Vec<u8>]) -> Option<Vec<u8>> {
+        self.subheader_page(
+            rows.iter()
+                .map(|data| FixtureSubheader {
+                    data: data.clone(),
+                    compression: SasCompression::Row as u8,
+                    is_compressed_data: true,
+                })
+                .collect(),
+        )
+    }
+}
+
+fn align8(offset: usize) -> usize {
+    offset.div_ceil(8) * 8
+}
+
+/// The text of the column text subheader: a length, the compression signature at offset 12
+/// and the strings after it, each padded to a multiple of 4 bytes.
+struct TextBlob {
+    data: Vec<u8>,
+}
+
+impl TextBlob {
+    fn new(compression: &ReadStatCompression) -> Self {
+        let mut data = vec![0u8; 20];
+        let signature = match compression {
+            ReadStatCompression::Rows => SasCompressionSignature::Rle.to_string(),
+            ReadStatCompression::Binary => SasCompressionSignature::Rdc.to_string(),
+            ReadStatCompression::None => " ".repeat(8),
+        };
+        data[12..20].copy_from_slice(signature.as_bytes());
+        Self { data }
+    }
+
+    fn add(&mut self, text: &str) -> SasTextRef {
+        if text.is_empty() {
+            return SasTextRef::new(0, 0, 0);
+        }
+        let offset = self.data.len();
+        self.data.extend_from_slice(text.as_bytes());
+        self.data.resize(offset + text.len().div_ceil(4) * 4, b' ');
+        SasTextRef::new(0, offset as u16, text.len() as u16)
+    }
+
+    fn finish(mut self, endianness: &SasEndian) -> Vec<u8> {
+        let length = self.data.len() as u16;
+        let bytes = match endianness {
+            SasEndian::Little => length.to_le_bytes(),
+            SasEndian::Big => length.to_be_bytes(),
+        };
+        self.data[0..2].copy_from_slice(&bytes);
+        self.data
+    }
+}
+
+/// Compress a row with the SAS RLE scheme, using runs of blanks and literal copies only.
+pub fn rle_compress(row: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut literal: Vec<u8> = Vec::new();
+    let flush = |literal: &mut Vec<u8>, output: &mut Vec<u8>| {
+        for chunk in literal.chunks(16) {
+            // COPY1: copy length + 1 bytes
+            output.push(0x80 | (chunk.len() as u8 - 1));
+            output.extend_from_slice(chunk);
+        }
+        literal.clear();
+    };
+
+    let mut i = 0;
+    while i < row.len() {
+        let run = row[i..]
+            .iter()
+            .take(17)
+            .take_while(|&&byte| byte == b' ')
+            .count();
+        if run >= 2 {
+            flush(&mut literal, &mut output);
+            // INSERT_BLANK2: insert length + 2 blanks
+            output.push(0xE0 | (run as u8 - 2));
+            i += run;
+        } else {
+            literal.push(row[i]);
+            i += 1;
+        }
+    }
+    flush(&mut literal, &mut output);
+    output
+}
+
+/// Compress a row with RDC, using literal bytes only.
+pub fn rdc_compress(row: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    for chunk in row.chunks(16) {
+        output.extend_from_slice(&[0, 0]);
+        output.extend_from_slice(chunk);
+    }
+    output
+}
@@ -0,0 +1,140 @@
+use crate::types::{ReadStatError, SasTextRef};
+use std::borrow::Cow;
+
+/// Characters 0x80 to 0x9F of WINDOWS-1252, the default SAS string encoding. Every other byte
+/// maps to the Unicode code point with the same value, as in ISO-8859-1. Undefined positions
+/// are mapped to the replacement character.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{FFFD}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{FFFD}', '\u{017D}', '\u{FFFD}',
+    '\u{FFFD}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{FFFD}', '\u{017E}', '\u{0178}',
+];
+
+/// Strip the trailing blanks and NUL bytes SAS uses to pad fixed-width character values.
+pub fn trim_padding(data: &[u8]) -> &[u8] {
+    let end = data
+        .iter()
+        .rposition(|&byte| byte != b' ' && byte != 0)
+        .map_or(0, |position| position + 1);
+    &data[..end]
+}
+
+/// Decode a character value stored in the given encoding, as named by `build_charset_lookup`.
+/// UTF-8, ASCII, ISO-8859-1 and WINDOWS-1252 are decoded exactly; any other encoding is decoded
+/// as UTF-8 if the bytes are valid UTF-8, and as ISO-8859-1 otherwise.
+pub fn decode_sas_string(data: &[u8], encoding: &str) -> String {
+    decode_sas_str(data, encoding).into_owned()
+}
+
+/// Decode a character value like `decode_sas_string`, borrowing it from `data` whenever its
+/// bytes are already the UTF-8 encoding of the text, which is always the case for ASCII.
+pub fn decode_sas_str<'a>(data: &'a [u8], encoding: &str) -> Cow<'a, str> {
+    if data.is_ascii() {
+        // Every supported encoding agrees with ASCII on the first 128 bytes
+        return Cow::Borrowed(std::str::from_utf8(data).unwrap_or_default());
+    }
+    match encoding {
+        "UTF-8" | "US-ASCII" => String::from_utf8_lossy(data),
+        "ISO-8859-1" => Cow::Owned(data.iter().map(|&byte| byte as char).collect()),
+        "WINDOWS-1252" => Cow::Owned(data.iter().map(|&byte| windows_1252_char(byte)).collect()),
+        _ => match std::str::from_utf8(data) {
+            Ok(text) => Cow::Borrowed(text),
+            Err(_) => Cow::Owned(data.iter().map(|&byte| byte as char).collect()),
+        },
+    }
+}
+
+fn windows_1252_char(byte: u8) -> char {
+    match byte {
+        0x80..=0x9F => WINDOWS_1252_HIGH[(byte - 0x80) as usize],
+        _ => byte as char,
+    }
+}
+
+/// Resolve a text reference against the column text blobs collected from the file.
+///
+/// # Returns
+/// The referenced text with its padding removed, or `ReadStatError::Parse` if the reference
+/// points outside the blobs.
+pub fn resolve_text_ref(
+    blobs: &[Vec<u8>],
+    text_ref: &SasTextRef,
+    encoding: &str,
+) -> Result<String, ReadStatError> {
+    if text_ref.length == 0 {
+        return Ok(String::new());
+    }
+
+    let blob = blobs
+        .get(text_ref.index as usize)
+        .ok_or(ReadStatError::Parse)?;
+    let start = text_ref.offset as usize;
+    let end = start + text_ref.length as usize;
+    if end > blob.len() {
+        return Err(ReadStatError::Parse);
+    }
+
+    Ok(decode_sas_string(trim_padding(&blob[start..end]), encoding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trim_padding() {
+        assert_eq!(trim_padding(b"abc  \0 "), b"abc");
+        assert_eq!(trim_padding(b"   "), b"");
+        assert_eq!(trim_padding(b" a"), b" a");
+    }
+
+    #[test]
+    fn test_decode_sas_string() {
+        assert_eq!(
+            decode_sas_string("caf\u{e9}".as_bytes(), "UTF-8"),
+            "caf\u{e9}"
+        );
+        assert_eq!(decode_sas_string(b"caf\xe9", "ISO-8859-1"), "caf\u{e9}");
+        assert_eq!(decode_sas_string(b"\x80 5", "WINDOWS-1252"), "\u{20AC} 5");
+        assert_eq!(decode_sas_string(b"caf\xe9", "CP1250"), "caf\u{e9}");
+    }
+
+    #[test]
+    fn test_decode_sas_str_borrows() {
+        assert!(matches!(
+            decode_sas_str(b"plain", "WINDOWS-1252"),
+            Cow::Borrowed("plain")
+        ));
+        assert!(matches!(
+            decode_sas_str("caf\u{e9}".as_bytes(), "UTF-8"),
+            Cow::Borrowed("caf\u{e9}")
+        ));
+        assert!(matches!(
+            decode_sas_str(b"caf\xe9", "ISO-8859-1"),
+            Cow::Owned(_)
+        ));
+    }
+
+    #[test]
+    fn test_resolve_text_ref() {
+        let blobs = vec![b"....NAME    AGE ".to_vec()];
+        let name = resolve_text_ref(&blobs, &SasTextRef::new(0, 4, 8), "UTF-8").unwrap();
+        assert_eq!(name, "NAME");
+        let empty = resolve_text_ref(&blobs, &SasTextRef::new(3, 0, 0), "UTF-8").unwrap();
+        assert_eq!(empty, "");
+    }
+
+    #[test]
+    fn test_resolve_text_ref_out_of_bounds() {
+        let blobs = vec![b"NAME".to_vec()];
+        assert_eq!(
+            resolve_text_ref(&blobs, &SasTextRef::new(0, 2, 8), "UTF-8"),
+            Err(ReadStatError::Parse)
+        );
+        assert_eq!(
+            resolve_text_ref(&blobs, &SasTextRef::new(1, 0, 1), "UTF-8"),
+            Err(ReadStatError::Parse)
+        );
+    }
+}
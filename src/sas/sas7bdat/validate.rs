@@ -0,0 +1,529 @@
+use crate::sas::sas7bdat::context::Sas7bdatContext;
+use crate::sas::sas7bdat::header::parse_header;
+use crate::sas::sas7bdat::page::{page_row_results, read_page_header};
+use crate::sas::sas7bdat::reader::{at_page, parse_metadata_page, MIN_HEADER_SIZE};
+use crate::sas::sas7bdat::source::Sas7bdatSource;
+use crate::sas::sas7bdat::subheader::{
+    is_valid_subheader_pointer, read_subheader_pointer_table, Sas7bdatMetadataParser,
+};
+use crate::types::{ReadStatError, SasError};
+use serde_json::{json, Value};
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+
+/// A structural problem found by `validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SasValidationIssueKind {
+    /// The file is not the header followed by exactly `page_count` pages.
+    PageCountMismatch {
+        page_count: u64,
+        file_size: u64,
+        expected_size: u64,
+    },
+
+    /// The page could not be read in full. Neither it nor any later page is checked.
+    UnreadablePage(ReadStatError),
+
+    /// The page type is none of those SAS writes, so the page is not checked any further.
+    UnknownPageType(u16),
+
+    /// The subheader pointer table of the page does not fit in it.
+    BadSubheaderCount(u16),
+
+    /// The subheader points into the pointer table or past the end of the page. `offset` is
+    /// counted from the start of the page.
+    BadSubheaderOffset { offset: u64, length: u64 },
+
+    /// A metadata subheader could not be parsed, or the metadata as a whole is inconsistent.
+    BadMetadata(ReadStatError),
+
+    /// A compressed row does not decompress to exactly the row length.
+    DecompressionMismatch(ReadStatError),
+
+    /// An uncompressed row stored as a subheader is shorter than the row length.
+    BadRow(ReadStatError),
+
+    /// The row size subheader declares a different number of rows than the pages hold.
+    RowCountMismatch { declared: u64, found: u64 },
+}
+
+impl SasValidationIssueKind {
+    /// A stable identifier for the kind of problem, for machine-readable reports.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::PageCountMismatch { .. } => "page_count_mismatch",
+            Self::UnreadablePage(_) => "unreadable_page",
+            Self::UnknownPageType(_) => "unknown_page_type",
+            Self::BadSubheaderCount(_) => "bad_subheader_count",
+            Self::BadSubheaderOffset { .. } => "bad_subheader_offset",
+            Self::BadMetadata(_) => "bad_metadata",
+            Self::DecompressionMismatch(_) => "decompression_mismatch",
+            Self::BadRow(_) => "bad_row",
+            Self::RowCountMismatch { .. } => "row_count_mismatch",
+        }
+    }
+}
+
+impl fmt::Display for SasValidationIssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PageCountMismatch {
+                page_count,
+                file_size,
+                expected_size,
+            } => write!(
+                f,
+                "File is {} bytes long, but its header and {} pages take {} bytes",
+                file_size, page_count, expected_size
+            ),
+            Self::UnreadablePage(err) => write!(f, "Page cannot be read: {}", err),
+            Self::UnknownPageType(page_type) => write!(f, "Unknown page type {:#06x}", page_type),
+            Self::BadSubheaderCount(count) => {
+                write!(f, "{} subheader pointers do not fit in the page", count)
+            }
+            Self::BadSubheaderOffset { offset, length } => write!(
+                f,
+                "Subheader of {} bytes at page offset {:#x} is outside the page",
+                length, offset
+            ),
+            Self::BadMetadata(err) => write!(f, "Bad metadata: {}", err),
+            Self::DecompressionMismatch(err) => {
+                write!(
+                    f,
+                    "Compressed row does not decompress to the row length: {}",
+                    err
+                )
+            }
+            Self::BadRow(err) => write!(f, "Row cannot be read: {}", err),
+            Self::RowCountMismatch { declared, found } => {
+                write!(f, "{} rows declared, but {} found", declared, found)
+            }
+        }
+    }
+}
+
+/// A structural problem and where in the file it was found. Offsets are counted in bytes from
+/// the start of the file; pages, subheaders and rows are numbered from 0, rows across the whole
+/// file, as in `SasError`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasValidationIssue {
+    pub kind: SasValidationIssueKind,
+    pub offset: Option<u64>,
+    pub page: Option<u64>,
+    pub subheader: Option<usize>,
+    pub row: Option<u64>,
+}
+
+impl SasValidationIssue {
+    pub fn new(kind: SasValidationIssueKind) -> Self {
+        Self {
+            kind,
+            offset: None,
+            page: None,
+            subheader: None,
+            row: None,
+        }
+    }
+
+    /// Create an issue found where `err` happened.
+    fn at(kind: SasValidationIssueKind, err: &SasError) -> Self {
+        Self {
+            kind,
+            offset: err.offset,
+            page: err.page,
+            subheader: err.subheader,
+            row: err.row,
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "code": self.kind.code(),
+            "message": self.kind.to_string(),
+            "offset": self.offset,
+            "page": self.page,
+            "subheader": self.subheader,
+            "row": self.row,
+        })
+    }
+}
+
+impl fmt::Display for SasValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        let mut context = Vec::new();
+        if let Some(page) = self.page {
+            context.push(format!("page {}", page));
+        }
+        if let Some(subheader) = self.subheader {
+            context.push(format!("subheader {}", subheader));
+        }
+        if let Some(offset) = self.offset {
+            context.push(format!("offset {:#x}", offset));
+        }
+        if let Some(row) = self.row {
+            context.push(format!("row {}", row));
+        }
+        if !context.is_empty() {
+            write!(f, " at {}", context.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// The outcome of `validate`: what was checked and every problem found, in file order except
+/// for the row count, which is compared last. The row counts are `None` when the metadata is
+/// too broken for rows to be extracted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SasValidationReport {
+    pub file_size: u64,
+    pub page_count: u64,
+    pub pages_checked: u64,
+    pub row_count: Option<u64>,
+    pub rows_found: Option<u64>,
+    pub issues: Vec<SasValidationIssue>,
+}
+
+impl SasValidationReport {
+    /// Return true if no problem was found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Render the report as pretty-printed JSON, ending with a line break.
+    pub fn to_json(&self) -> String {
+        let report = json!({
+            "valid": self.is_valid(),
+            "file_size": self.file_size,
+            "page_count": self.page_count,
+            "pages_checked": self.pages_checked,
+            "row_count": self.row_count,
+            "rows_found": self.rows_found,
+            "issues": self.issues.iter().map(SasValidationIssue::to_json).collect::<Vec<Value>>(),
+        });
+        let mut text = serde_json::to_string_pretty(&report).unwrap_or_default();
+        text.push('\n');
+        text
+    }
+}
+
+/// Check the structure of the sas7bdat file at the given path.
+///
+/// # Returns
+/// A report of every problem found, or an error if the file cannot be opened or its header
+/// cannot be parsed, in which case nothing else can be checked.
+pub fn validate<P: AsRef<Path>>(path: P) -> Result<SasValidationReport, SasError> {
+    let file = File::open(path).map_err(|err| SasError::io(ReadStatError::Open, err))?;
+    validate_source(Sas7bdatSource::Io(Box::new(file)))
+}
+
+/// Check the structure of a sas7bdat file read from the given source. Every page and subheader
+/// is walked, carrying on past problems instead of stopping at the first one like
+/// `Sas7bdatReader` does.
+///
+/// # Returns
+/// A report of every problem found, or an error if the header cannot be read or parsed.
+pub fn validate_source(mut source: Sas7bdatSource) -> Result<SasValidationReport, SasError> {
+    let header_bytes = source.read_at(0, MIN_HEADER_SIZE)?.into_owned();
+    let header = parse_header(&header_bytes).map_err(|err| SasError::from(err).with_offset(0))?;
+    let mut ctx = Sas7bdatContext::from_header(&header);
+    let mut report = SasValidationReport {
+        file_size: source.size()?,
+        page_count: ctx.page_count,
+        pages_checked: 0,
+        row_count: None,
+        rows_found: None,
+        issues: Vec::new(),
+    };
+
+    let expected_size = ctx.page_offset(ctx.page_count);
+    if report.file_size != expected_size {
+        report.issues.push(SasValidationIssue::new(
+            SasValidationIssueKind::PageCountMismatch {
+                page_count: ctx.page_count,
+                file_size: report.file_size,
+                expected_size,
+            },
+        ));
+    }
+
+    // Check the structure of every page while collecting the metadata, which rows can only be
+    // extracted with once it is complete
+    let mut parser = Sas7bdatMetadataParser::new();
+    let mut metadata_complete = false;
+    let mut row_pages = Vec::new();
+    for index in 0..ctx.page_count {
+        let page = match source.read_at(ctx.page_offset(index), ctx.page_size) {
+            Ok(page) => page,
+            Err(err) => {
+                let err = err.with_page(index);
+                let kind = SasValidationIssueKind::UnreadablePage(err.kind.clone());
+                report.issues.push(SasValidationIssue::at(kind, &err));
+                break;
+            }
+        };
+        report.pages_checked += 1;
+        if !check_page(&page, index, &ctx, &mut report.issues) {
+            continue;
+        }
+        row_pages.push(index);
+        if !metadata_complete {
+            match parse_metadata_page(&page, index, &mut parser, &mut ctx) {
+                Ok(complete) => metadata_complete = complete,
+                Err(err) => {
+                    let kind = SasValidationIssueKind::BadMetadata(err.kind.clone());
+                    report.issues.push(SasValidationIssue::at(kind, &err));
+                }
+            }
+        }
+    }
+
+    if let Err(err) = parser.finish(&mut ctx) {
+        let kind = SasValidationIssueKind::BadMetadata(err.kind.clone());
+        report.issues.push(SasValidationIssue::at(kind, &err));
+        return Ok(report);
+    }
+
+    let mut rows_found = 0;
+    for index in row_pages {
+        let page = source
+            .read_at(ctx.page_offset(index), ctx.page_size)
+            .map_err(|err| err.with_page(index))?;
+        let rows = match page_row_results(&page, &ctx) {
+            Ok(rows) => rows,
+            Err(err) => {
+                let err = at_page(err, &ctx, index, rows_found);
+                let kind = SasValidationIssueKind::BadRow(err.kind.clone());
+                report.issues.push(SasValidationIssue::at(kind, &err));
+                continue;
+            }
+        };
+        let rows_before = rows_found;
+        rows_found += rows.len() as u64;
+        for err in rows.into_iter().filter_map(Result::err) {
+            let kind = if is_compressed_row(&page, err.subheader, &ctx) {
+                SasValidationIssueKind::DecompressionMismatch(err.kind.clone())
+            } else {
+                SasValidationIssueKind::BadRow(err.kind.clone())
+            };
+            let err = at_page(err, &ctx, index, rows_before);
+            report.issues.push(SasValidationIssue::at(kind, &err));
+        }
+    }
+
+    report.row_count = Some(ctx.row_count);
+    report.rows_found = Some(rows_found);
+    if rows_found != ctx.row_count {
+        report.issues.push(SasValidationIssue::new(
+            SasValidationIssueKind::RowCountMismatch {
+                declared: ctx.row_count,
+                found: rows_found,
+            },
+        ));
+    }
+
+    Ok(report)
+}
+
+/// Check the page type and subheader pointers of the page with the given index, adding the
+/// problems found to `issues`.
+///
+/// # Returns
+/// True if the metadata and rows of the page can be read.
+fn check_page(
+    page: &[u8],
+    index: u64,
+    ctx: &Sas7bdatContext,
+    issues: &mut Vec<SasValidationIssue>,
+) -> bool {
+    let page_offset = ctx.page_offset(index);
+    let mut report = |kind: SasValidationIssueKind, offset: u64, subheader: Option<usize>| {
+        issues.push(SasValidationIssue {
+            kind,
+            offset: Some(offset),
+            page: Some(index),
+            subheader,
+            row: None,
+        });
+    };
+
+    let header = match read_page_header(page, ctx) {
+        Ok(header) => header,
+        Err(err) => {
+            report(
+                SasValidationIssueKind::UnreadablePage(err),
+                page_offset,
+                None,
+            );
+            return false;
+        }
+    };
+    if !header.is_known_type() {
+        let kind = SasValidationIssueKind::UnknownPageType(header.page_type);
+        report(kind, page_offset, None);
+        return false;
+    }
+    if !header.has_subheaders() {
+        return true;
+    }
+
+    let subheader_count = header.subheader_count as usize;
+    let Ok(pointers) = read_subheader_pointer_table(page, subheader_count, ctx) else {
+        let kind = SasValidationIssueKind::BadSubheaderCount(header.subheader_count);
+        report(kind, page_offset, None);
+        return false;
+    };
+    let mut valid = true;
+    for (subheader, pointer) in pointers.iter().enumerate() {
+        if !is_valid_subheader_pointer(pointer, page, subheader_count, ctx) {
+            let kind = SasValidationIssueKind::BadSubheaderOffset {
+                offset: pointer.offset,
+                length: pointer.length,
+            };
+            let pointer_offset = ctx.page_header_size + subheader * ctx.subheader_pointer_size;
+            report(kind, page_offset + pointer_offset as u64, Some(subheader));
+            valid = false;
+        }
+    }
+    valid
+}
+
+/// Return true if the given subheader of a page, whose pointers are known to be valid, holds a
+/// compressed row.
+fn is_compressed_row(page: &[u8], subheader: Option<usize>, ctx: &Sas7bdatContext) -> bool {
+    let (Some(subheader), Ok(header)) = (subheader, read_page_header(page, ctx)) else {
+        return false;
+    };
+    read_subheader_pointer_table(page, header.subheader_count as usize, ctx)
+        .ok()
+        .and_then(|pointers| {
+            pointers
+                .get(subheader)
+                .map(|pointer| pointer.is_compressed_row())
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sas::sas7bdat::test_fixtures::{SasFixture, SasFixtureColumn};
+    use crate::types::{ReadStatCompression, ReadStatValueType};
+    use std::io::Cursor;
+
+    fn fixture() -> SasFixture {
+        SasFixture::builder()
+            .columns(vec![
+                SasFixtureColumn::numeric("ID", "BEST", 12),
+                SasFixtureColumn::character("NAME", 16),
+            ])
+            .rows(
+                (0..300)
+                    .map(|i| {
+                        vec![
+                            ReadStatValueType::Double(i as f64),
+                            ReadStatValueType::String(format!("name{}", i)),
+                        ]
+                    })
+                    .collect(),
+            )
+            .build()
+    }
+
+    fn validate_bytes(bytes: Vec<u8>) -> SasValidationReport {
+        validate_source(Sas7bdatSource::Io(Box::new(Cursor::new(bytes)))).unwrap()
+    }
+
+    fn codes(report: &SasValidationReport) -> Vec<&'static str> {
+        report
+            .issues
+            .iter()
+            .map(|issue| issue.kind.code())
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_file() {
+        let path = fixture().write_temp("validate_valid");
+        let report = validate(&path).unwrap();
+        assert!(report.is_valid(), "{:?}", report.issues);
+        assert_eq!(report.pages_checked, report.page_count);
+        assert_eq!(report.row_count, Some(300));
+        assert_eq!(report.rows_found, Some(300));
+        assert!(report.to_json().contains("\"valid\": true"));
+    }
+
+    #[test]
+    fn test_truncated_file() {
+        let mut bytes = fixture().to_bytes();
+        let page_count = (bytes.len() as u64 - 1024) / 4096;
+        bytes.truncate(bytes.len() - 100);
+        let report = validate_bytes(bytes);
+
+        assert_eq!(
+            codes(&report),
+            [
+                "page_count_mismatch",
+                "unreadable_page",
+                "row_count_mismatch"
+            ]
+        );
+        assert_eq!(report.pages_checked, page_count - 1);
+        assert_eq!(report.issues[1].page, Some(page_count - 1));
+        assert!(report.rows_found < report.row_count);
+    }
+
+    #[test]
+    fn test_unknown_page_type() {
+        let mut bytes = fixture().to_bytes();
+        bytes[1024 + 4096 + 17] = 0x08;
+        let report = validate_bytes(bytes);
+
+        assert_eq!(codes(&report), ["unknown_page_type", "row_count_mismatch"]);
+        assert_eq!(
+            report.issues[0].kind,
+            SasValidationIssueKind::UnknownPageType(0x0800)
+        );
+        assert_eq!(report.issues[0].page, Some(1));
+        assert_eq!(
+            report.issues[1].to_string(),
+            format!(
+                "300 rows declared, but {} found",
+                report.rows_found.unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn test_bad_subheader_offset() {
+        let mut bytes = fixture().to_bytes();
+        bytes[1024 + 24..1024 + 28].copy_from_slice(&0xFFFFu32.to_le_bytes());
+        let report = validate_bytes(bytes);
+
+        assert_eq!(codes(&report), ["bad_subheader_offset", "bad_metadata"]);
+        let issue = &report.issues[0];
+        assert_eq!(issue.page, Some(0));
+        assert_eq!(issue.subheader, Some(0));
+        assert_eq!(issue.offset, Some(1024 + 24));
+        assert_eq!(report.rows_found, None);
+    }
+
+    #[test]
+    fn test_decompression_mismatch() {
+        let mut bytes = fixture()
+            .with_compression(ReadStatCompression::Rows)
+            .to_bytes();
+        // Cut the first compressed row on the first row page down to a single byte
+        bytes[1024 + 4096 + 28..1024 + 4096 + 32].copy_from_slice(&1u32.to_le_bytes());
+        let report = validate_bytes(bytes);
+
+        assert_eq!(codes(&report), ["decompression_mismatch"]);
+        let issue = &report.issues[0];
+        assert_eq!(issue.page, Some(1));
+        assert_eq!(issue.subheader, Some(0));
+        assert_eq!(issue.row, Some(0));
+        assert_eq!(report.rows_found, Some(300));
+    }
+}
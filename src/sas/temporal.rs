@@ -0,0 +1,406 @@
+use crate::types::{
+    ReadStatError, SasTemporalKind, SasTemporalPolicy, SasTemporalText, SasTemporalValue,
+};
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+const ISO8601_DATE: &str = "%Y-%m-%d";
+const ISO8601_DATETIME: &str = "%Y-%m-%dT%H:%M:%S%.f";
+
+fn sas_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1960, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("1960-01-01 is a valid date")
+}
+
+/// Convert a SAS date, in days since 1960-01-01, to a calendar date. Fractional days are
+/// truncated towards the earlier date, as SAS does when displaying them.
+pub fn sas_date_to_naive(days: f64) -> Option<NaiveDate> {
+    if !days.is_finite() {
+        return None;
+    }
+    let days = Duration::try_days(days.floor() as i64)?;
+    sas_epoch().date().checked_add_signed(days)
+}
+
+/// Convert a SAS datetime, in seconds since 1960-01-01T00:00:00, to a date and time with
+/// microsecond precision, about as fine as a double holding a recent datetime can resolve.
+pub fn sas_datetime_to_naive(seconds: f64) -> Option<NaiveDateTime> {
+    sas_epoch().checked_add_signed(split_seconds(seconds)?)
+}
+
+/// Convert a SAS datetime, in seconds since 1960-01-01T00:00:00, to a UTC date and time,
+/// keeping fractions of a second to the microsecond. Returns None for missing values and
+/// datetimes chrono cannot represent.
+pub fn sas_to_utc(seconds: f64) -> Option<DateTime<Utc>> {
+    sas_datetime_to_naive(seconds).map(|datetime| datetime.and_utc())
+}
+
+/// Convert a SAS time, in seconds since midnight, to a time of day with microsecond
+/// precision. Times from 24 hours on, and negative times, are durations rather than times of
+/// day and return None.
+pub fn sas_time_to_naive(seconds: f64) -> Option<NaiveTime> {
+    if !(0.0..86_400.0).contains(&seconds) {
+        return None;
+    }
+    let duration = split_seconds(seconds)?;
+    let time = NaiveTime::MIN + duration;
+    // Rounding up to the next microsecond may reach midnight
+    (duration < Duration::days(1)).then_some(time)
+}
+
+/// Convert a number of seconds to a duration rounded to the microsecond, for up to about
+/// three million years.
+fn split_seconds(seconds: f64) -> Option<Duration> {
+    if !seconds.is_finite() || seconds.abs() > 1e14 {
+        return None;
+    }
+    let whole = seconds.floor();
+    let micros = ((seconds - whole) * 1e6).round() as i64;
+    Duration::try_seconds(whole as i64)?.checked_add(&Duration::microseconds(micros))
+}
+
+/// Decode a SAS date, datetime or time, or return None if it is missing or out of range.
+/// Fractional dates are truncated; use `decode_temporal_with` to choose what happens to values
+/// that cannot be decoded.
+pub fn decode_temporal(value: f64, kind: SasTemporalKind) -> Option<SasTemporalValue> {
+    match kind {
+        SasTemporalKind::Date => sas_date_to_naive(value).map(SasTemporalValue::Date),
+        SasTemporalKind::DateTime => sas_datetime_to_naive(value).map(SasTemporalValue::DateTime),
+        SasTemporalKind::Time => sas_time_to_naive(value).map(SasTemporalValue::Time),
+    }
+}
+
+/// Decode a SAS date, datetime or time, applying `policy` to values that cannot be decoded:
+/// infinite values, values out of chrono's range, times outside a day and dates that are not
+/// a whole number of days. Missing values decode to None whatever the policy.
+///
+/// # Returns
+/// `ReadStatError::BadTimestampValue` for values that cannot be decoded under
+/// `SasTemporalPolicy::Error`.
+pub fn decode_temporal_with(
+    value: f64,
+    kind: SasTemporalKind,
+    policy: SasTemporalPolicy,
+) -> Result<Option<SasTemporalValue>, ReadStatError> {
+    if value.is_nan() {
+        return Ok(None);
+    }
+    if kind != SasTemporalKind::Date || value.fract() == 0.0 {
+        if let Some(decoded) = decode_temporal(value, kind) {
+            return Ok(Some(decoded));
+        }
+    }
+    match policy {
+        SasTemporalPolicy::Error => Err(ReadStatError::BadTimestampValue),
+        SasTemporalPolicy::Missing => Ok(None),
+        SasTemporalPolicy::Clamp => Ok(Some(clamp_temporal(value, kind))),
+        SasTemporalPolicy::Raw => Ok(Some(SasTemporalValue::Raw(value))),
+    }
+}
+
+/// Decode a value, or return the earliest or latest value chrono can represent if it is out
+/// of range.
+fn clamp_temporal(value: f64, kind: SasTemporalKind) -> SasTemporalValue {
+    let last_time = NaiveTime::from_hms_micro_opt(23, 59, 59, 999_999).expect("valid time");
+    let (earliest, latest) = match kind {
+        SasTemporalKind::Date => (
+            SasTemporalValue::Date(NaiveDate::MIN),
+            SasTemporalValue::Date(NaiveDate::MAX),
+        ),
+        SasTemporalKind::DateTime => (
+            SasTemporalValue::DateTime(NaiveDate::MIN.and_time(NaiveTime::MIN)),
+            SasTemporalValue::DateTime(NaiveDate::MAX.and_time(last_time)),
+        ),
+        SasTemporalKind::Time => (
+            SasTemporalValue::Time(NaiveTime::MIN),
+            SasTemporalValue::Time(last_time),
+        ),
+    };
+    decode_temporal(value, kind).unwrap_or(if value < 0.0 { earliest } else { latest })
+}
+
+/// Convert a calendar date to a SAS date, in days since 1960-01-01.
+pub fn naive_to_sas_date(date: NaiveDate) -> f64 {
+    (date - sas_epoch().date()).num_days() as f64
+}
+
+/// Convert a date and time to a SAS datetime, in seconds since 1960-01-01T00:00:00, keeping
+/// fractions of a second.
+pub fn naive_to_sas_datetime(datetime: NaiveDateTime) -> f64 {
+    let elapsed = datetime - sas_epoch();
+    elapsed.num_seconds() as f64 + elapsed.subsec_nanos() as f64 / 1e9
+}
+
+/// Render a numeric value as ISO 8601 according to its temporal kind: `2024-01-31` for dates,
+/// `2024-01-31T13:45:00` for datetimes and `13:45:00` for times. Fractional seconds are only
+/// shown when present. Times may exceed 24 hours, as SAS durations do.
+pub fn format_iso8601(value: f64, kind: SasTemporalKind) -> Option<String> {
+    match kind {
+        SasTemporalKind::Date => {
+            sas_date_to_naive(value).map(|date| date.format(ISO8601_DATE).to_string())
+        }
+        SasTemporalKind::DateTime => sas_datetime_to_naive(value)
+            .map(|datetime| datetime.format(ISO8601_DATETIME).to_string()),
+        SasTemporalKind::Time => format_sas_time(value),
+    }
+}
+
+/// Render a numeric value as ISO 8601, decoding it with `decode_temporal_with` so that `policy`
+/// applies to every value that cannot be decoded, including fractional dates and times from 24
+/// hours on. Returns `Ok(None)` for missing values, including those the policy treats as
+/// missing.
+///
+/// # Returns
+/// `Err(ReadStatError::BadTimestampValue)` under `SasTemporalPolicy::Error`, and the stored
+/// number as `SasTemporalText::Raw` under `SasTemporalPolicy::Raw`.
+pub fn format_iso8601_with(
+    value: f64,
+    kind: SasTemporalKind,
+    policy: SasTemporalPolicy,
+) -> Result<Option<SasTemporalText>, ReadStatError> {
+    let text = match decode_temporal_with(value, kind, policy)? {
+        None => return Ok(None),
+        Some(SasTemporalValue::Raw(number)) => return Ok(Some(SasTemporalText::Raw(number))),
+        Some(SasTemporalValue::Date(date)) => date.format(ISO8601_DATE).to_string(),
+        Some(SasTemporalValue::DateTime(datetime)) => datetime.format(ISO8601_DATETIME).to_string(),
+        Some(SasTemporalValue::Time(time)) => time.format("%H:%M:%S%.f").to_string(),
+    };
+    Ok(Some(SasTemporalText::Iso8601(text)))
+}
+
+fn format_sas_time(seconds: f64) -> Option<String> {
+    if !seconds.is_finite() || seconds.abs() > 1e14 {
+        return None;
+    }
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let milliseconds = (seconds.abs() * 1000.0).round() as u64;
+    let (whole, fraction) = (milliseconds / 1000, milliseconds % 1000);
+    let time = format!(
+        "{}{:02}:{:02}:{:02}",
+        sign,
+        whole / 3600,
+        whole / 60 % 60,
+        whole % 60
+    );
+    if fraction == 0 {
+        Some(time)
+    } else {
+        Some(format!("{}.{:03}", time, fraction))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_iso8601() {
+        assert_eq!(
+            format_iso8601(0.0, SasTemporalKind::Date),
+            Some("1960-01-01".to_string())
+        );
+        assert_eq!(
+            format_iso8601(23_406.0, SasTemporalKind::Date),
+            Some("2024-01-31".to_string())
+        );
+        assert_eq!(
+            format_iso8601(-1.0, SasTemporalKind::Date),
+            Some("1959-12-31".to_string())
+        );
+        assert_eq!(
+            format_iso8601(23_406.0 * 86_400.0 + 49_500.0, SasTemporalKind::DateTime),
+            Some("2024-01-31T13:45:00".to_string())
+        );
+        assert_eq!(
+            format_iso8601(49_500.25, SasTemporalKind::Time),
+            Some("13:45:00.250".to_string())
+        );
+        assert_eq!(
+            format_iso8601(90_000.0, SasTemporalKind::Time),
+            Some("25:00:00".to_string())
+        );
+        assert_eq!(format_iso8601(1e300, SasTemporalKind::Date), None);
+    }
+
+    #[test]
+    fn test_sas_to_utc() {
+        let datetime = sas_to_utc(2_022_327_900.5).unwrap();
+        assert_eq!(datetime.to_rfc3339(), "2024-01-31T13:45:00.500+00:00");
+        assert_eq!(sas_to_utc(f64::NAN), None);
+    }
+
+    #[test]
+    fn test_naive_to_sas() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        assert_eq!(naive_to_sas_date(date), 23_406.0);
+        assert_eq!(
+            naive_to_sas_date(NaiveDate::from_ymd_opt(1959, 12, 31).unwrap()),
+            -1.0
+        );
+        let datetime = date.and_hms_milli_opt(13, 45, 0, 250).unwrap();
+        assert_eq!(naive_to_sas_datetime(datetime), 2_022_327_900.25);
+        assert_eq!(sas_datetime_to_naive(2_022_327_900.25), Some(datetime));
+    }
+
+    #[test]
+    fn test_decode_temporal() {
+        let date = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        assert_eq!(
+            decode_temporal(23_406.9, SasTemporalKind::Date),
+            Some(SasTemporalValue::Date(date))
+        );
+        assert_eq!(
+            decode_temporal(2_022_327_900.123456, SasTemporalKind::DateTime),
+            Some(SasTemporalValue::DateTime(
+                date.and_hms_micro_opt(13, 45, 0, 123_456).unwrap()
+            ))
+        );
+        assert_eq!(
+            decode_temporal(-0.5, SasTemporalKind::DateTime),
+            Some(SasTemporalValue::DateTime(
+                NaiveDate::from_ymd_opt(1959, 12, 31)
+                    .unwrap()
+                    .and_hms_milli_opt(23, 59, 59, 500)
+                    .unwrap()
+            ))
+        );
+        assert_eq!(
+            decode_temporal(49_507.25, SasTemporalKind::Time),
+            Some(SasTemporalValue::Time(
+                NaiveTime::from_hms_milli_opt(13, 45, 7, 250).unwrap()
+            ))
+        );
+        assert_eq!(decode_temporal(86_399.9999999, SasTemporalKind::Time), None);
+        assert_eq!(decode_temporal(90_000.0, SasTemporalKind::Time), None);
+        assert_eq!(decode_temporal(-1.0, SasTemporalKind::Time), None);
+        assert_eq!(decode_temporal(f64::NAN, SasTemporalKind::Date), None);
+        assert_eq!(decode_temporal(1e300, SasTemporalKind::DateTime), None);
+    }
+
+    #[test]
+    fn test_decode_temporal_with_policy() {
+        use SasTemporalPolicy::*;
+        let date = |y, m, d| SasTemporalValue::Date(NaiveDate::from_ymd_opt(y, m, d).unwrap());
+
+        for policy in [Error, Missing, Clamp, Raw] {
+            assert_eq!(
+                decode_temporal_with(0.0, SasTemporalKind::Date, policy),
+                Ok(Some(date(1960, 1, 1)))
+            );
+            assert_eq!(
+                decode_temporal_with(f64::NAN, SasTemporalKind::DateTime, policy),
+                Ok(None)
+            );
+        }
+
+        // Out of range, infinite and fractional dates
+        for value in [1e13, f64::INFINITY, 23_406.5] {
+            for kind in [SasTemporalKind::Date, SasTemporalKind::DateTime] {
+                if kind == SasTemporalKind::DateTime && value == 23_406.5 {
+                    continue;
+                }
+                assert_eq!(
+                    decode_temporal_with(value, kind, Error),
+                    Err(ReadStatError::BadTimestampValue)
+                );
+                assert_eq!(decode_temporal_with(value, kind, Missing), Ok(None));
+                assert_eq!(
+                    decode_temporal_with(value, kind, Raw),
+                    Ok(Some(SasTemporalValue::Raw(value)))
+                );
+            }
+        }
+        assert_eq!(
+            decode_temporal_with(23_406.5, SasTemporalKind::Date, Clamp),
+            Ok(Some(date(2024, 1, 31)))
+        );
+        assert_eq!(
+            decode_temporal_with(1e13, SasTemporalKind::Date, Clamp),
+            Ok(Some(SasTemporalValue::Date(NaiveDate::MAX)))
+        );
+        assert_eq!(
+            decode_temporal_with(f64::NEG_INFINITY, SasTemporalKind::DateTime, Clamp),
+            Ok(Some(SasTemporalValue::DateTime(
+                NaiveDate::MIN.and_time(NaiveTime::MIN)
+            )))
+        );
+        assert_eq!(
+            decode_temporal_with(90_000.0, SasTemporalKind::Time, Clamp),
+            Ok(Some(SasTemporalValue::Time(
+                NaiveTime::from_hms_micro_opt(23, 59, 59, 999_999).unwrap()
+            )))
+        );
+        assert_eq!(
+            decode_temporal_with(-1.0, SasTemporalKind::Time, Error),
+            Err(ReadStatError::BadTimestampValue)
+        );
+    }
+
+    #[test]
+    fn test_format_iso8601_with_policy() {
+        use SasTemporalPolicy::*;
+        let iso = |text: &str| Ok(Some(SasTemporalText::Iso8601(text.to_string())));
+
+        for policy in [Error, Missing, Clamp, Raw] {
+            assert_eq!(
+                format_iso8601_with(23_406.0, SasTemporalKind::Date, policy),
+                iso("2024-01-31")
+            );
+            assert_eq!(
+                format_iso8601_with(f64::NAN, SasTemporalKind::Date, policy),
+                Ok(None)
+            );
+        }
+        assert_eq!(
+            format_iso8601_with(1e13, SasTemporalKind::Date, Error),
+            Err(ReadStatError::BadTimestampValue)
+        );
+        assert_eq!(
+            format_iso8601_with(1e13, SasTemporalKind::Date, Missing),
+            Ok(None)
+        );
+        assert_eq!(
+            format_iso8601_with(1e13, SasTemporalKind::Date, Clamp),
+            Ok(Some(SasTemporalText::Iso8601(
+                NaiveDate::MAX.format(ISO8601_DATE).to_string()
+            )))
+        );
+        assert_eq!(
+            format_iso8601_with(1e13, SasTemporalKind::Date, Raw),
+            Ok(Some(SasTemporalText::Raw(1e13)))
+        );
+        assert_eq!(
+            format_iso8601_with(1e15, SasTemporalKind::Time, Clamp),
+            iso("23:59:59.999999")
+        );
+        assert_eq!(
+            format_iso8601_with(49_500.25, SasTemporalKind::Time, Error),
+            iso("13:45:00.250")
+        );
+
+        // Fractional dates and times from 24 hours on are not rendered leniently
+        for (value, kind) in [
+            (23_406.5, SasTemporalKind::Date),
+            (86_400.0, SasTemporalKind::Time),
+            (90_000.0, SasTemporalKind::Time),
+        ] {
+            assert_eq!(
+                format_iso8601_with(value, kind, Error),
+                Err(ReadStatError::BadTimestampValue)
+            );
+            assert_eq!(format_iso8601_with(value, kind, Missing), Ok(None));
+            assert_eq!(
+                format_iso8601_with(value, kind, Raw),
+                Ok(Some(SasTemporalText::Raw(value)))
+            );
+        }
+        assert_eq!(
+            format_iso8601_with(23_406.5, SasTemporalKind::Date, Clamp),
+            iso("2024-01-31")
+        );
+        assert_eq!(
+            format_iso8601_with(90_000.0, SasTemporalKind::Time, Clamp),
+            iso("23:59:59.999999")
+        );
+    }
+}
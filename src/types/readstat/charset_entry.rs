@@ -31,7 +31,6 @@ impl ReadStatCharsetEntryBuilder {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
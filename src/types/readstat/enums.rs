@@ -1,4 +1,5 @@
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatHandler {
     Ok = 0,
     Abort = -1,
@@ -6,6 +7,7 @@ pub enum ReadStatHandler {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatType {
     String,
     Int8,
@@ -22,26 +24,33 @@ pub enum ReadStatType {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatEncoding {
     Utf8,
     Latin1,
     Windows1252,
     Ascii,
+
+    /// Any other character set, identified by its iconv name (eg `"CP1250"`).
+    Other(String),
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatFileLabel {
     Sas,
     Xport,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatTypeClass {
     String,
     Numeric,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatMeasure {
     Nominal,
     Ordinal,
@@ -49,6 +58,7 @@ pub enum ReadStatMeasure {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatAlignment {
     Left,
     Center,
@@ -56,6 +66,7 @@ pub enum ReadStatAlignment {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatCompression {
     None,
     Rows,
@@ -63,12 +74,14 @@ pub enum ReadStatCompression {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatEndianness {
     Little,
     Big,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatError {
     Open,
     Read,
@@ -109,9 +122,15 @@ pub enum ReadStatError {
     TooManyColumns,
     NameIsZeroLength,
     BadTimestampValue,
+    UnknownColumn,
+    TooManyPages,
+    ColumnTextTooLarge,
+    TooManyCatalogEntries,
+    BadBatchSize,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatValueType {
     String(String),
     Int8(i8),
@@ -122,8 +141,29 @@ pub enum ReadStatValueType {
     UInt16(u16),
     UInt32(u32),
     UInt64(u64),
-    Float(f32),
-    Double(f64),
+    Float(#[cfg_attr(feature = "serde", serde(with = "nan_as_none"))] f32),
+    Double(#[cfg_attr(feature = "serde", serde(with = "nan_as_none"))] f64),
+}
+
+/// Missing numeric values are NaN, which JSON cannot hold: serde_json writes it as `null` but
+/// does not read `null` back as a number. NaN is (de)serialized as `None` here instead.
+#[cfg(feature = "serde")]
+pub(crate) mod nan_as_none {
+    use num::Float;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer, T: Float + Serialize>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        (!value.is_nan()).then_some(*value).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Float + Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_else(T::nan))
+    }
 }
 
 // typedef enum readstat_io_flags_e {
@@ -132,6 +172,7 @@ pub enum ReadStatValueType {
 // READSTAT_SEEK_END
 // } readstat_io_flags_t;
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatIoFlags {
     SeekSet,
     SeekCurrent,
@@ -143,6 +184,7 @@ pub enum ReadStatIoFlags {
 // READSTAT_VENDOR_SAS
 // };
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ReadStatVendor {
     StatTransfer,
     Sas,
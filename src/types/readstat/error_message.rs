@@ -1,84 +1,80 @@
 use crate::types::readstat::ReadStatError;
+use std::fmt;
 
+/// Format an error the way readstat's `readstat_error_message` does, followed by the error code.
 pub fn readstat_error_message(err: ReadStatError, error_code: i32) -> String {
-    match err {
-        ReadStatError::Open => format!("Error opening file: {}", error_code),
-        ReadStatError::Read => format!("Error reading file: {}", error_code),
-        ReadStatError::Malloc => format!("Error allocating memory: {}", error_code),
-        ReadStatError::UserAbort => format!("User aborted: {}", error_code),
-        ReadStatError::Parse => format!("Error parsing file: {}", error_code),
-        ReadStatError::UnsupportedCompression => {
-            format!("Unsupported compression: {}", error_code)
-        }
-        ReadStatError::UnsupportedCharset => format!("Unsupported charset: {}", error_code),
-        ReadStatError::ColumnCountMismatch => format!("Column count mismatch: {}", error_code),
-        ReadStatError::RowCountMismatch => format!("Row count mismatch: {}", error_code),
-        ReadStatError::RowWidthMismatch => format!("Row width mismatch: {}", error_code),
-        ReadStatError::BadFormatString => format!("Bad format string: {}", error_code),
-        ReadStatError::ValueTypeMismatch => format!("Value type mismatch: {}", error_code),
-        ReadStatError::Write => format!("Error writing file: {}", error_code),
-        ReadStatError::WriterNotInitialized => {
-            format!("Writer not initialized: {}", error_code)
-        }
-        ReadStatError::Seek => format!("Error seeking file: {}", error_code),
-        ReadStatError::Convert => format!("Error converting value: {}", error_code),
-        ReadStatError::ConvertBadString => {
-            format!("Error converting bad string: {}", error_code)
-        }
-        ReadStatError::ConvertShortString => {
-            format!("Error converting short string: {}", error_code)
-        }
-        ReadStatError::ConvertLongString => {
-            format!("Error converting long string: {}", error_code)
-        }
-        ReadStatError::NumericValueOutOfRange => {
-            format!("Numeric value out of range: {}", error_code)
-        }
-        ReadStatError::TaggedValueOutOfRange => {
-            format!("Tagged value out of range: {}", error_code)
-        }
-        ReadStatError::StringValueTooLong => format!("String value too long: {}", error_code),
-        ReadStatError::TaggedValuesNotSupported => {
-            format!("Tagged values not supported: {}", error_code)
-        }
-        ReadStatError::UnsupportedFileFormatVersion => {
-            format!("Unsupported file format version: {}", error_code)
-        }
-        ReadStatError::NameBeginsWithIllegalCharacter => {
-            format!("Name begins with illegal character: {}", error_code)
-        }
-        ReadStatError::NameContainsIllegalCharacter => {
-            format!("Name contains illegal character: {}", error_code)
-        }
-        ReadStatError::NameIsReservedKeyword => {
-            format!("Name is reserved keyword: {}", error_code)
-        }
-        ReadStatError::NameIsTooLong => format!("Name is too long: {}", error_code),
-        ReadStatError::BadTimestampString => format!("Bad timestamp string: {}", error_code),
-        ReadStatError::BadFrequencyWeight => format!("Bad frequency weight: {}", error_code),
-        ReadStatError::TooManyMissingValueDefinitions => {
-            format!("Too many missing value definitions: {}", error_code)
-        }
-        ReadStatError::NoteIsTooLong => format!("Note is too long: {}", error_code),
-        ReadStatError::StringRefsNotSupported => {
-            format!("String refs not supported: {}", error_code)
-        }
-        ReadStatError::StringRefIsRequired => format!("String ref is required: {}", error_code),
-        ReadStatError::RowIsTooWideForPage => {
-            format!("Row is too wide for page: {}", error_code)
-        }
-        ReadStatError::TooFewColumns => format!("Too few columns: {}", error_code),
-        ReadStatError::TooManyColumns => format!("Too many columns: {}", error_code),
-        ReadStatError::NameIsZeroLength => format!("Name is zero length: {}", error_code),
-        ReadStatError::BadTimestampValue => format!("Bad timestamp value: {}", error_code),
+    format!("{}: {}", err, error_code)
+}
+
+impl fmt::Display for ReadStatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ReadStatError::Open => "Error opening file",
+            ReadStatError::Read => "Error reading file",
+            ReadStatError::Malloc => "Error allocating memory",
+            ReadStatError::UserAbort => "User aborted",
+            ReadStatError::Parse => "Error parsing file",
+            ReadStatError::UnsupportedCompression => "Unsupported compression",
+            ReadStatError::UnsupportedCharset => "Unsupported charset",
+            ReadStatError::ColumnCountMismatch => "Column count mismatch",
+            ReadStatError::RowCountMismatch => "Row count mismatch",
+            ReadStatError::RowWidthMismatch => "Row width mismatch",
+            ReadStatError::BadFormatString => "Bad format string",
+            ReadStatError::ValueTypeMismatch => "Value type mismatch",
+            ReadStatError::Write => "Error writing file",
+            ReadStatError::WriterNotInitialized => "Writer not initialized",
+            ReadStatError::Seek => "Error seeking file",
+            ReadStatError::Convert => "Error converting value",
+            ReadStatError::ConvertBadString => "Error converting bad string",
+            ReadStatError::ConvertShortString => "Error converting short string",
+            ReadStatError::ConvertLongString => "Error converting long string",
+            ReadStatError::NumericValueOutOfRange => "Numeric value out of range",
+            ReadStatError::TaggedValueOutOfRange => "Tagged value out of range",
+            ReadStatError::StringValueTooLong => "String value too long",
+            ReadStatError::TaggedValuesNotSupported => "Tagged values not supported",
+            ReadStatError::UnsupportedFileFormatVersion => "Unsupported file format version",
+            ReadStatError::NameBeginsWithIllegalCharacter => "Name begins with illegal character",
+            ReadStatError::NameContainsIllegalCharacter => "Name contains illegal character",
+            ReadStatError::NameIsReservedKeyword => "Name is reserved keyword",
+            ReadStatError::NameIsTooLong => "Name is too long",
+            ReadStatError::BadTimestampString => "Bad timestamp string",
+            ReadStatError::BadFrequencyWeight => "Bad frequency weight",
+            ReadStatError::TooManyMissingValueDefinitions => "Too many missing value definitions",
+            ReadStatError::NoteIsTooLong => "Note is too long",
+            ReadStatError::StringRefsNotSupported => "String refs not supported",
+            ReadStatError::StringRefIsRequired => "String ref is required",
+            ReadStatError::RowIsTooWideForPage => "Row is too wide for page",
+            ReadStatError::TooFewColumns => "Too few columns",
+            ReadStatError::TooManyColumns => "Too many columns",
+            ReadStatError::NameIsZeroLength => "Name is zero length",
+            ReadStatError::BadTimestampValue => "Bad timestamp value",
+            ReadStatError::UnknownColumn => "Unknown column",
+            ReadStatError::TooManyPages => "Too many pages",
+            ReadStatError::ColumnTextTooLarge => "Column text too large",
+            ReadStatError::TooManyCatalogEntries => "Too many catalog entries",
+            ReadStatError::BadBatchSize => "Batch size must be at least 1",
+        };
+        f.write_str(message)
     }
 }
 
-#[cfg(test)]
+impl std::error::Error for ReadStatError {}
 
+#[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_display() {
+        assert_eq!(ReadStatError::Seek.to_string(), "Error seeking file");
+        assert_eq!(ReadStatError::UnknownColumn.to_string(), "Unknown column");
+        assert_eq!(ReadStatError::TooManyPages.to_string(), "Too many pages");
+        assert_eq!(
+            ReadStatError::BadBatchSize.to_string(),
+            "Batch size must be at least 1"
+        );
+    }
+
     #[test]
     fn test_readstat_error_message() {
         assert_eq!(
@@ -238,5 +234,9 @@ mod tests {
             readstat_error_message(ReadStatError::BadTimestampValue, 39),
             "Bad timestamp value: 39"
         );
+        assert_eq!(
+            readstat_error_message(ReadStatError::UnknownColumn, 40),
+            "Unknown column: 40"
+        );
     }
 }
@@ -13,55 +13,138 @@
 // int                            io_ctx_needs_free;
 // } readstat_io_t;
 
-use crate::types::ReadStatError;
-use std::fs::{File, Path};
+use crate::types::{ReadStatError, ReadStatIoFlags, SasError};
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
 
-/// ReadStatIO is a struct that contains the file path and the file I/O handlers.
-/// The file I/O handlers are used to open, close, seek, read, and update the file.
-/// Since this is implemented in Rust instead of C, the file I/O handlers are implemented as
-/// methods, instead of function pointers.
+/// ReadStatIO is the source a parser reads a file from. It replaces the seek and read handlers
+/// of readstat's `readstat_io_t`; opening and closing are left to the owner of the source, which
+/// is dropped to close it, and the update handler is replaced by `ReadStatProgressHandler`.
 ///
-/// Rust features that simplify the implementation of the file I/O handlers include:
-/// - The `std::fs::File` struct, which provides methods for opening, closing, seeking, and reading
-/// files.
-/// - The `std::fs::File` struct, which provides a `metadata` method that returns a `Metadata`
-/// struct that contains the file size.
-/// - The `std::fs::File` struct, which provides a `set_len` method that sets the file size.
-pub struct ReadStatIO {
-    path: Path,
-    file: Option<File>,
-}
+/// It is implemented for every `Read + Seek` type, so files, in-memory buffers such as
+/// `Cursor<Vec<u8>>`, seekable decompression streams and user-defined sources can all be parsed
+/// without first being written to disk.
+pub trait ReadStatIO {
+    /// Move to `offset` bytes from the position given by `whence`.
+    ///
+    /// # Returns
+    /// The new position from the start of the source, or an error of kind
+    /// `ReadStatError::Seek` if it would be before the start or the source cannot seek.
+    fn seek(&mut self, offset: i64, whence: ReadStatIoFlags) -> Result<u64, SasError>;
 
-impl ReadStatIO {
-    /// Create a new `ReadStatIO` struct with the given file path and no file.
-    pub fn new(path: Path) -> Self {
-        Self { path, file: None }
-    }
+    /// Read up to `buf.len()` bytes at the current position.
+    ///
+    /// # Returns
+    /// The number of bytes read, which is 0 at the end of the source, or an error of kind
+    /// `ReadStatError::Read`.
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, SasError>;
 
-    /// Open the file at the given path.
-    pub fn open(&mut self) -> Result<(), ReadStatError> {
-        self.file = Some(File::open(&self.path)?);
+    /// Fill `buf` from the current position.
+    ///
+    /// # Returns
+    /// An error of kind `ReadStatError::Read` if the source ends first.
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), SasError> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.read(&mut buf[filled..])? {
+                0 => {
+                    let eof = ErrorKind::UnexpectedEof.into();
+                    return Err(SasError::io(ReadStatError::Read, eof));
+                }
+                count => filled += count,
+            }
+        }
         Ok(())
     }
 
-    /// Close the file.
-    pub fn close(&mut self) -> Result<(), ReadStatError> {
-        self.file = None;
-        Ok(())
+    /// Return the size of the source in bytes, leaving the position unchanged.
+    fn size(&mut self) -> Result<u64, SasError> {
+        let position = self.seek(0, ReadStatIoFlags::SeekCurrent)?;
+        let size = self.seek(0, ReadStatIoFlags::SeekEnd)?;
+        self.seek(position as i64, ReadStatIoFlags::SeekSet)?;
+        Ok(size)
     }
+}
 
-    /// Seek to the given offset in the file.
-    pub fn seek(&mut self, offset: i64, whence: std::io::SeekFrom) -> Result<(), ReadStatError> {
-        self.file.as_mut().unwrap().seek(whence)?;
-        Ok(())
+impl<T: Read + Seek> ReadStatIO for T {
+    fn seek(&mut self, offset: i64, whence: ReadStatIoFlags) -> Result<u64, SasError> {
+        let position = match whence {
+            ReadStatIoFlags::SeekSet => {
+                SeekFrom::Start(u64::try_from(offset).map_err(|_| ReadStatError::Seek)?)
+            }
+            ReadStatIoFlags::SeekCurrent => SeekFrom::Current(offset),
+            ReadStatIoFlags::SeekEnd => SeekFrom::End(offset),
+        };
+        Seek::seek(self, position).map_err(|err| SasError::io(ReadStatError::Seek, err))
     }
 
-    pub fn read(&mut self, buf: &mut [u8]) -> Result<usize, ReadStatError> {
-        self.file.as_mut().unwrap().read(buf)
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, SasError> {
+        loop {
+            match Read::read(self, buf) {
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                result => return result.map_err(|err| SasError::io(ReadStatError::Read, err)),
+            }
+        }
     }
+}
 
-    pub fn update(&mut self, file_size: u64) -> Result<(), ReadStatError> {
-        self.file.as_mut().unwrap().set_len(file_size)?;
-        Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_seek_and_read() {
+        let mut io = Cursor::new((0..10).collect::<Vec<u8>>());
+        assert_eq!(
+            ReadStatIO::seek(&mut io, 4, ReadStatIoFlags::SeekSet).unwrap(),
+            4
+        );
+        assert_eq!(
+            ReadStatIO::seek(&mut io, 2, ReadStatIoFlags::SeekCurrent).unwrap(),
+            6
+        );
+        assert_eq!(
+            ReadStatIO::seek(&mut io, -3, ReadStatIoFlags::SeekEnd).unwrap(),
+            7
+        );
+
+        let mut buf = [0; 2];
+        assert_eq!(ReadStatIO::read(&mut io, &mut buf).unwrap(), 2);
+        assert_eq!(buf, [7, 8]);
+        assert_eq!(ReadStatIO::read(&mut io, &mut buf).unwrap(), 1);
+        assert_eq!(ReadStatIO::read(&mut io, &mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_read_exact() {
+        let mut io = Cursor::new(vec![1, 2, 3]);
+        let mut buf = [0; 2];
+        ReadStatIO::read_exact(&mut io, &mut buf).unwrap();
+        assert_eq!(buf, [1, 2]);
+        assert_eq!(
+            ReadStatIO::read_exact(&mut io, &mut buf).map_err(|err| err.kind),
+            Err(ReadStatError::Read)
+        );
+    }
+
+    #[test]
+    fn test_seek_errors_and_size() {
+        let mut io = Cursor::new(vec![0; 16]);
+        assert_eq!(
+            ReadStatIO::seek(&mut io, -1, ReadStatIoFlags::SeekSet).map_err(|err| err.kind),
+            Err(ReadStatError::Seek)
+        );
+        let err = ReadStatIO::seek(&mut io, -17, ReadStatIoFlags::SeekEnd).unwrap_err();
+        assert_eq!(err.kind, ReadStatError::Seek);
+        assert_eq!(
+            err.io_error().map(|err| err.kind()),
+            Some(ErrorKind::InvalidInput)
+        );
+        ReadStatIO::seek(&mut io, 5, ReadStatIoFlags::SeekSet).unwrap();
+        assert_eq!(ReadStatIO::size(&mut io).unwrap(), 16);
+        assert_eq!(
+            ReadStatIO::seek(&mut io, 0, ReadStatIoFlags::SeekCurrent).unwrap(),
+            5
+        );
     }
 }
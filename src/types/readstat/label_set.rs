@@ -2,6 +2,7 @@ use crate::types::{ReadStatType, ReadStatValueLabel, SasLabelName};
 use derive_builder::Builder;
 
 #[derive(Debug, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadStatLabelSet {
     pub type_: ReadStatType,
     pub name: SasLabelName,
@@ -44,7 +45,7 @@ mod tests {
         let label_set = ReadStatLabelSet::new(
             ReadStatType::Int64,
             SasLabelName::from_str("label"),
-            ReadStatValueLabel::new(String::new(), 0, 0.0, 0, String::new(), 0, Vec::new()),
+            ReadStatValueLabel::new(String::new(), 0, 0.0, 0, String::new(), 0, None),
             0,
             0,
         );
@@ -1,15 +1,18 @@
 use crate::types::{
     ReadStatCompression, ReadStatEncoding, ReadStatEndianness, ReadStatFileLabel, SasTableName,
 };
-use datetime::Instant;
+use chrono::{DateTime, Utc};
 use derive_builder::Builder;
 
-#[derive(Debug, Clone, Builder)]
+/// The properties of a dataset as a whole. Creation and modification times are UTC
+/// timestamps with a precision of one second.
+#[derive(Debug, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadStatMetadata {
     pub row_count: u128,
     pub var_count: u128,
-    pub created_at: Instant,
-    pub modified_at: Instant,
+    pub created_at: DateTime<Utc>,
+    pub modified_at: DateTime<Utc>,
     pub file_format_version: u16,
     pub compression: ReadStatCompression,
     pub endianness: ReadStatEndianness,
@@ -20,11 +23,12 @@ pub struct ReadStatMetadata {
 }
 
 impl ReadStatMetadata {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         row_count: u128,
         var_count: u128,
-        created_at: Instant,
-        modified_at: Instant,
+        created_at: DateTime<Utc>,
+        modified_at: DateTime<Utc>,
         file_format_version: u16,
         compression: ReadStatCompression,
         endianness: ReadStatEndianness,
@@ -54,12 +58,13 @@ impl ReadStatMetadata {
 }
 
 impl ReadStatMetadataBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             row_count: Some(0),
             var_count: Some(0),
-            created_at: Some(Instant::now()),
-            modified_at: Some(Instant::now()),
+            created_at: Some(Utc::now()),
+            modified_at: Some(Utc::now()),
             file_format_version: Some(0),
             compression: Some(ReadStatCompression::None),
             endianness: Some(ReadStatEndianness::Little),
@@ -72,7 +77,6 @@ impl ReadStatMetadataBuilder {
 }
 
 #[cfg(test)]
-
 mod tests {
 
     use super::*;
@@ -82,8 +86,8 @@ mod tests {
         let metadata = ReadStatMetadata::new(
             100,
             10,
-            Instant::now(),
-            Instant::now(),
+            Utc::now(),
+            Utc::now(),
             0,
             ReadStatCompression::None,
             ReadStatEndianness::Little,
@@ -104,7 +108,7 @@ mod tests {
         );
         assert_eq!(metadata.file_label, ReadStatFileLabel::Sas);
         assert_eq!(metadata.file_encoding, ReadStatEncoding::Latin1);
-        assert_eq!(metadata.is_64bit, false);
+        assert!(!metadata.is_64bit);
     }
 
     #[test]
@@ -112,8 +116,8 @@ mod tests {
         let metadata = ReadStatMetadata::builder()
             .row_count(100)
             .var_count(10)
-            .created_at(Instant::now())
-            .modified_at(Instant::now())
+            .created_at(Utc::now())
+            .modified_at(Utc::now())
             .file_format_version(0)
             .compression(ReadStatCompression::None)
             .endianness(ReadStatEndianness::Little)
@@ -135,7 +139,7 @@ mod tests {
         );
         assert_eq!(metadata.file_label, ReadStatFileLabel::Sas);
         assert_eq!(metadata.file_encoding, ReadStatEncoding::Latin1);
-        assert_eq!(metadata.is_64bit, false);
+        assert!(!metadata.is_64bit);
     }
 
     #[test]
@@ -155,6 +159,26 @@ mod tests {
         );
         assert_eq!(metadata.file_label, ReadStatFileLabel::Sas);
         assert_eq!(metadata.file_encoding, ReadStatEncoding::Latin1);
-        assert_eq!(metadata.is_64bit, false);
+        assert!(!metadata.is_64bit);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_readstat_metadata_serde_round_trip() {
+        let created_at = DateTime::from_timestamp(1_706_708_700, 0).unwrap();
+        let metadata = ReadStatMetadata::builder()
+            .created_at(created_at)
+            .modified_at(created_at)
+            .file_encoding(ReadStatEncoding::Other("cp1252".to_string()))
+            .build()
+            .expect("Failed to build metadata");
+
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(json["created_at"], "2024-01-31T13:45:00Z");
+        assert_eq!(json["file_encoding"]["Other"], "cp1252");
+        assert_eq!(
+            serde_json::from_value::<ReadStatMetadata>(json).unwrap(),
+            metadata
+        );
     }
 }
@@ -2,6 +2,7 @@ use crate::types::ReadStatValue;
 use derive_builder::Builder;
 
 #[derive(Debug, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadStatMissingRanges {
     pub missing_ranges: Vec<ReadStatValue>,
     pub missing_ranges_count: u32,
@@ -21,6 +22,7 @@ impl ReadStatMissingRanges {
 }
 
 impl ReadStatMissingRangesBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             missing_ranges: Some(Vec::with_capacity(32)),
@@ -30,7 +32,6 @@ impl ReadStatMissingRangesBuilder {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
@@ -1,9 +1,11 @@
 pub mod enums;
 pub mod error_message;
+pub mod io;
 pub mod label_set;
 pub mod metadata;
 pub mod missing_ranges;
 pub mod missing_value;
+pub mod progress;
 pub mod schema;
 pub mod schema_entry;
 pub mod value;
@@ -12,10 +14,12 @@ pub mod variable;
 
 pub use enums::*;
 pub use error_message::*;
+pub use io::*;
 pub use label_set::*;
 pub use metadata::*;
 pub use missing_ranges::*;
 pub use missing_value::*;
+pub use progress::*;
 pub use schema::*;
 pub use schema_entry::*;
 pub use value::*;
@@ -0,0 +1,131 @@
+// typedef int (*readstat_progress_handler)(double progress, void *ctx);
+
+use crate::types::{ReadStatError, ReadStatHandler};
+use std::fmt;
+
+/// How far a read has got through a file. Totals come from the header page count and the row
+/// count of the metadata; bytes are counted in whole pages after the header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadStatProgress {
+    pub bytes_read: u64,
+    pub bytes_total: u64,
+    pub pages_read: u64,
+    pub page_count: u64,
+    pub rows_read: u64,
+    pub row_count: u64,
+}
+
+impl ReadStatProgress {
+    pub fn new(
+        bytes_read: u64,
+        bytes_total: u64,
+        pages_read: u64,
+        page_count: u64,
+        rows_read: u64,
+        row_count: u64,
+    ) -> Self {
+        Self {
+            bytes_read,
+            bytes_total,
+            pages_read,
+            page_count,
+            rows_read,
+            row_count,
+        }
+    }
+
+    /// The fraction of the rows read so far, between 0 and 1, like the `progress` argument of
+    /// readstat's progress handler. Files without rows count as complete.
+    pub fn fraction(&self) -> f64 {
+        if self.row_count == 0 {
+            1.0
+        } else {
+            (self.rows_read as f64 / self.row_count as f64).min(1.0)
+        }
+    }
+}
+
+pub type ReadStatProgressCallback = Box<dyn FnMut(&ReadStatProgress) -> ReadStatHandler + Send>;
+
+/// A progress callback and how often to call it. Returning `ReadStatHandler::Abort` from the
+/// callback stops the read with `ReadStatError::UserAbort`.
+pub struct ReadStatProgressHandler {
+    callback: ReadStatProgressCallback,
+    granularity: u64,
+}
+
+impl ReadStatProgressHandler {
+    /// Create a handler calling `callback` every `granularity` pages, and once more when the
+    /// last row has been read. A granularity of 0 is treated as 1.
+    pub fn new(granularity: u64, callback: ReadStatProgressCallback) -> Self {
+        Self {
+            callback,
+            granularity: granularity.max(1),
+        }
+    }
+
+    /// Report progress to the callback if it is due.
+    pub fn report(&mut self, progress: &ReadStatProgress) -> Result<(), ReadStatError> {
+        let done =
+            progress.rows_read >= progress.row_count || progress.pages_read >= progress.page_count;
+        if !done && !progress.pages_read.is_multiple_of(self.granularity) {
+            return Ok(());
+        }
+        match (self.callback)(progress) {
+            ReadStatHandler::Abort => Err(ReadStatError::UserAbort),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for ReadStatProgressHandler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReadStatProgressHandler")
+            .field("granularity", &self.granularity)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn progress(pages_read: u64, rows_read: u64) -> ReadStatProgress {
+        ReadStatProgress::new(pages_read * 10, 100, pages_read, 10, rows_read, 1000)
+    }
+
+    #[test]
+    fn test_fraction() {
+        assert_eq!(progress(5, 250).fraction(), 0.25);
+        assert_eq!(ReadStatProgress::new(0, 0, 0, 0, 0, 0).fraction(), 1.0);
+    }
+
+    #[test]
+    fn test_report_granularity() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&seen);
+        let mut handler = ReadStatProgressHandler::new(
+            3,
+            Box::new(move |progress| {
+                log.lock().unwrap().push(progress.pages_read);
+                ReadStatHandler::Ok
+            }),
+        );
+        for pages_read in 1..=10 {
+            handler
+                .report(&progress(pages_read, pages_read * 100))
+                .unwrap();
+        }
+        assert_eq!(*seen.lock().unwrap(), vec![3, 6, 9, 10]);
+    }
+
+    #[test]
+    fn test_report_abort() {
+        let mut handler = ReadStatProgressHandler::new(0, Box::new(|_| ReadStatHandler::Abort));
+        assert_eq!(
+            handler.report(&progress(1, 100)),
+            Err(ReadStatError::UserAbort)
+        );
+    }
+}
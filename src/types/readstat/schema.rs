@@ -49,6 +49,7 @@ impl ReadStatSchema {
 }
 
 impl ReadStatSchemaBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             filename: Some(String::new()),
@@ -63,7 +64,6 @@ impl ReadStatSchemaBuilder {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
 
@@ -63,7 +63,7 @@ mod tests {
         assert_eq!(schema_entry.row, 0);
         assert_eq!(schema_entry.col, 0);
         assert_eq!(schema_entry.len, 0);
-        assert_eq!(schema_entry.skip, false);
+        assert!(!schema_entry.skip);
         assert_eq!(schema_entry.labelset, String::new());
         assert_eq!(schema_entry.decimal_separator, ' ');
     }
@@ -1,49 +1,94 @@
 use crate::sas::is_missing;
-use crate::types::{ReadStatType, ReadStatValueType};
+use crate::types::{ReadStatType, ReadStatValueType, SasMissing};
 use derive_builder::Builder;
 
 #[derive(Debug, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadStatValue {
     pub type_: ReadStatType,
-    pub tags: Vec<String>,
+    /// The kind of missing value a NaN numeric value holds. None for values that are not
+    /// missing; a NaN without a kind is system missing.
+    pub missing: Option<SasMissing>,
     pub value: ReadStatValueType,
 }
 
 impl ReadStatValue {
-    pub fn new(type_: ReadStatType, tags: Vec<String>, value: ReadStatValueType) -> Self {
-        Self { type_, tags, value }
+    pub fn new(type_: ReadStatType, missing: Option<SasMissing>, value: ReadStatValueType) -> Self {
+        Self {
+            type_,
+            missing,
+            value,
+        }
     }
 
     pub fn builder() -> ReadStatValueBuilder {
         ReadStatValueBuilder::default()
     }
 
+    /// Build a missing numeric value of the given kind.
+    pub fn missing(kind: SasMissing) -> Self {
+        Self::new(
+            ReadStatType::Double,
+            Some(kind),
+            ReadStatValueType::Double(f64::NAN),
+        )
+    }
+
+    /// Return the kind of missing value a numeric value holds, or None if it is not a missing
+    /// number.
+    pub fn missing_kind(&self) -> Option<SasMissing> {
+        let is_nan = match self.value {
+            ReadStatValueType::Double(v) => v.is_nan(),
+            ReadStatValueType::Float(v) => v.is_nan(),
+            _ => false,
+        };
+        is_nan.then(|| self.missing.unwrap_or(SasMissing::System))
+    }
+
     /// Return true if the value is "system missing", eg if it was delivered to value
-    /// handlers as NaN.
+    /// handlers as NaN without a tag.
     pub fn is_system_missing(&self) -> bool {
         match self.value {
-            ReadStatValueType::Double(v) => v.is_nan(),
-            ReadStatValueType::String(ref v) => v.trim().is_empty() || *v == " ".to_string(),
-            _ => false,
+            ReadStatValueType::String(ref v) => v.trim().is_empty() || v == " ",
+            _ => self.missing_kind() == Some(SasMissing::System),
         }
     }
 
     /// Return true if the value is "tagged missing", eg if it was delivered to value
+    /// handlers as NaN with a tag such as `.A` or `._`.
     pub fn is_tagged_missing_by_sas(&self) -> bool {
-        is_missing::is_tagged_missing(&self)
+        is_missing::is_tagged_missing(self)
     }
 
     /// Return true if the value is missing, either system missing or tagged missing.
     pub fn is_missing(&self) -> bool {
         self.is_system_missing() || self.is_tagged_missing_by_sas()
     }
+
+    /// Return the value as a double if it is numeric, including NaN for missing values.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.value {
+            ReadStatValueType::String(_) => None,
+            ReadStatValueType::Int8(v) => Some(v as f64),
+            ReadStatValueType::Int16(v) => Some(v as f64),
+            ReadStatValueType::Int32(v) => Some(v as f64),
+            ReadStatValueType::Int64(v) => Some(v as f64),
+            ReadStatValueType::UInt8(v) => Some(v as f64),
+            ReadStatValueType::UInt16(v) => Some(v as f64),
+            ReadStatValueType::UInt32(v) => Some(v as f64),
+            ReadStatValueType::UInt64(v) => Some(v as f64),
+            ReadStatValueType::Float(v) => Some(v as f64),
+            ReadStatValueType::Double(v) => Some(v),
+        }
+    }
 }
 
 impl ReadStatValueBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             type_: Some(ReadStatType::Double),
-            tags: Some(vec!["".to_string()]),
+            missing: Some(None),
             value: Some(ReadStatValueType::Double(0.0)),
         }
     }
@@ -56,21 +101,16 @@ mod tests {
     #[test]
     fn test_read_stat_value() {
         let value = ReadStatValue::builder().build().unwrap();
-        let expected_tags = vec!["".to_string()];
         assert_eq!(value.type_, ReadStatType::Double);
-        assert_eq!(value.tags, expected_tags);
+        assert_eq!(value.missing, None);
         assert_eq!(value.value, ReadStatValueType::Double(0.0));
     }
 
     #[test]
     fn test_read_stat_value_new() {
-        let value = ReadStatValue::new(
-            ReadStatType::Double,
-            Vec::new(),
-            ReadStatValueType::Double(0.0),
-        );
+        let value = ReadStatValue::new(ReadStatType::Double, None, ReadStatValueType::Double(0.0));
         assert_eq!(value.type_, ReadStatType::Double);
-        assert_eq!(value.tags, Vec::<String>::new());
+        assert_eq!(value.missing, None);
         assert_eq!(value.value, ReadStatValueType::Double(0.0));
     }
 
@@ -80,22 +120,45 @@ mod tests {
             .value(ReadStatValueType::Double(f64::NAN))
             .build()
             .unwrap();
-        assert_eq!(value.is_system_missing(), true);
+        assert!(value.is_system_missing());
     }
 
     #[test]
     fn test_read_stat_value_is_tagged_missing() {
         let value = ReadStatValue::builder().build().unwrap();
-        assert_eq!(value.is_tagged_missing_by_sas(), false);
+        assert!(!value.is_tagged_missing_by_sas());
     }
 
     #[test]
     fn test_sas_missing_char() {
         let value = ReadStatValue::builder()
-            .tags(vec![String::from(".")])
+            .value(ReadStatValueType::Double(f64::NAN))
             .build()
             .unwrap();
-        assert_eq!(value.is_tagged_missing_by_sas(), true);
+        assert_eq!(value.missing_kind(), Some(SasMissing::System));
+        assert!(value.is_system_missing());
+        assert!(!value.is_tagged_missing_by_sas());
+    }
+
+    #[test]
+    fn test_read_stat_value_missing_kind() {
+        let refused = ReadStatValue::missing(SasMissing::Letter('R'));
+        let dont_know = ReadStatValue::missing(SasMissing::Letter('D'));
+        assert_eq!(refused.missing, Some(SasMissing::Letter('R')));
+        assert_eq!(refused.missing_kind(), Some(SasMissing::Letter('R')));
+        assert_eq!(dont_know.missing_kind(), Some(SasMissing::Letter('D')));
+        assert_ne!(refused.missing_kind(), dont_know.missing_kind());
+        assert!(refused.is_tagged_missing_by_sas());
+        assert!(!refused.is_system_missing());
+        assert!(refused.is_missing());
+
+        let system = ReadStatValue::missing(SasMissing::System);
+        assert!(system.is_system_missing());
+        assert!(!system.is_tagged_missing_by_sas());
+        assert_eq!(
+            ReadStatValue::builder().build().unwrap().missing_kind(),
+            None
+        );
     }
 
     #[test]
@@ -104,6 +167,21 @@ mod tests {
             .value(ReadStatValueType::Double(1.0))
             .build()
             .unwrap();
-        assert_eq!(value.is_missing(), false);
+        assert!(!value.is_missing());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_read_stat_value_serde_round_trip() {
+        let value = ReadStatValue::missing(SasMissing::Letter('A'));
+        let json = serde_json::to_value(&value).unwrap();
+        assert_eq!(json["value"]["Double"], serde_json::Value::Null);
+        assert_eq!(json["missing"]["Letter"], "A");
+        let parsed = serde_json::from_value::<ReadStatValue>(json).unwrap();
+        assert_eq!(parsed.missing_kind(), Some(SasMissing::Letter('A')));
+
+        let value = ReadStatValue::new(ReadStatType::Float, None, ReadStatValueType::Float(1.5));
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<ReadStatValue>(&json).unwrap(), value);
     }
 }
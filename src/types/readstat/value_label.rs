@@ -1,14 +1,18 @@
+use crate::types::SasMissing;
 use derive_builder::Builder;
 
 #[derive(Debug, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadStatValueLabel {
     pub label: String,
     pub label_length: u32,
+    #[cfg_attr(feature = "serde", serde(with = "super::enums::nan_as_none"))]
     pub double_key: f64,
     pub int32_key: i32,
     pub string_key: String,
     pub string_key_length: u32,
-    pub tags: Vec<String>,
+    /// The kind of missing value labelled by a NaN `double_key`.
+    pub missing: Option<SasMissing>,
 }
 
 impl ReadStatValueLabel {
@@ -19,7 +23,7 @@ impl ReadStatValueLabel {
         int32_key: i32,
         string_key: String,
         string_key_length: u32,
-        tags: Vec<String>,
+        missing: Option<SasMissing>,
     ) -> Self {
         Self {
             label,
@@ -28,7 +32,7 @@ impl ReadStatValueLabel {
             int32_key,
             string_key,
             string_key_length,
-            tags,
+            missing,
         }
     }
 
@@ -38,6 +42,7 @@ impl ReadStatValueLabel {
 }
 
 impl ReadStatValueLabelBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             label: Some(String::new()),
@@ -46,7 +51,7 @@ impl ReadStatValueLabelBuilder {
             int32_key: Some(0),
             string_key: Some(String::new()),
             string_key_length: Some(0),
-            tags: Some(Vec::new()),
+            missing: Some(None),
         }
     }
 }
@@ -64,6 +69,23 @@ mod tests {
         assert_eq!(value_label.int32_key, 0);
         assert_eq!(value_label.string_key, String::new());
         assert_eq!(value_label.string_key_length, 0);
-        assert_eq!(value_label.tags, Vec::<String>::new());
+        assert_eq!(value_label.missing, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_read_stat_value_label_serde_round_trip() {
+        let value_label = ReadStatValueLabel::builder()
+            .label("Refused".to_string())
+            .double_key(f64::NAN)
+            .missing(Some(SasMissing::Letter('R')))
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&value_label).unwrap();
+        assert_eq!(json["double_key"], serde_json::Value::Null);
+        let parsed = serde_json::from_value::<ReadStatValueLabel>(json.clone()).unwrap();
+        assert!(parsed.double_key.is_nan());
+        assert_eq!(parsed.missing, Some(SasMissing::Letter('R')));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
     }
 }
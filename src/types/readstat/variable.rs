@@ -1,15 +1,19 @@
+use crate::sas::temporal::{decode_temporal, decode_temporal_with};
 use crate::types::{
-    ReadStatAlignment, ReadStatLabelSet, ReadStatMeasure, ReadStatMissingRanges, ReadStatType,
-    ReadStatValueLabel, SasLabelName,
+    ReadStatAlignment, ReadStatError, ReadStatLabelSet, ReadStatMeasure, ReadStatMissingRanges,
+    ReadStatType, ReadStatValue, ReadStatValueLabel, SasFormat, SasLabelName, SasTemporalKind,
+    SasTemporalPolicy, SasTemporalValue,
 };
 use derive_builder::Builder;
 
 #[derive(Debug, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ReadStatVariable {
     pub type_: ReadStatType,
     pub index: u32,
     pub name: String,
     pub format: String,
+    pub informat: String,
     pub label: String,
     pub label_set: ReadStatLabelSet,
     pub offset: u64,
@@ -25,11 +29,13 @@ pub struct ReadStatVariable {
 }
 
 impl ReadStatVariable {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         type_: ReadStatType,
         index: u32,
         name: String,
         format: String,
+        informat: String,
         label: String,
         label_set: ReadStatLabelSet,
         offset: u64,
@@ -48,6 +54,7 @@ impl ReadStatVariable {
             index,
             name,
             format,
+            informat,
             label,
             label_set,
             offset,
@@ -66,20 +73,65 @@ impl ReadStatVariable {
     pub fn builder() -> ReadStatVariableBuilder {
         ReadStatVariableBuilder::default()
     }
+
+    /// The parsed display format, or None if the variable has none or it cannot be parsed.
+    pub fn sas_format(&self) -> Option<SasFormat> {
+        SasFormat::from_str(&self.format).ok()
+    }
+
+    /// The parsed input format, or None if the variable has none or it cannot be parsed.
+    pub fn sas_informat(&self) -> Option<SasFormat> {
+        SasFormat::from_str(&self.informat).ok()
+    }
+
+    /// Return whether the values of a numeric variable are dates, datetimes or times, as told
+    /// by its format, or None for other variables.
+    pub fn temporal_kind(&self) -> Option<SasTemporalKind> {
+        match self.type_ {
+            ReadStatType::String | ReadStatType::StringRef => None,
+            _ => self.sas_format()?.temporal_kind(),
+        }
+    }
+
+    /// Decode a value of this variable as a date, datetime or time, or return None if the
+    /// variable is not temporal or the value is missing or out of range.
+    pub fn temporal_value(&self, value: &ReadStatValue) -> Option<SasTemporalValue> {
+        decode_temporal(value.as_f64()?, self.temporal_kind()?)
+    }
+
+    /// Decode a value of this variable as `temporal_value` does, applying `policy` to values
+    /// that cannot be decoded. Returns None if the variable is not temporal or the value is
+    /// missing.
+    ///
+    /// # Returns
+    /// `ReadStatError::BadTimestampValue` for values that cannot be decoded under
+    /// `SasTemporalPolicy::Error`.
+    pub fn temporal_value_with(
+        &self,
+        value: &ReadStatValue,
+        policy: SasTemporalPolicy,
+    ) -> Result<Option<SasTemporalValue>, ReadStatError> {
+        match (value.as_f64(), self.temporal_kind()) {
+            (Some(number), Some(kind)) => decode_temporal_with(number, kind, policy),
+            _ => Ok(None),
+        }
+    }
 }
 
 impl ReadStatVariableBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             type_: Some(ReadStatType::Double),
             index: Some(0),
             name: Some(String::new()),
             format: Some(String::new()),
+            informat: Some(String::new()),
             label: Some(String::new()),
             label_set: Some(ReadStatLabelSet::new(
                 ReadStatType::Double,
                 SasLabelName::from_str("label"),
-                ReadStatValueLabel::new(String::new(), 0, 0.0, 0, String::new(), 0, Vec::new()),
+                ReadStatValueLabel::new(String::new(), 0, 0.0, 0, String::new(), 0, None),
                 0,
                 0,
             )),
@@ -98,7 +150,6 @@ impl ReadStatVariableBuilder {
 }
 
 #[cfg(test)]
-
 mod tests {
     use super::*;
     use crate::types::{ReadStatType, ReadStatValueLabel, SasLabelName};
@@ -136,6 +187,15 @@ mod tests {
         assert_eq!(variable.format, String::from("format"));
     }
 
+    #[test]
+    fn test_read_stat_variable_informat() {
+        let variable = ReadStatVariable::builder()
+            .informat(String::from("DATE9."))
+            .build()
+            .unwrap();
+        assert_eq!(variable.informat, String::from("DATE9."));
+    }
+
     #[test]
     fn test_read_stat_variable_label() {
         let variable = ReadStatVariable::builder()
@@ -153,7 +213,7 @@ mod tests {
             ReadStatLabelSet::new(
                 ReadStatType::Double,
                 SasLabelName::from_str("label"),
-                ReadStatValueLabel::new(String::new(), 0, 0.0, 0, String::new(), 0, Vec::new()),
+                ReadStatValueLabel::new(String::new(), 0, 0.0, 0, String::new(), 0, None),
                 0,
                 0,
             )
@@ -177,7 +237,100 @@ mod tests {
         );
         assert_eq!(variable.display_width, 0);
         assert_eq!(variable.display_decimals, 0);
-        assert_eq!(variable.skip, false);
+        assert!(!variable.skip);
         assert_eq!(variable.index_after_skipping, 0);
     }
+
+    #[test]
+    fn test_read_stat_variable_sas_format() {
+        let variable = ReadStatVariable::builder()
+            .format("COMMA12.2".to_string())
+            .build()
+            .unwrap();
+        let format = variable.sas_format().unwrap();
+        assert_eq!(format.name, "COMMA");
+        assert_eq!(format.decimals, Some(2));
+        assert_eq!(variable.sas_informat(), None);
+    }
+
+    #[test]
+    fn test_read_stat_variable_temporal_value() {
+        let variable = |type_: ReadStatType, format: &str| {
+            ReadStatVariable::builder()
+                .type_(type_)
+                .format(format.to_string())
+                .build()
+                .unwrap()
+        };
+        let value = |number: f64| {
+            ReadStatValue::new(
+                ReadStatType::Double,
+                None,
+                crate::types::ReadStatValueType::Double(number),
+            )
+        };
+
+        let date = variable(ReadStatType::Double, "YYMMDD10.");
+        assert_eq!(date.temporal_kind(), Some(SasTemporalKind::Date));
+        assert_eq!(
+            date.temporal_value(&value(0.0)),
+            Some(SasTemporalValue::Date(
+                chrono::NaiveDate::from_ymd_opt(1960, 1, 1).unwrap()
+            ))
+        );
+        assert_eq!(date.temporal_value(&value(f64::NAN)), None);
+        assert_eq!(
+            date.temporal_value_with(&value(1e12), SasTemporalPolicy::Error),
+            Err(ReadStatError::BadTimestampValue)
+        );
+        assert_eq!(
+            date.temporal_value_with(&value(1e12), SasTemporalPolicy::Missing),
+            Ok(None)
+        );
+
+        let time = variable(ReadStatType::Double, "TIME8.");
+        assert_eq!(
+            time.temporal_value(&value(3723.5)),
+            Some(SasTemporalValue::Time(
+                chrono::NaiveTime::from_hms_milli_opt(1, 2, 3, 500).unwrap()
+            ))
+        );
+        assert_eq!(
+            variable(ReadStatType::Double, "BEST12.").temporal_kind(),
+            None
+        );
+        assert_eq!(
+            variable(ReadStatType::String, "DATE9.").temporal_kind(),
+            None
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_read_stat_variable_serde_round_trip() {
+        use crate::types::SasMissing;
+
+        let ranges = vec![
+            ReadStatValue::missing(SasMissing::Letter('D')),
+            ReadStatValue::missing(SasMissing::Letter('R')),
+        ];
+        let variable = ReadStatVariable::builder()
+            .name("ANSWER".to_string())
+            .missing_ranges(ReadStatMissingRanges::new(ranges, 2))
+            .build()
+            .unwrap();
+        let json = serde_json::to_value(&variable).unwrap();
+        let parsed = serde_json::from_value::<ReadStatVariable>(json.clone()).unwrap();
+        let kinds = parsed
+            .missing_ranges
+            .missing_ranges
+            .iter()
+            .map(ReadStatValue::missing_kind)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![Some(SasMissing::Letter('D')), Some(SasMissing::Letter('R'))]
+        );
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+    }
 }
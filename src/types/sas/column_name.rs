@@ -12,6 +12,7 @@ pub struct SasColumnName {
 
 impl SasColumnName {
     /// Create a new SAS column name
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(name: &str) -> Result<Self, &'static str> {
         if name.len() > MAX_COLUMN_NAME_LENGTH {
             return Err("Column name is too long");
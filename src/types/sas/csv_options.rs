@@ -0,0 +1,46 @@
+use crate::types::SasTemporalPolicy;
+use derive_builder::Builder;
+
+/// Options for the CSV writer. `temporal_policy` decides what happens to dates that cannot be
+/// rendered as ISO 8601.
+#[derive(Debug, Clone, PartialEq, Default, Builder)]
+pub struct SasCsvOptions {
+    pub temporal_policy: SasTemporalPolicy,
+}
+
+impl SasCsvOptions {
+    pub fn new(temporal_policy: SasTemporalPolicy) -> Self {
+        Self { temporal_policy }
+    }
+
+    pub fn builder() -> SasCsvOptionsBuilder {
+        SasCsvOptionsBuilder::default()
+    }
+}
+
+impl SasCsvOptionsBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            temporal_policy: Some(SasTemporalPolicy::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_options() {
+        let options = SasCsvOptions::builder().build().unwrap();
+        assert_eq!(options, SasCsvOptions::default());
+        assert_eq!(options.temporal_policy, SasTemporalPolicy::Error);
+
+        let options = SasCsvOptions::builder()
+            .temporal_policy(SasTemporalPolicy::Clamp)
+            .build()
+            .unwrap();
+        assert_eq!(options, SasCsvOptions::new(SasTemporalPolicy::Clamp));
+    }
+}
@@ -0,0 +1,63 @@
+use crate::types::{SasFieldNames, SasTemporalPolicy};
+use derive_builder::Builder;
+
+/// Options for deserializing rows into records. `field_names` decides what the fields of a
+/// record are matched against, and `temporal_policy` what happens to dates that cannot be
+/// decoded.
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+pub struct SasDeserializeOptions {
+    pub field_names: SasFieldNames,
+    pub temporal_policy: SasTemporalPolicy,
+}
+
+impl SasDeserializeOptions {
+    pub fn new(field_names: SasFieldNames, temporal_policy: SasTemporalPolicy) -> Self {
+        Self {
+            field_names,
+            temporal_policy,
+        }
+    }
+
+    pub fn builder() -> SasDeserializeOptionsBuilder {
+        SasDeserializeOptionsBuilder::default()
+    }
+}
+
+impl Default for SasDeserializeOptions {
+    fn default() -> Self {
+        Self::new(SasFieldNames::Name, SasTemporalPolicy::Error)
+    }
+}
+
+impl SasDeserializeOptionsBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            field_names: Some(SasFieldNames::Name),
+            temporal_policy: Some(SasTemporalPolicy::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_options() {
+        let options = SasDeserializeOptions::builder().build().unwrap();
+        assert_eq!(options, SasDeserializeOptions::default());
+        assert_eq!(options.field_names, SasFieldNames::Name);
+        assert_eq!(options.temporal_policy, SasTemporalPolicy::Error);
+
+        let options = SasDeserializeOptions::builder()
+            .field_names(SasFieldNames::Label)
+            .temporal_policy(SasTemporalPolicy::Missing)
+            .build()
+            .unwrap();
+        assert_eq!(
+            options,
+            SasDeserializeOptions::new(SasFieldNames::Label, SasTemporalPolicy::Missing)
+        );
+    }
+}
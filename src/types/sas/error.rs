@@ -0,0 +1,221 @@
+use crate::types::ReadStatError;
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// An error raised while reading or writing a SAS file: the kind of failure, where in the file
+/// it happened, as far as it is known, and the I/O error or message behind it, if any.
+///
+/// Offsets are counted in bytes from the start of the file; pages, subheaders and rows are
+/// numbered from 0, rows across the whole file.
+#[derive(Debug)]
+pub struct SasError {
+    pub kind: ReadStatError,
+    pub offset: Option<u64>,
+    pub page: Option<u64>,
+    pub subheader: Option<usize>,
+    pub variable: Option<String>,
+    pub row: Option<u64>,
+    message: Option<Box<str>>,
+    source: Option<io::Error>,
+}
+
+impl SasError {
+    /// Create an error of the given kind, without any context.
+    pub fn new(kind: ReadStatError) -> Self {
+        Self {
+            kind,
+            offset: None,
+            page: None,
+            subheader: None,
+            variable: None,
+            row: None,
+            message: None,
+            source: None,
+        }
+    }
+
+    /// Create an error of the given kind caused by an I/O error.
+    pub fn io(kind: ReadStatError, source: io::Error) -> Self {
+        Self {
+            source: Some(source),
+            ..Self::new(kind)
+        }
+    }
+
+    pub fn io_error(&self) -> Option<&io::Error> {
+        self.source.as_ref()
+    }
+
+    /// A description of the failure beyond its kind, such as why a value could not be
+    /// deserialized.
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    pub fn with_message<S: Into<String>>(mut self, message: S) -> Self {
+        self.message
+            .get_or_insert_with(|| message.into().into_boxed_str());
+        self
+    }
+
+    /// Record the file offset the error happened at, unless a more precise one is known.
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset.get_or_insert(offset);
+        self
+    }
+
+    pub fn with_page(mut self, page: u64) -> Self {
+        self.page.get_or_insert(page);
+        self
+    }
+
+    pub fn with_subheader(mut self, subheader: usize) -> Self {
+        self.subheader.get_or_insert(subheader);
+        self
+    }
+
+    pub fn with_variable<S: Into<String>>(mut self, variable: S) -> Self {
+        self.variable.get_or_insert_with(|| variable.into());
+        self
+    }
+
+    pub fn with_row(mut self, row: u64) -> Self {
+        self.row.get_or_insert(row);
+        self
+    }
+}
+
+impl fmt::Display for SasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.kind)?;
+
+        let mut context = Vec::new();
+        if let Some(page) = self.page {
+            context.push(format!("page {}", page));
+        }
+        if let Some(subheader) = self.subheader {
+            context.push(format!("subheader {}", subheader));
+        }
+        if let Some(offset) = self.offset {
+            context.push(format!("offset {:#x}", offset));
+        }
+        if let Some(row) = self.row {
+            context.push(format!("row {}", row));
+        }
+        if let Some(variable) = &self.variable {
+            context.push(format!("variable {}", variable));
+        }
+        if !context.is_empty() {
+            write!(f, " at {}", context.join(", "))?;
+        }
+        if let Some(message) = &self.message {
+            write!(f, ": {}", message)?;
+        }
+
+        match &self.source {
+            Some(source) => write!(f, ": {}", source),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Error for SasError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+impl From<ReadStatError> for SasError {
+    fn from(kind: ReadStatError) -> Self {
+        Self::new(kind)
+    }
+}
+
+/// Errors raised by `Deserialize` implementations, when a row does not fit the type it is
+/// deserialized into.
+#[cfg(feature = "serde")]
+impl serde::de::Error for SasError {
+    fn custom<T: fmt::Display>(message: T) -> Self {
+        Self::new(ReadStatError::ValueTypeMismatch).with_message(message.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        Self::new(ReadStatError::UnknownColumn).with_message(format!("missing field `{}`", field))
+    }
+}
+
+/// I/O errors become `ReadStatError::Open` when the file cannot be found or accessed, and
+/// `ReadStatError::Read` otherwise.
+impl From<io::Error> for SasError {
+    fn from(source: io::Error) -> Self {
+        let kind = match source.kind() {
+            io::ErrorKind::NotFound | io::ErrorKind::PermissionDenied => ReadStatError::Open,
+            _ => ReadStatError::Read,
+        };
+        Self::io(kind, source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_with_context() {
+        let err = SasError::new(ReadStatError::Parse)
+            .with_subheader(4)
+            .with_page(2)
+            .with_offset(0x2410)
+            .with_variable("AGE");
+        assert_eq!(
+            err.to_string(),
+            "Error parsing file at page 2, subheader 4, offset 0x2410, variable AGE"
+        );
+        assert_eq!(
+            SasError::from(ReadStatError::Seek).to_string(),
+            "Error seeking file"
+        );
+    }
+
+    #[test]
+    fn test_context_keeps_innermost_value() {
+        let err = SasError::new(ReadStatError::Read)
+            .with_offset(10)
+            .with_row(3)
+            .with_offset(20)
+            .with_row(7);
+        assert_eq!(err.offset, Some(10));
+        assert_eq!(err.row, Some(3));
+    }
+
+    #[test]
+    fn test_display_with_message() {
+        let err = SasError::new(ReadStatError::ValueTypeMismatch)
+            .with_message("expected a date")
+            .with_row(3)
+            .with_message("ignored");
+        assert_eq!(err.message(), Some("expected a date"));
+        assert_eq!(
+            err.to_string(),
+            "Value type mismatch at row 3: expected a date"
+        );
+    }
+
+    #[test]
+    fn test_from_io_error() {
+        let err = SasError::from(io::Error::new(io::ErrorKind::NotFound, "no such file"));
+        assert_eq!(err.kind, ReadStatError::Open);
+        assert_eq!(err.to_string(), "Error opening file: no such file");
+        assert!(err.source().is_some());
+
+        let err = SasError::from(io::Error::from(io::ErrorKind::UnexpectedEof));
+        assert_eq!(err.kind, ReadStatError::Read);
+        assert_eq!(
+            err.io_error().map(io::Error::kind),
+            Some(io::ErrorKind::UnexpectedEof)
+        );
+    }
+}
@@ -0,0 +1,328 @@
+use crate::types::{ReadStatError, SasFormatCategory, SasTemporalKind};
+use derive_builder::Builder;
+use std::fmt;
+
+// Numeric formats that ship with SAS, other than dates, datetimes and times
+const SAS_NUMERIC_FORMATS: [&str; 34] = [
+    "BEST", "BESTD", "BINARY", "COMMA", "COMMAX", "D", "DOLLAR", "DOLLARX", "E", "EURO", "EUROX",
+    "F", "FLOAT", "FRACT", "HEX", "IB", "NEGPAREN", "NLMNY", "NLNUM", "NUMX", "OCTAL", "PD",
+    "PERCENT", "PERCENTN", "PIB", "PK", "PVALUE", "RB", "ROMAN", "SSN", "WORDF", "WORDS", "Z",
+    "ZD",
+];
+
+// Character formats that ship with SAS, without their leading `$`
+const SAS_CHARACTER_FORMATS: [&str; 14] = [
+    "", "ASCII", "BINARY", "CHAR", "EBCDIC", "F", "HEX", "OCTAL", "QUOTE", "REVERJ", "REVERS",
+    "UPCASE", "VARYING", "W",
+];
+
+// Formats displaying, and informats reading, a number of days since 1960-01-01
+const SAS_DATE_FORMATS: [&str; 37] = [
+    "ANYDTDTE", "DATE", "DAY", "DDMMYY", "DDMMYYB", "DDMMYYC", "DDMMYYD", "DDMMYYN", "DDMMYYP",
+    "DDMMYYS", "DOWNAME", "E8601DA", "B8601DA", "JULDAY", "JULIAN", "MMDDYY", "MMDDYYB", "MMDDYYC",
+    "MMDDYYD", "MMDDYYN", "MMDDYYP", "MMDDYYS", "MMYY", "MONNAME", "MONTH", "MONYY", "QTR",
+    "WEEKDATE", "WEEKDATX", "WEEKDAY", "WORDDATE", "WORDDATX", "YEAR", "YYMM", "YYMMDD", "YYMON",
+    "YYQ",
+];
+
+// Formats displaying, and informats reading, a number of seconds since 1960-01-01T00:00:00
+const SAS_DATETIME_FORMATS: [&str; 11] = [
+    "ANYDTDTM", "DATETIME", "DATEAMPM", "DTDATE", "DTMONYY", "DTWKDATX", "DTYEAR", "E8601DT",
+    "B8601DT", "E8601DZ", "MDYAMPM",
+];
+
+// Formats displaying, and informats reading, a number of seconds since midnight
+const SAS_TIME_FORMATS: [&str; 9] = [
+    "ANYDTTME", "TIME", "TIMEAMPM", "TOD", "HHMM", "HOUR", "MMSS", "E8601TM", "B8601TM",
+];
+
+/// A format specification such as `DATE9.`, `COMMA12.2` or `$CHAR20.`, split into its name,
+/// width and number of decimals.
+///
+/// Names are upper case and keep the `$` of character formats, eg `$CHAR`. A bare width such as
+/// `8.2` is the `w.d` format, whose name is empty. Formats that do not ship with SAS are
+/// user-defined, as created by `PROC FORMAT`.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SasFormat {
+    pub name: String,
+    pub width: Option<u16>,
+    pub decimals: Option<u16>,
+    pub category: SasFormatCategory,
+}
+
+impl SasFormat {
+    pub fn new(
+        name: String,
+        width: Option<u16>,
+        decimals: Option<u16>,
+        category: SasFormatCategory,
+    ) -> Self {
+        Self {
+            name,
+            width,
+            decimals,
+            category,
+        }
+    }
+
+    pub fn builder() -> SasFormatBuilder {
+        SasFormatBuilder::default()
+    }
+
+    /// Parse a format specification as SAS writes it: an optional `$`, a name that does not end
+    /// in a digit, an optional width and an optional period followed by the number of decimals.
+    /// Names are compared case-insensitively.
+    ///
+    /// # Returns
+    /// `ReadStatError::BadFormatString` if `format` is empty or not a format specification.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(format: &str) -> Result<Self, ReadStatError> {
+        let format = format.trim();
+        let (spec, decimals) = match format.split_once('.') {
+            Some((spec, decimals)) => (spec, Some(decimals)),
+            None => (format, None),
+        };
+        let is_character = spec.starts_with('$');
+        let spec = spec.strip_prefix('$').unwrap_or(spec);
+        let name = spec.trim_end_matches(|c: char| c.is_ascii_digit());
+        let width = &spec[name.len()..];
+
+        if format.is_empty()
+            || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            || name.starts_with(|c: char| c.is_ascii_digit())
+        {
+            return Err(ReadStatError::BadFormatString);
+        }
+        let width = parse_number(width)?;
+        let decimals = match decimals {
+            Some(decimals) => parse_number(decimals)?,
+            None => None,
+        };
+
+        let name = name.to_ascii_uppercase();
+        let category = if is_character {
+            if SAS_CHARACTER_FORMATS.contains(&name.as_str()) {
+                SasFormatCategory::Character
+            } else {
+                SasFormatCategory::UserDefined
+            }
+        } else {
+            match temporal_category(&name) {
+                Some(category) => category,
+                None if name.is_empty() || SAS_NUMERIC_FORMATS.contains(&name.as_str()) => {
+                    SasFormatCategory::Numeric
+                }
+                None => SasFormatCategory::UserDefined,
+            }
+        };
+        Ok(Self {
+            name: if is_character {
+                format!("${}", name)
+            } else {
+                name
+            },
+            width,
+            decimals,
+            category,
+        })
+    }
+
+    /// Return true for formats applied to character values, whose names start with `$`.
+    pub fn is_character(&self) -> bool {
+        self.name.starts_with('$')
+    }
+
+    /// Return true for formats created by `PROC FORMAT` rather than shipped with SAS.
+    pub fn is_user_defined(&self) -> bool {
+        self.category == SasFormatCategory::UserDefined
+    }
+
+    /// Return what a numeric value displayed with this format holds, or None if it is not a
+    /// date, time or datetime.
+    pub fn temporal_kind(&self) -> Option<SasTemporalKind> {
+        match self.category {
+            SasFormatCategory::Date => Some(SasTemporalKind::Date),
+            SasFormatCategory::DateTime => Some(SasTemporalKind::DateTime),
+            SasFormatCategory::Time => Some(SasTemporalKind::Time),
+            _ => None,
+        }
+    }
+}
+
+/// Return the category of a format name shipped with SAS that displays a date, datetime or time.
+fn temporal_category(name: &str) -> Option<SasFormatCategory> {
+    if SAS_DATE_FORMATS.contains(&name) || name.starts_with("YYMMDD") {
+        Some(SasFormatCategory::Date)
+    } else if SAS_DATETIME_FORMATS.contains(&name) {
+        Some(SasFormatCategory::DateTime)
+    } else if SAS_TIME_FORMATS.contains(&name) {
+        Some(SasFormatCategory::Time)
+    } else {
+        None
+    }
+}
+
+/// Parse the width or decimals of a format, which may be left out.
+fn parse_number(digits: &str) -> Result<Option<u16>, ReadStatError> {
+    if digits.is_empty() {
+        return Ok(None);
+    }
+    digits
+        .parse()
+        .map(Some)
+        .map_err(|_| ReadStatError::BadFormatString)
+}
+
+/// Formats are displayed the way SAS writes them, eg `DATE9.` or `COMMA12.2`. A format without
+/// a width or decimals is displayed as its bare name.
+impl fmt::Display for SasFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if self.width.is_none() && self.decimals.is_none() {
+            return Ok(());
+        }
+        if let Some(width) = self.width {
+            write!(f, "{}", width)?;
+        }
+        write!(f, ".")?;
+        if let Some(decimals) = self.decimals {
+            write!(f, "{}", decimals)?;
+        }
+        Ok(())
+    }
+}
+
+impl SasFormatBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            name: Some("BEST".to_string()),
+            width: Some(Some(12)),
+            decimals: Some(None),
+            category: Some(SasFormatCategory::Numeric),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(
+        name: &str,
+        width: Option<u16>,
+        decimals: Option<u16>,
+        category: SasFormatCategory,
+    ) -> SasFormat {
+        SasFormat::new(name.to_string(), width, decimals, category)
+    }
+
+    #[test]
+    fn test_parse_builtin_formats() {
+        let cases = [
+            (
+                "DATE9.",
+                format("DATE", Some(9), None, SasFormatCategory::Date),
+            ),
+            (
+                "DATETIME20.",
+                format("DATETIME", Some(20), None, SasFormatCategory::DateTime),
+            ),
+            (
+                "time8.",
+                format("TIME", Some(8), None, SasFormatCategory::Time),
+            ),
+            (
+                "E8601DA10.",
+                format("E8601DA", Some(10), None, SasFormatCategory::Date),
+            ),
+            (
+                "BEST12.",
+                format("BEST", Some(12), None, SasFormatCategory::Numeric),
+            ),
+            (
+                "COMMA12.2",
+                format("COMMA", Some(12), Some(2), SasFormatCategory::Numeric),
+            ),
+            (
+                "DOLLAR10.2",
+                format("DOLLAR", Some(10), Some(2), SasFormatCategory::Numeric),
+            ),
+            (
+                "Z5.",
+                format("Z", Some(5), None, SasFormatCategory::Numeric),
+            ),
+            (
+                "PERCENT8.1",
+                format("PERCENT", Some(8), Some(1), SasFormatCategory::Numeric),
+            ),
+            (
+                "8.2",
+                format("", Some(8), Some(2), SasFormatCategory::Numeric),
+            ),
+            (
+                "$CHAR20.",
+                format("$CHAR", Some(20), None, SasFormatCategory::Character),
+            ),
+            ("$", format("$", None, None, SasFormatCategory::Character)),
+            (
+                "$10.",
+                format("$", Some(10), None, SasFormatCategory::Character),
+            ),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(SasFormat::from_str(text), Ok(expected), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_parse_user_defined_formats() {
+        let format = SasFormat::from_str("yesno.").unwrap();
+        assert_eq!(format.name, "YESNO");
+        assert!(format.is_user_defined());
+        assert!(!format.is_character());
+
+        let format = SasFormat::from_str("$REGION_A3.").unwrap();
+        assert_eq!(format.name, "$REGION_A");
+        assert_eq!(format.width, Some(3));
+        assert!(format.is_user_defined());
+        assert!(format.is_character());
+    }
+
+    #[test]
+    fn test_parse_bad_formats() {
+        for text in ["", "DATE9.x", "DATE-9.", "1X.", "BEST99999."] {
+            assert_eq!(
+                SasFormat::from_str(text),
+                Err(ReadStatError::BadFormatString),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn test_display_round_trips() {
+        for text in ["DATE9.", "COMMA12.2", "$CHAR20.", "BEST", "8.2", "$", "F.2"] {
+            assert_eq!(SasFormat::from_str(text).unwrap().to_string(), text);
+        }
+        assert_eq!(SasFormat::builder().build().unwrap().temporal_kind(), None);
+        assert_eq!(
+            SasFormat::from_str("MMDDYY10.").unwrap().temporal_kind(),
+            Some(SasTemporalKind::Date)
+        );
+    }
+
+    #[test]
+    fn test_temporal_kind() {
+        let kind = |text: &str| SasFormat::from_str(text).unwrap().temporal_kind();
+        assert_eq!(kind("DATE9."), Some(SasTemporalKind::Date));
+        assert_eq!(kind("yymmdd10."), Some(SasTemporalKind::Date));
+        assert_eq!(kind("E8601DA10."), Some(SasTemporalKind::Date));
+        assert_eq!(kind("DATETIME20."), Some(SasTemporalKind::DateTime));
+        assert_eq!(kind("TIME8."), Some(SasTemporalKind::Time));
+        assert_eq!(kind("BEST12."), None);
+        assert_eq!(kind("$DATE9."), None);
+    }
+}
@@ -18,6 +18,7 @@ use derive_builder::Builder;
 // char          file_info[8];
 // } sas_header_start_t;
 #[derive(Debug, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SasHeaderStart {
     pub magic: [u8; 32],
     pub a2: u8,
@@ -37,6 +38,7 @@ pub struct SasHeaderStart {
 }
 
 impl SasHeaderStart {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         magic: [u8; 32],
         a2: u8,
@@ -79,6 +81,7 @@ impl SasHeaderStart {
 }
 
 impl SasHeaderStartBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             magic: Some([0; 32]),
@@ -109,15 +112,42 @@ impl SasHeaderStartBuilder {
 // char          extra[48];
 // } sas_header_end_t;
 #[derive(Debug, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SasHeaderEnd {
     pub release: [char; 8],
     pub host: [char; 16],
     pub version: [char; 16],
     pub os_vendor: [char; 16],
     pub os_name: [char; 16],
+    #[cfg_attr(feature = "serde", serde(with = "long_array"))]
     pub extra: [char; 48],
 }
 
+/// Serde only implements its traits for arrays of up to 32 elements, so longer arrays are
+/// (de)serialized as sequences here.
+#[cfg(feature = "serde")]
+mod long_array {
+    use serde::de::{Deserialize, Deserializer, Error};
+    use serde::ser::Serializer;
+
+    pub fn serialize<S: Serializer, T: serde::Serialize, const N: usize>(
+        array: &[T; N],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(array)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: Deserialize<'de>, const N: usize>(
+        deserializer: D,
+    ) -> Result<[T; N], D::Error> {
+        let items = Vec::<T>::deserialize(deserializer)?;
+        let len = items.len();
+        items
+            .try_into()
+            .map_err(|_| D::Error::invalid_length(len, &N.to_string().as_str()))
+    }
+}
+
 impl SasHeaderEnd {
     pub fn new(
         release: [char; 8],
@@ -143,6 +173,7 @@ impl SasHeaderEnd {
 }
 
 impl SasHeaderEndBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             release: Some([' '; 8]),
@@ -156,6 +187,7 @@ impl SasHeaderEndBuilder {
 }
 
 #[derive(Debug, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SasHeader {
     pub start: SasHeaderStart,
     pub end: SasHeaderEnd,
@@ -172,6 +204,7 @@ impl SasHeader {
 }
 
 impl SasHeaderBuilder {
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             start: Some(SasHeaderStart::builder().build().unwrap()),
@@ -227,4 +260,16 @@ mod tests {
         );
         assert_eq!(header.start.magic, [0; 32]);
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_sas_header_serde_round_trip() {
+        let mut header = SasHeader::builder().build().unwrap();
+        header.end.extra[47] = 'x';
+        let json = serde_json::to_string(&header).unwrap();
+        assert_eq!(serde_json::from_str::<SasHeader>(&json).unwrap(), header);
+
+        let short = json.replace(",\"x\"]", "]");
+        assert!(serde_json::from_str::<SasHeader>(&short).is_err());
+    }
 }
@@ -0,0 +1,165 @@
+// typedef struct sas_header_info_s {
+// int      little_endian;
+// int      u64;
+// int      vendor;
+// int      major_version;
+// int      minor_version;
+// int      revision;
+// int      pad1;
+// int64_t  page_size;
+// int64_t  page_header_size;
+// int64_t  subheader_pointer_size;
+// int64_t  page_count;
+// int64_t  header_size;
+// time_t   creation_time;
+// time_t   modification_time;
+// char     table_name[32];
+// char     file_label[256];
+// char    *encoding;
+// } sas_header_info_t;
+
+use crate::types::{ReadStatVendor, SasEndian};
+use derive_builder::Builder;
+
+/// The decoded contents of the header at the start of a sas7bdat file. Unlike `SasHeader`,
+/// which mirrors the raw on-disk layout, this holds the values the rest of the parser needs:
+/// byte order, word size, page geometry and the creation and modification timestamps.
+#[derive(Debug, Clone, PartialEq, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SasHeaderInfo {
+    pub endianness: SasEndian,
+    pub is_64bit: bool,
+    pub vendor: ReadStatVendor,
+    pub major_version: u8,
+    pub minor_version: u16,
+    pub revision: u8,
+    pub pad1: u64,
+    pub page_size: u64,
+    pub page_header_size: u64,
+    pub subheader_pointer_size: u64,
+    pub page_count: u64,
+    pub header_size: u64,
+
+    /// Seconds since the Unix epoch.
+    pub creation_time: i64,
+
+    /// Seconds since the Unix epoch.
+    pub modification_time: i64,
+    pub table_name: String,
+    pub encoding_code: u8,
+    pub encoding: String,
+    pub release: String,
+    pub host: String,
+    pub os_name: String,
+}
+
+impl SasHeaderInfo {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        endianness: SasEndian,
+        is_64bit: bool,
+        vendor: ReadStatVendor,
+        major_version: u8,
+        minor_version: u16,
+        revision: u8,
+        pad1: u64,
+        page_size: u64,
+        page_header_size: u64,
+        subheader_pointer_size: u64,
+        page_count: u64,
+        header_size: u64,
+        creation_time: i64,
+        modification_time: i64,
+        table_name: String,
+        encoding_code: u8,
+        encoding: String,
+        release: String,
+        host: String,
+        os_name: String,
+    ) -> Self {
+        Self {
+            endianness,
+            is_64bit,
+            vendor,
+            major_version,
+            minor_version,
+            revision,
+            pad1,
+            page_size,
+            page_header_size,
+            subheader_pointer_size,
+            page_count,
+            header_size,
+            creation_time,
+            modification_time,
+            table_name,
+            encoding_code,
+            encoding,
+            release,
+            host,
+            os_name,
+        }
+    }
+
+    pub fn builder() -> SasHeaderInfoBuilder {
+        SasHeaderInfoBuilder::default()
+    }
+
+    /// Return true if the file was written in little-endian byte order.
+    pub fn is_little_endian(&self) -> bool {
+        self.endianness == SasEndian::Little
+    }
+}
+
+impl SasHeaderInfoBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            endianness: Some(SasEndian::Little),
+            is_64bit: Some(false),
+            vendor: Some(ReadStatVendor::Sas),
+            major_version: Some(9),
+            minor_version: Some(0),
+            revision: Some(0),
+            pad1: Some(0),
+            page_size: Some(4096),
+            page_header_size: Some(24),
+            subheader_pointer_size: Some(12),
+            page_count: Some(0),
+            header_size: Some(1024),
+            creation_time: Some(0),
+            modification_time: Some(0),
+            table_name: Some(String::new()),
+            encoding_code: Some(0),
+            encoding: Some(String::from("WINDOWS-1252")),
+            release: Some(String::new()),
+            host: Some(String::new()),
+            os_name: Some(String::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sas_header_info_builder_default() {
+        let header = SasHeaderInfo::builder().build().unwrap();
+        assert_eq!(header.endianness, SasEndian::Little);
+        assert!(!header.is_64bit);
+        assert_eq!(header.page_size, 4096);
+        assert_eq!(header.page_header_size, 24);
+        assert_eq!(header.subheader_pointer_size, 12);
+        assert_eq!(header.encoding, "WINDOWS-1252");
+    }
+
+    #[test]
+    fn test_sas_header_info_is_little_endian() {
+        let header = SasHeaderInfo::builder()
+            .endianness(SasEndian::Big)
+            .build()
+            .unwrap();
+        assert!(!header.is_little_endian());
+    }
+}
@@ -0,0 +1,90 @@
+use crate::types::{SasMissingStyle, SasTemporalPolicy};
+use derive_builder::Builder;
+
+/// Options for the JSON Lines writer. Dates, times and datetimes are rendered as ISO 8601
+/// strings when `iso_dates` is set, and as raw SAS numbers otherwise. A leading metadata record
+/// is written when `include_metadata` is set. `temporal_policy` decides what happens to dates
+/// that cannot be rendered as ISO 8601.
+#[derive(Debug, Clone, PartialEq, Builder)]
+pub struct SasJsonLinesOptions {
+    pub system_missing: SasMissingStyle,
+    pub special_missing: SasMissingStyle,
+    pub iso_dates: bool,
+    pub include_metadata: bool,
+    pub temporal_policy: SasTemporalPolicy,
+}
+
+impl SasJsonLinesOptions {
+    pub fn new(
+        system_missing: SasMissingStyle,
+        special_missing: SasMissingStyle,
+        iso_dates: bool,
+        include_metadata: bool,
+        temporal_policy: SasTemporalPolicy,
+    ) -> Self {
+        Self {
+            system_missing,
+            special_missing,
+            iso_dates,
+            include_metadata,
+            temporal_policy,
+        }
+    }
+
+    pub fn builder() -> SasJsonLinesOptionsBuilder {
+        SasJsonLinesOptionsBuilder::default()
+    }
+}
+
+impl Default for SasJsonLinesOptions {
+    fn default() -> Self {
+        Self::new(
+            SasMissingStyle::Null,
+            SasMissingStyle::Null,
+            true,
+            false,
+            SasTemporalPolicy::Error,
+        )
+    }
+}
+
+impl SasJsonLinesOptionsBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            system_missing: Some(SasMissingStyle::Null),
+            special_missing: Some(SasMissingStyle::Null),
+            iso_dates: Some(true),
+            include_metadata: Some(false),
+            temporal_policy: Some(SasTemporalPolicy::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_json_lines_options() {
+        let options = SasJsonLinesOptions::builder().build().unwrap();
+        assert_eq!(options, SasJsonLinesOptions::default());
+        assert_eq!(options.system_missing, SasMissingStyle::Null);
+        assert!(options.iso_dates);
+        assert!(!options.include_metadata);
+        assert_eq!(options.temporal_policy, SasTemporalPolicy::Error);
+    }
+
+    #[test]
+    fn test_json_lines_options_builder() {
+        let options = SasJsonLinesOptions::builder()
+            .special_missing(SasMissingStyle::SasNotation)
+            .include_metadata(true)
+            .temporal_policy(SasTemporalPolicy::Raw)
+            .build()
+            .unwrap();
+        assert_eq!(options.special_missing, SasMissingStyle::SasNotation);
+        assert!(options.include_metadata);
+        assert_eq!(options.temporal_policy, SasTemporalPolicy::Raw);
+    }
+}
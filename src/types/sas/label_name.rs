@@ -1,6 +1,7 @@
 use derive_builder::Builder;
 
 #[derive(Debug, Clone, Builder, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SasLabelName {
     pub name: String,
 }
@@ -10,6 +11,7 @@ impl SasLabelName {
         Self { name }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(name: &str) -> Self {
         Self {
             name: name.to_string(),
@@ -1,17 +1,39 @@
 pub mod charset;
 pub mod column_name;
+pub mod csv_options;
+pub mod deserialize_options;
+pub mod error;
+pub mod format;
 pub mod header;
+pub mod header_info;
+pub mod json_lines_options;
 pub mod label_name;
+pub mod polars_options;
+pub mod read_options;
+pub mod reader_limits;
 pub mod sas_enums;
+pub mod subheader_pointer;
 pub mod subheader_signature;
 pub mod table_name;
+pub mod text_ref;
 pub mod time;
 
 pub use charset::build_charset_lookup;
 pub use column_name::SasColumnName;
+pub use csv_options::SasCsvOptions;
+pub use deserialize_options::SasDeserializeOptions;
+pub use error::SasError;
+pub use format::SasFormat;
 pub use header::SasHeader;
+pub use header_info::SasHeaderInfo;
+pub use json_lines_options::SasJsonLinesOptions;
 pub use label_name::SasLabelName;
+pub use polars_options::SasPolarsOptions;
+pub use read_options::SasReadOptions;
+pub use reader_limits::ReaderLimits;
 pub use sas_enums::*;
+pub use subheader_pointer::SasSubheaderPointer;
 pub use subheader_signature::SasSubheaderSignature;
 pub use table_name::SasTableName;
+pub use text_ref::SasTextRef;
 pub use time::*;
@@ -0,0 +1,47 @@
+use crate::types::SasTemporalPolicy;
+use derive_builder::Builder;
+
+/// Options for reading into Polars and writing Parquet. `temporal_policy` decides what happens
+/// to dates that cannot be decoded; under `SasTemporalPolicy::Raw`, date, datetime and time
+/// variables are read as `Float64` columns holding the stored numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Builder)]
+pub struct SasPolarsOptions {
+    pub temporal_policy: SasTemporalPolicy,
+}
+
+impl SasPolarsOptions {
+    pub fn new(temporal_policy: SasTemporalPolicy) -> Self {
+        Self { temporal_policy }
+    }
+
+    pub fn builder() -> SasPolarsOptionsBuilder {
+        SasPolarsOptionsBuilder::default()
+    }
+}
+
+impl SasPolarsOptionsBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            temporal_policy: Some(SasTemporalPolicy::Error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_polars_options() {
+        let options = SasPolarsOptions::builder().build().unwrap();
+        assert_eq!(options, SasPolarsOptions::default());
+        assert_eq!(options.temporal_policy, SasTemporalPolicy::Error);
+
+        let options = SasPolarsOptions::builder()
+            .temporal_policy(SasTemporalPolicy::Missing)
+            .build()
+            .unwrap();
+        assert_eq!(options, SasPolarsOptions::new(SasTemporalPolicy::Missing));
+    }
+}
@@ -0,0 +1,67 @@
+use derive_builder::Builder;
+
+/// Options controlling which part of a sas7bdat file is decoded. `columns` selects variables by
+/// name, in the order they should appear in each row; `None` selects every variable. Rows before
+/// `row_offset` are skipped and at most `row_limit` rows are returned.
+#[derive(Debug, Clone, PartialEq, Builder)]
+pub struct SasReadOptions {
+    pub columns: Option<Vec<String>>,
+    pub row_offset: u64,
+    pub row_limit: Option<u64>,
+}
+
+impl SasReadOptions {
+    pub fn new(columns: Option<Vec<String>>, row_offset: u64, row_limit: Option<u64>) -> Self {
+        Self {
+            columns,
+            row_offset,
+            row_limit,
+        }
+    }
+
+    pub fn builder() -> SasReadOptionsBuilder {
+        SasReadOptionsBuilder::default()
+    }
+}
+
+impl Default for SasReadOptions {
+    fn default() -> Self {
+        Self::new(None, 0, None)
+    }
+}
+
+impl SasReadOptionsBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            columns: Some(None),
+            row_offset: Some(0),
+            row_limit: Some(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sas_read_options_default() {
+        let options = SasReadOptions::default();
+        assert_eq!(options.columns, None);
+        assert_eq!(options.row_offset, 0);
+        assert_eq!(options.row_limit, None);
+    }
+
+    #[test]
+    fn test_sas_read_options_builder() {
+        let options = SasReadOptions::builder()
+            .columns(Some(vec!["a".to_string()]))
+            .row_limit(Some(10))
+            .build()
+            .unwrap();
+        assert_eq!(options.columns, Some(vec!["a".to_string()]));
+        assert_eq!(options.row_offset, 0);
+        assert_eq!(options.row_limit, Some(10));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::types::ReadStatError;
+use derive_builder::Builder;
+
+/// Upper bounds on what a file may declare before any memory is spent on it, for reading files
+/// from untrusted sources. `None` leaves a quantity unbounded, which is the default for all of
+/// them.
+///
+/// - `max_columns`: variables in a dataset.
+/// - `max_row_length`: bytes in a row, as declared by the row size subheader.
+/// - `max_column_text_size`: bytes of column text, summed over every column text subheader.
+///   Names, labels and formats are all read from it.
+/// - `max_pages`: pages in a dataset.
+/// - `max_decompressed_row_size`: bytes a compressed row expands to.
+/// - `max_catalog_entries`: formats in a format catalog.
+#[derive(Debug, Clone, PartialEq, Builder)]
+pub struct ReaderLimits {
+    pub max_columns: Option<u64>,
+    pub max_row_length: Option<u64>,
+    pub max_column_text_size: Option<usize>,
+    pub max_pages: Option<u64>,
+    pub max_decompressed_row_size: Option<u64>,
+    pub max_catalog_entries: Option<u64>,
+}
+
+impl ReaderLimits {
+    pub fn new(
+        max_columns: Option<u64>,
+        max_row_length: Option<u64>,
+        max_column_text_size: Option<usize>,
+        max_pages: Option<u64>,
+        max_decompressed_row_size: Option<u64>,
+        max_catalog_entries: Option<u64>,
+    ) -> Self {
+        Self {
+            max_columns,
+            max_row_length,
+            max_column_text_size,
+            max_pages,
+            max_decompressed_row_size,
+            max_catalog_entries,
+        }
+    }
+
+    pub fn builder() -> ReaderLimitsBuilder {
+        ReaderLimitsBuilder::default()
+    }
+
+    /// # Returns
+    /// `ReadStatError::TooManyColumns` if a dataset has more than `max_columns` variables.
+    pub fn check_columns(&self, count: u64) -> Result<(), ReadStatError> {
+        check(count, self.max_columns, ReadStatError::TooManyColumns)
+    }
+
+    /// # Returns
+    /// `ReadStatError::RowIsTooWideForPage` if a row is longer than `max_row_length`, or if it
+    /// is compressed and longer than `max_decompressed_row_size`.
+    pub fn check_row_length(&self, length: u64, compressed: bool) -> Result<(), ReadStatError> {
+        check(
+            length,
+            self.max_row_length,
+            ReadStatError::RowIsTooWideForPage,
+        )?;
+        if compressed {
+            check(
+                length,
+                self.max_decompressed_row_size,
+                ReadStatError::RowIsTooWideForPage,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// # Returns
+    /// `ReadStatError::ColumnTextTooLarge` if there are more than `max_column_text_size` bytes
+    /// of column text.
+    pub fn check_column_text_size(&self, size: usize) -> Result<(), ReadStatError> {
+        check(
+            size as u64,
+            self.max_column_text_size.map(|max| max as u64),
+            ReadStatError::ColumnTextTooLarge,
+        )
+    }
+
+    /// # Returns
+    /// `ReadStatError::TooManyPages` if a dataset has more than `max_pages` pages.
+    pub fn check_pages(&self, count: u64) -> Result<(), ReadStatError> {
+        check(count, self.max_pages, ReadStatError::TooManyPages)
+    }
+
+    /// # Returns
+    /// `ReadStatError::TooManyCatalogEntries` if a catalog has more than `max_catalog_entries`
+    /// formats.
+    pub fn check_catalog_entries(&self, count: u64) -> Result<(), ReadStatError> {
+        check(
+            count,
+            self.max_catalog_entries,
+            ReadStatError::TooManyCatalogEntries,
+        )
+    }
+}
+
+fn check(value: u64, max: Option<u64>, err: ReadStatError) -> Result<(), ReadStatError> {
+    match max {
+        Some(max) if value > max => Err(err),
+        _ => Ok(()),
+    }
+}
+
+impl Default for ReaderLimits {
+    fn default() -> Self {
+        Self::new(None, None, None, None, None, None)
+    }
+}
+
+impl ReaderLimitsBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            max_columns: Some(None),
+            max_row_length: Some(None),
+            max_column_text_size: Some(None),
+            max_pages: Some(None),
+            max_decompressed_row_size: Some(None),
+            max_catalog_entries: Some(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_limits_are_unbounded() {
+        let limits = ReaderLimits::builder().build().unwrap();
+        assert_eq!(limits, ReaderLimits::default());
+        assert_eq!(limits.check_columns(u64::MAX), Ok(()));
+        assert_eq!(limits.check_row_length(u64::MAX, true), Ok(()));
+        assert_eq!(limits.check_column_text_size(usize::MAX), Ok(()));
+        assert_eq!(limits.check_pages(u64::MAX), Ok(()));
+    }
+
+    #[test]
+    fn test_limits_are_inclusive() {
+        let limits = ReaderLimits::builder()
+            .max_columns(Some(10))
+            .max_row_length(Some(4096))
+            .max_decompressed_row_size(Some(1024))
+            .max_pages(Some(100))
+            .build()
+            .unwrap();
+        assert_eq!(limits.check_columns(10), Ok(()));
+        assert_eq!(limits.check_columns(11), Err(ReadStatError::TooManyColumns));
+        assert_eq!(limits.check_row_length(4096, false), Ok(()));
+        assert_eq!(
+            limits.check_row_length(4097, false),
+            Err(ReadStatError::RowIsTooWideForPage)
+        );
+        assert_eq!(
+            limits.check_row_length(2048, true),
+            Err(ReadStatError::RowIsTooWideForPage)
+        );
+        assert_eq!(limits.check_pages(101), Err(ReadStatError::TooManyPages));
+        assert_eq!(limits.check_column_text_size(1 << 30), Ok(()));
+    }
+}
@@ -1,17 +1,13 @@
 // #define SAS_ENDIAN_BIG       0x00
 // #define SAS_ENDIAN_LITTLE    0x01
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasEndian {
+    #[default]
     Big = 0x00,
     Little = 0x01,
 }
 
-impl Default for SasEndian {
-    fn default() -> Self {
-        SasEndian::Big
-    }
-}
-
 impl SasEndian {
     pub fn to_hex(&self) -> u8 {
         match self {
@@ -22,6 +18,7 @@ impl SasEndian {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasFileFormat {
     Unix = 1,
     Windows = 2,
@@ -30,6 +27,7 @@ pub enum SasFileFormat {
 // #define SAS_ALIGNMENT_OFFSET_0  0x22
 // #define SAS_ALIGNMENT_OFFSET_4  0x33
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasAlignmentOffset {
     Offset0 = 0x22,
     Offset4 = 0x33,
@@ -38,6 +36,7 @@ pub enum SasAlignmentOffset {
 // #define SAS_COLUMN_TYPE_NUM  0x01
 // #define SAS_COLUMN_TYPE_CHR  0x02
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasColumnType {
     Numeric = 0x01,
     Character = 0x02,
@@ -52,6 +51,7 @@ pub enum SasColumnType {
 // #define SAS_PAGE_TYPE_META2  0x4000
 // #define SAS_PAGE_TYPE_COMP   0x9000
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasPageType {
     Meta = 0x0000,
     Data = 0x0100,
@@ -64,6 +64,7 @@ pub enum SasPageType {
 // #define SAS_SUBHEADER_POINTER_SIZE_32BIT    12
 // #define SAS_SUBHEADER_POINTER_SIZE_64BIT    24
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasSubheaderPointerSize {
     Bit32 = 12,
     Bit64 = 24,
@@ -72,6 +73,7 @@ pub enum SasSubheaderPointerSize {
 // #define SAS_PAGE_HEADER_SIZE_32BIT  24
 // #define SAS_PAGE_HEADER_SIZE_64BIT  40
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasPageHeaderSize {
     Bit32 = 24,
     Bit64 = 40,
@@ -84,6 +86,7 @@ pub enum SasPageHeaderSize {
 // #define SAS_COMPRESSION_SIGNATURE_RLE  "SASYZCRL"
 // #define SAS_COMPRESSION_SIGNATURE_RDC  "SASYZCR2"
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasCompression {
     None = 0x00,
     Trunc = 0x01,
@@ -91,16 +94,17 @@ pub enum SasCompression {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasCompressionSignature {
     Rle,
     Rdc,
 }
 
-impl SasCompressionSignature {
-    pub fn to_string(&self) -> String {
+impl std::fmt::Display for SasCompressionSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SasCompressionSignature::Rle => "SASYZCRL".to_string(),
-            SasCompressionSignature::Rdc => "SASYZCR2".to_string(),
+            SasCompressionSignature::Rle => write!(f, "SASYZCRL"),
+            SasCompressionSignature::Rdc => write!(f, "SASYZCR2"),
         }
     }
 }
@@ -109,34 +113,40 @@ impl SasCompressionSignature {
 // #define SAS_FILE_HEADER_SIZE_64BIT 8192
 // #define SAS_DEFAULT_PAGE_SIZE      4096
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasFileHeaderSize {
     Bit32 = 1024,
     Bit64 = 8192,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasPageSize {
     Default = 4096,
 }
 
 // #define SAS_DEFAULT_STRING_ENCODING "WINDOWS-1252"
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasStringEncoding {
     Windows1252,
 }
 
 impl SasStringEncoding {
-    pub fn to_string(&self) -> String {
-        match self {
-            SasStringEncoding::Windows1252 => "WINDOWS-1252".to_string(),
-        }
-    }
-
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         SasStringEncoding::Windows1252
     }
 }
 
+impl std::fmt::Display for SasStringEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SasStringEncoding::Windows1252 => write!(f, "WINDOWS-1252"),
+        }
+    }
+}
+
 // unsigned char sas7bdat_magic_number[32] = {
 // 0x00, 0x00, 0x00, 0x00,   0x00, 0x00, 0x00, 0x00,
 // 0x00, 0x00, 0x00, 0x00,   0xc2, 0xea, 0x81, 0x60,
@@ -151,19 +161,13 @@ impl SasStringEncoding {
 // 0x09, 0xc7, 0x31, 0x8c,   0x18, 0x1f, 0x10, 0x11
 // };
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SasFileMagicNumber {
     Sas7bdat,
     Sas7bcat,
 }
 
 impl SasFileMagicNumber {
-    pub fn to_string(&self) -> String {
-        match self {
-            SasFileMagicNumber::Sas7bdat => "sas7bdat".to_string(),
-            SasFileMagicNumber::Sas7bcat => "sas7bcat".to_string(),
-        }
-    }
-
     pub fn get_array(&self) -> [u8; 32] {
         match self {
             SasFileMagicNumber::Sas7bdat => [
@@ -179,3 +183,202 @@ impl SasFileMagicNumber {
         }
     }
 }
+
+impl std::fmt::Display for SasFileMagicNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SasFileMagicNumber::Sas7bdat => write!(f, "sas7bdat"),
+            SasFileMagicNumber::Sas7bcat => write!(f, "sas7bcat"),
+        }
+    }
+}
+
+/// The kind of a missing numeric value: the system missing value `.`, or one of the 27 special
+/// missing values `._` and `.A` to `.Z`, which SAS keeps apart so that eg "refused" and
+/// "don't know" answers can be told from each other.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasMissing {
+    System,
+    Underscore,
+    Letter(char),
+}
+
+impl SasMissing {
+    /// Resolve the tag byte of a missing value. Tags in either of the two schemes readstat
+    /// accepts are resolved, and anything that is not a valid tag is system missing.
+    ///
+    /// # Original C function
+    /// ```c
+    /// void sas_assign_tag(readstat_value_t *value, uint8_t tag) {
+    ///    /* We accommodate two tag schemes. In the first, the tag is an ASCII code
+    ///     * given by uint8_t tag above. System missing is represented by an ASCII
+    ///     * period. In the second scheme, (tag-2) is an offset from 'A', except when
+    ///     * tag == 0, in which case it represents an underscore, or tag == 1, in
+    ///     * which case it represents system-missing.
+    ///     */
+    ///    if (tag == 0) {
+    ///        tag = '_';
+    ///    } else if (tag >= 2 && tag < 28) {
+    ///        tag = 'A' + (tag - 2);
+    ///    }
+    ///    if (sas_validate_tag(tag) == READSTAT_OK) {
+    ///        value->tag = tag;
+    ///        value->is_tagged_missing = 1;
+    ///    } else {
+    ///        value->tag = 0;
+    ///        value->is_system_missing = 1;
+    ///    }
+    ///}
+    /// ```
+    pub fn from_tag(tag: u8) -> Self {
+        match tag {
+            0 | b'_' => SasMissing::Underscore,
+            2..=27 => SasMissing::Letter((b'A' + (tag - 2)) as char),
+            b'A'..=b'Z' => SasMissing::Letter(tag as char),
+            _ => SasMissing::System,
+        }
+    }
+
+    /// Decode the kind of missing value held by the bits of a double, as SAS stores them: the
+    /// complement of the tag is in the byte below the top two. Returns None if the double is
+    /// not NaN, ie not missing.
+    pub fn from_nan_bits(bits: u64) -> Option<Self> {
+        if !f64::from_bits(bits).is_nan() {
+            return None;
+        }
+        Some(Self::from_tag(!((bits >> 40) & 0xFF) as u8))
+    }
+
+    /// The character SAS displays after the period, eg `A` for `.A` or `_` for `._`, or None
+    /// for system missing.
+    pub fn tag(&self) -> Option<char> {
+        match self {
+            SasMissing::System => None,
+            SasMissing::Underscore => Some('_'),
+            SasMissing::Letter(letter) => Some(*letter),
+        }
+    }
+}
+
+/// Missing values are displayed the way SAS writes them, eg `.`, `._` or `.R`.
+impl std::fmt::Display for SasMissing {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.tag() {
+            Some(tag) => write!(f, ".{}", tag),
+            None => write!(f, "."),
+        }
+    }
+}
+
+/// What a numeric value holds, according to its display format. SAS stores dates as days and
+/// datetimes as seconds since 1960-01-01, and times as seconds since midnight.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasTemporalKind {
+    Date,
+    DateTime,
+    Time,
+}
+
+/// A decoded date, datetime or time value. Datetimes and times keep fractions of a second to
+/// the microsecond. `Raw` holds a value that could not be decoded, kept as it is stored under
+/// `SasTemporalPolicy::Raw`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasTemporalValue {
+    Date(chrono::NaiveDate),
+    DateTime(chrono::NaiveDateTime),
+    Time(chrono::NaiveTime),
+    Raw(f64),
+}
+
+/// A date, datetime or time rendered for text output by `format_iso8601_with`: its ISO 8601
+/// text, or the stored number when `SasTemporalPolicy::Raw` keeps a value that cannot be
+/// rendered.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasTemporalText {
+    Iso8601(String),
+    Raw(f64),
+}
+
+/// What to do with a date, datetime or time that cannot be decoded: an infinite value, one
+/// outside the range chrono can represent, a time outside a day, or a date that is not a
+/// whole number of days.
+///
+/// - `Error`: fail with `ReadStatError::BadTimestampValue`.
+/// - `Missing`: treat the value as missing.
+/// - `Clamp`: use the nearest value that can be represented; fractional days are truncated.
+/// - `Raw`: keep the stored number as `SasTemporalValue::Raw`.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasTemporalPolicy {
+    #[default]
+    Error,
+    Missing,
+    Clamp,
+    Raw,
+}
+
+/// What kind of values a format displays.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasFormatCategory {
+    /// Days since 1960-01-01, eg `DATE9.`.
+    Date,
+
+    /// Seconds since 1960-01-01T00:00:00, eg `DATETIME20.`.
+    DateTime,
+
+    /// Seconds since midnight, eg `TIME8.`.
+    Time,
+
+    /// Other numbers, eg `BEST12.` or `COMMA12.2`.
+    Numeric,
+
+    /// Character values, eg `$CHAR20.`.
+    Character,
+
+    /// A format created by `PROC FORMAT`, whose definition lives in a format catalog.
+    UserDefined,
+}
+
+/// How a missing numeric value is written to text outputs such as JSON Lines.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasMissingStyle {
+    /// A JSON `null`, or an empty field in delimited outputs.
+    Null,
+
+    /// The SAS notation for the value: `.` for system missing and `.A` to `.Z` or `._` for
+    /// special missing values.
+    SasNotation,
+
+    /// The given text, whatever the kind of missing value.
+    Text(String),
+}
+
+/// The order in which a parallel read returns rows. Pages are decoded concurrently, so keeping
+/// file order means holding back pages that finish before an earlier one.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasRowOrder {
+    /// Rows are returned in file order, as a sequential read would.
+    Ordered,
+
+    /// Rows of a page are returned together as soon as the page is decoded, in whatever order
+    /// pages finish.
+    Unordered,
+}
+
+/// What the fields of a deserialized record are matched against.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SasFieldNames {
+    /// Variable names, eg `CLAIM_ID`.
+    Name,
+
+    /// Variable labels, eg `Claim identifier`. Variables without a label are matched by name.
+    Label,
+}
@@ -0,0 +1,85 @@
+// typedef struct subheader_pointer_s {
+// uint64_t offset;
+// uint64_t len;
+// unsigned char compression;
+// unsigned char is_compressed_data;
+// } subheader_pointer_t;
+
+use crate::types::SasCompression;
+use derive_builder::Builder;
+
+/// One entry of the subheader pointer table that follows a page header. The pointer gives the
+/// location of a subheader within its page and whether the bytes it points to are a compressed
+/// row rather than metadata.
+#[derive(Debug, Clone, PartialEq, Builder)]
+pub struct SasSubheaderPointer {
+    pub offset: u64,
+    pub length: u64,
+    pub compression: u8,
+    pub is_compressed_data: bool,
+}
+
+impl SasSubheaderPointer {
+    pub fn new(offset: u64, length: u64, compression: u8, is_compressed_data: bool) -> Self {
+        Self {
+            offset,
+            length,
+            compression,
+            is_compressed_data,
+        }
+    }
+
+    pub fn builder() -> SasSubheaderPointerBuilder {
+        SasSubheaderPointerBuilder::default()
+    }
+
+    /// Return true if the pointer does not refer to any usable bytes, either because it is
+    /// empty or because SAS marked the subheader as truncated.
+    pub fn is_empty(&self) -> bool {
+        self.length == 0 || self.compression == SasCompression::Trunc as u8
+    }
+
+    /// Return true if the subheader holds a single row compressed with the file's row
+    /// compression scheme.
+    pub fn is_compressed_row(&self) -> bool {
+        self.compression == SasCompression::Row as u8
+    }
+}
+
+impl SasSubheaderPointerBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            offset: Some(0),
+            length: Some(0),
+            compression: Some(SasCompression::None as u8),
+            is_compressed_data: Some(false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sas_subheader_pointer_builder_default() {
+        let pointer = SasSubheaderPointer::builder().build().unwrap();
+        assert_eq!(pointer.offset, 0);
+        assert!(pointer.is_empty());
+        assert!(!pointer.is_compressed_row());
+    }
+
+    #[test]
+    fn test_sas_subheader_pointer_truncated_is_empty() {
+        let pointer = SasSubheaderPointer::new(100, 24, SasCompression::Trunc as u8, false);
+        assert!(pointer.is_empty());
+    }
+
+    #[test]
+    fn test_sas_subheader_pointer_compressed_row() {
+        let pointer = SasSubheaderPointer::new(100, 24, SasCompression::Row as u8, true);
+        assert!(!pointer.is_empty());
+        assert!(pointer.is_compressed_row());
+    }
+}
@@ -26,6 +26,7 @@ pub struct SasSubheaderSignature {
 }
 
 impl SasSubheaderSignature {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         row_size: u32,
         column_size: u32,
@@ -50,6 +51,7 @@ impl SasSubheaderSignature {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Self {
         Self {
             row_size: 0xF7F7F7F7,
@@ -5,12 +5,14 @@ use derive_builder::Builder;
 /// Wrapper around a normal string type that represents a SAS table name, and validates that it
 /// is a valid SAS table name
 #[derive(Debug, PartialEq, Clone, Builder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SasTableName {
     pub name: String,
 }
 
 impl SasTableName {
     /// Create a new SAS table name
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(name: &str) -> Result<Self, &'static str> {
         if name.len() > MAX_TABLE_NAME_LENGTH {
             return Err("Table name is too long");
@@ -0,0 +1,54 @@
+// typedef struct text_ref_s {
+// uint16_t    index;
+// uint16_t    offset;
+// uint16_t    length;
+// } text_ref_t;
+
+use derive_builder::Builder;
+
+/// A reference into one of the column text subheaders of a sas7bdat file. Column names, formats,
+/// labels and the file label are all stored as text refs rather than inline strings.
+#[derive(Debug, Clone, Copy, PartialEq, Builder)]
+pub struct SasTextRef {
+    pub index: u16,
+    pub offset: u16,
+    pub length: u16,
+}
+
+impl SasTextRef {
+    pub fn new(index: u16, offset: u16, length: u16) -> Self {
+        Self {
+            index,
+            offset,
+            length,
+        }
+    }
+
+    pub fn builder() -> SasTextRefBuilder {
+        SasTextRefBuilder::default()
+    }
+}
+
+impl SasTextRefBuilder {
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> Self {
+        Self {
+            index: Some(0),
+            offset: Some(0),
+            length: Some(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sas_text_ref_new() {
+        let text_ref = SasTextRef::new(1, 2, 3);
+        assert_eq!(text_ref.index, 1);
+        assert_eq!(text_ref.offset, 2);
+        assert_eq!(text_ref.length, 3);
+    }
+}
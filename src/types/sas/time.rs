@@ -1,76 +1,32 @@
-use chrono::DateTime;
-
-/// The number of seconds between the Unix epoch and the SAS epoch.
-fn get_sas_epoch_offset() -> i128 {
-    let sas_epoch = DateTime::parse_from_rfc3339("1960-01-01T00:00:00Z").unwrap();
-    let unix_epoch = DateTime::parse_from_rfc3339("1970-01-01T00:00:00Z").unwrap();
-    (sas_epoch - unix_epoch + chrono::Duration::days(1)).num_seconds() as i128
-}
-
-/// Max and min values for a SAS timestamp.
-fn get_max_long() -> i128 {
-    2_i128.pow(63) - 1
-}
-
-fn get_min_long() -> i128 {
-    -(2_i128.pow(63)) - 1
-}
-
-/// Convert a SAS timestamp to a Unix timestamp.
-pub fn sas_to_unix(sas_timestamp: i128) -> i128 {
-    let epoch_offset = get_sas_epoch_offset();
-    let unix_timestamp = sas_timestamp - epoch_offset;
-
-    let max_long = get_max_long();
-    let min_long = get_min_long();
-
-    println!("max long: {} | min_long: {}", max_long, min_long);
-    println!("unix_timestamp: {}", unix_timestamp);
-
-    if unix_timestamp > max_long {
-        return max_long;
-    } else if unix_timestamp < min_long {
-        return min_long;
-    } else {
-        return unix_timestamp;
-    }
+/// The SAS epoch, 1960-01-01T00:00:00Z, in seconds since the Unix epoch.
+pub const SAS_EPOCH_UNIX_SECONDS: i64 = -315_619_200;
+
+/// Convert a SAS timestamp, in whole seconds since 1960-01-01T00:00:00, to a Unix timestamp.
+/// Returns None if the result does not fit in an `i64`.
+pub fn sas_to_unix(sas_timestamp: i128) -> Option<i64> {
+    sas_timestamp
+        .checked_add(SAS_EPOCH_UNIX_SECONDS as i128)
+        .and_then(|seconds| i64::try_from(seconds).ok())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Duration;
-
-    #[test]
-    fn test_offset() {
-        assert_eq!(get_sas_epoch_offset(), -((10 * 365 + 2) * 24 * 60 * 60)); // Add 2 days for leap
-                                                                              // years
-    }
 
     #[test]
     fn test_sas_to_unix() {
-        let sas_timestamp = 0;
-        assert_eq!(sas_to_unix(sas_timestamp), ((10 * 365 + 2) * 24 * 60 * 60));
-    }
-
-    #[test]
-    fn test_sas_to_unix_max() {
-        let sas_timestamp = get_max_long();
-        assert_eq!(sas_to_unix(sas_timestamp), get_max_long());
+        // 1960 and 1964 and 1968 are leap years
+        assert_eq!(sas_to_unix(0), Some(-(10 * 365 + 3) * 24 * 60 * 60));
+        assert_eq!(sas_to_unix(315_619_200), Some(0));
+        assert_eq!(sas_to_unix(2_022_327_900), Some(1_706_708_700));
     }
 
     #[test]
-    fn test_sas_to_unix_min() {
-        let sas_timestamp = get_min_long();
-        let timestamp_from_min_long =
-            get_min_long() + Duration::days(10 * 365 + 2).num_seconds() as i128;
-        println!(
-            "n days between sas and getminlong: {}",
-            Duration::seconds(
-                (sas_to_unix(sas_timestamp) as i128 - timestamp_from_min_long) as i64
-            )
-            .num_days()
-        );
-        assert_eq!(sas_to_unix(sas_timestamp), timestamp_from_min_long);
+    fn test_sas_to_unix_out_of_range() {
+        assert_eq!(sas_to_unix(i64::MAX as i128 + 315_619_200), Some(i64::MAX));
+        assert_eq!(sas_to_unix(i64::MAX as i128 + 315_619_201), None);
+        assert_eq!(sas_to_unix(i64::MIN as i128), None);
+        assert_eq!(sas_to_unix(i128::MIN), None);
+        assert_eq!(sas_to_unix(i128::MAX), None);
     }
 }